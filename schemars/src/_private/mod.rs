@@ -9,10 +9,40 @@ mod regex_syntax;
 mod rustdoc;
 
 pub extern crate alloc;
+#[cfg(feature = "registry")]
+pub extern crate linkme;
 pub extern crate serde_json;
 
 pub use rustdoc::get_title_and_description;
 
+#[cfg(feature = "tool")]
+pub fn new_tool_schema<P: JsonSchema, R: ?Sized + JsonSchema>(
+    name: &str,
+    settings: &crate::generate::SchemaSettings,
+) -> crate::ToolSchema {
+    let parameters = settings
+        .clone()
+        .for_deserialize()
+        .into_generator()
+        .into_root_schema_for::<P>();
+    let returns = settings
+        .clone()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<R>();
+    let description = parameters
+        .get("description")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned);
+
+    crate::ToolSchema {
+        name: name.to_owned(),
+        description,
+        parameters,
+        returns,
+    }
+}
+
 pub fn json_schema_for_internally_tagged_enum_newtype_variant<T: ?Sized + JsonSchema>(
     generator: &mut SchemaGenerator,
 ) -> Schema {
@@ -165,6 +195,24 @@ impl Transform for AllowUnknownProperties {
     }
 }
 
+/// Creates a schema for the tag of a `#[serde(other)]` variant, which matches any string other than
+/// the given tags of the enum's other variants.
+#[must_use]
+pub fn new_catch_all_enum_variant(other_tags: &[&str]) -> Schema {
+    if other_tags.is_empty() {
+        return json_schema!({
+            "type": "string",
+        });
+    }
+
+    json_schema!({
+        "type": "string",
+        "not": {
+            "enum": other_tags,
+        },
+    })
+}
+
 /// Hack to simulate specialization:
 /// `MaybeSerializeWrapper(x).maybe_to_value()` will resolve to either
 /// - The inherent method `MaybeSerializeWrapper::maybe_to_value(...)` if x is `Serialize`
@@ -198,10 +246,19 @@ impl<T: Serialize> MaybeSerializeWrapper<T> {
 
 /// Create a schema for a unit enum variant
 #[must_use]
-pub fn new_unit_enum_variant(variant: &str) -> Schema {
+pub fn new_unit_enum_variant(variant: &str, aliases: &[&str]) -> Schema {
+    if aliases.is_empty() {
+        return json_schema!({
+            "type": "string",
+            "const": variant,
+        });
+    }
+
+    let mut values = vec![variant];
+    values.extend(aliases);
     json_schema!({
         "type": "string",
-        "const": variant,
+        "enum": values,
     })
 }
 
@@ -243,7 +300,20 @@ impl<T: JsonSchema + ?Sized> MaybeJsonSchemaWrapper<T> {
 /// Create a schema for an externally tagged enum variant
 #[allow(clippy::needless_pass_by_value)]
 #[must_use]
-pub fn new_externally_tagged_enum_variant(variant: &str, sub_schema: Schema) -> Schema {
+pub fn new_externally_tagged_enum_variant(
+    variant: &str,
+    aliases: &[&str],
+    sub_schema: Schema,
+) -> Schema {
+    if !aliases.is_empty() {
+        let mut schema = json_schema!({
+            "type": "object",
+            "additionalProperties": false,
+        });
+        insert_object_property(&mut schema, variant, aliases, false, sub_schema);
+        return schema;
+    }
+
     // TODO: this can be optimised by inserting the `sub_schema` as a `Value` rather than
     // using the `json_schema!` macro which borrows and serializes the sub_schema
     json_schema!({
@@ -260,7 +330,7 @@ pub fn new_externally_tagged_enum_variant(variant: &str, sub_schema: Schema) ->
 pub fn apply_internal_enum_variant_tag(
     schema: &mut Schema,
     tag_name: &str,
-    variant: &str,
+    tag_schema: Schema,
     deny_unknown_fields: bool,
 ) {
     let obj = schema.ensure_object();
@@ -273,13 +343,7 @@ pub fn apply_internal_enum_variant_tag(
         .or_insert(Value::Object(Map::new()))
         .as_object_mut()
     {
-        properties.insert(
-            tag_name.to_string(),
-            json!({
-                "type": "string",
-                "const": variant
-            }),
-        );
+        properties.insert(tag_name.to_string(), tag_schema.to_value());
     }
 
     if let Some(required) = obj
@@ -295,9 +359,60 @@ pub fn apply_internal_enum_variant_tag(
     }
 }
 
+/// Adds an OpenAPI `discriminator` to the `oneOf` schema of an internally tagged enum, mapping the
+/// tag values of each `$ref`ed variant to its definition. The schema is left unchanged if any
+/// variant does not have string tag values.
+pub fn add_discriminator(generator: &SchemaGenerator, schema: &mut Schema, tag_name: &str) {
+    let Some(Value::Array(variants)) = schema.get("oneOf") else {
+        return;
+    };
+    if variants.is_empty() {
+        return;
+    }
+
+    let mut mapping = Map::new();
+    for variant in variants {
+        let reference = variant.get("$ref").and_then(Value::as_str);
+        let variant = match reference {
+            Some(reference) => generator.definition_for_ref(reference),
+            None => Some(variant),
+        };
+
+        let Some(tag_schema) = variant
+            .and_then(|v| v.get("properties"))
+            .and_then(|p| p.get(tag_name))
+        else {
+            return;
+        };
+
+        let tag_values = match (tag_schema.get("const"), tag_schema.get("enum")) {
+            (Some(value), _) => core::slice::from_ref(value),
+            (None, Some(Value::Array(values))) => values.as_slice(),
+            _ => return,
+        };
+
+        for value in tag_values {
+            let Some(value) = value.as_str() else {
+                return;
+            };
+            if let Some(reference) = reference {
+                mapping.insert(value.to_owned(), reference.into());
+            }
+        }
+    }
+
+    let mut discriminator = Map::new();
+    discriminator.insert("propertyName".to_owned(), tag_name.into());
+    if !mapping.is_empty() {
+        discriminator.insert("mapping".to_owned(), mapping.into());
+    }
+    schema.insert("discriminator".to_owned(), discriminator.into());
+}
+
 pub fn insert_object_property(
     schema: &mut Schema,
     key: &str,
+    aliases: &[&str],
     is_optional: bool,
     sub_schema: Schema,
 ) {
@@ -307,7 +422,48 @@ pub fn insert_object_property(
         .or_insert(Value::Object(Map::new()))
         .as_object_mut()
     {
-        properties.insert(key.to_owned(), sub_schema.into());
+        if aliases.is_empty() {
+            properties.insert(key.to_owned(), sub_schema.into());
+        } else {
+            for name in core::iter::once(&key).chain(aliases) {
+                properties.insert((*name).to_owned(), sub_schema.as_value().clone());
+            }
+        }
+    }
+
+    if !aliases.is_empty() {
+        // serde accepts a field under any one of its names, but rejects it if it appears under
+        // more than one of them.
+        let mut names = vec![key];
+        names.extend(aliases);
+        let constraint = if is_optional {
+            let mut pairs = Vec::new();
+            for (i, a) in names.iter().enumerate() {
+                for b in &names[i + 1..] {
+                    pairs.push(json!({ "required": [a, b] }));
+                }
+            }
+            if pairs.len() == 1 {
+                json!({ "not": pairs.remove(0) })
+            } else {
+                json!({ "not": { "anyOf": pairs } })
+            }
+        } else {
+            let one_of: Vec<_> = names
+                .iter()
+                .map(|name| json!({ "required": [name] }))
+                .collect();
+            json!({ "oneOf": one_of })
+        };
+
+        if let Some(all_of) = obj
+            .entry("allOf")
+            .or_insert(Value::Array(Vec::new()))
+            .as_array_mut()
+        {
+            all_of.push(constraint);
+        }
+        return;
     }
 
     if !is_optional {
@@ -480,6 +636,7 @@ pub(crate) fn allow_null(generator: &mut SchemaGenerator, schema: &mut Schema) {
                             array.push(null);
                         }
                     }
+                    #[allow(clippy::collapsible_match)]
                     Value::String(string) => {
                         if string != "null" {
                             let current_type = core::mem::take(string).into();
@@ -507,6 +664,31 @@ pub(crate) fn allow_null(generator: &mut SchemaGenerator, schema: &mut Schema) {
     }
 }
 
+#[cfg(feature = "registry")]
+#[linkme::distributed_slice]
+pub static REGISTERED_TYPES: [crate::registry::RegisteredType];
+
+/// Adds the given type to the `schemars::registry`, if the `registry` feature is enabled.
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _schemars_register {
+    ($ty:ty, $group:expr) => {
+        #[$crate::_private::linkme::distributed_slice($crate::_private::REGISTERED_TYPES)]
+        #[linkme(crate = $crate::_private::linkme)]
+        static REGISTERED_TYPE: $crate::registry::RegisteredType =
+            $crate::registry::RegisteredType::new::<$ty>($group);
+    };
+}
+
+/// Adds the given type to the `schemars::registry`, if the `registry` feature is enabled.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _schemars_register {
+    ($ty:ty, $group:expr) => {};
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
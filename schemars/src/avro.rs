@@ -0,0 +1,617 @@
+/*!
+Conversion of a [`Schema`] into an [Apache Avro](https://avro.apache.org/docs/1.12.0/specification/)
+schema.
+
+This module is only available when the `avro` feature flag is enabled.
+
+The Avro schema is returned in its JSON form, as a [`Value`]. Schemas are converted as follows:
+- object schemas with `properties` become `record`s. Properties that are not `required` become
+  unions with `"null"`, with a default value of `null`.
+- object schemas with only `additionalProperties` (e.g. for maps) become `map`s.
+- schemas with `enum` values or `oneOf`/`anyOf` subschemas that only allow strings (e.g. for
+  unit-only enums) become `enum`s.
+- other `oneOf`/`anyOf` schemas, and schemas with multiple `type`s (e.g. for `Option<T>`) become
+  unions. Any `"null"` branch is moved to the start of the union, as is conventional in Avro.
+- integers with a `format` of `int8`, `int16`, `int32`, `uint8` or `uint16` become `int`s, and
+  other integers become `long`s. Note that `u64` and `usize` values greater than `i64::MAX`
+  cannot be represented by a `long`.
+- numbers with a `format` of `float` become `float`s, and other numbers become `double`s.
+- strings with a `format` of `uuid` become `string`s with a `uuid` logical type.
+
+Each definition that becomes a named type (`record` or `enum`) is named after its key in the
+definitions, and is defined where it is first used - subsequent uses (including recursive ones)
+refer to it by name. Named types that do not come from a definition are named after the type
+that contains them, e.g. a record for an internally tagged enum variant `Circle` within the
+`Shape` enum is named `ShapeCircle`. `description`s become `doc`s on named types and fields.
+
+Keywords that only restrict values (such as `minimum` or `pattern`) are ignored, and a `const`
+value is represented by its type. Schemas that have no Avro equivalent, such as tuples or
+schemas that allow any value, cause an [`AvroError`].
+
+# Example
+```
+use schemars::{avro, JsonSchema, SchemaGenerator};
+use serde_json::json;
+
+/// A user of the application.
+#[derive(JsonSchema)]
+struct User {
+    id: u64,
+    name: String,
+    nickname: Option<String>,
+    role: Role,
+}
+
+#[derive(JsonSchema)]
+enum Role {
+    Admin,
+    Member,
+}
+
+let mut generator = SchemaGenerator::default();
+let schema = avro::schema_for::<User>(&mut generator).unwrap();
+
+assert_eq!(
+    schema,
+    json!({
+        "type": "record",
+        "name": "User",
+        "doc": "A user of the application.",
+        "fields": [
+            { "name": "id", "type": "long" },
+            { "name": "name", "type": "string" },
+            { "name": "nickname", "type": ["null", "string"], "default": null },
+            {
+                "name": "role",
+                "type": {
+                    "type": "enum",
+                    "name": "Role",
+                    "symbols": ["Admin", "Member"]
+                }
+            }
+        ]
+    })
+);
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::encoding::{percent_decode, push_pointer_segment};
+use crate::{JsonSchema, Schema, SchemaGenerator};
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+use serde_json::{json, Map, Value};
+
+/// Converts the schema for the type `T` (and all types that it references) into an Avro
+/// schema, using the given generator.
+///
+/// The root type is named after [`T::schema_name()`](JsonSchema::schema_name).
+pub fn schema_for<T: ?Sized + JsonSchema>(
+    generator: &mut SchemaGenerator,
+) -> Result<Value, AvroError> {
+    let root = generator.root_schema_for::<T>();
+    convert(&T::schema_name(), &root)
+}
+
+/// Converts a root schema into an Avro schema, giving it the name `root_name` if it becomes a
+/// named type.
+///
+/// Any `$ref`s within the schema are resolved against `root`, so it should contain its
+/// definitions (e.g. under `$defs`), as is the case for schemas returned by
+/// [`SchemaGenerator::root_schema_for`].
+pub fn convert(root_name: &str, root: &Schema) -> Result<Value, AvroError> {
+    let mut converter = Converter {
+        root,
+        named: BTreeMap::new(),
+        in_progress: BTreeSet::new(),
+        used_names: BTreeSet::new(),
+    };
+
+    converter.convert(root.as_value(), "", root_name, None)
+}
+
+/// An error describing why a [`Schema`] could not be converted into an Avro schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AvroError {
+    /// A JSON Pointer to the part of the schema that could not be converted.
+    ///
+    /// When conversion follows a `$ref`, this points to the referenced definition, e.g.
+    /// `/$defs/MyStruct/properties/my_tuple`.
+    pub path: String,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for AvroError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at \"{}\")", self.message, self.path)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AvroError {}
+
+fn error<T>(path: &str, message: impl Into<String>) -> Result<T, AvroError> {
+    Err(AvroError {
+        path: path.to_owned(),
+        message: message.into(),
+    })
+}
+
+struct Converter<'a> {
+    root: &'a Schema,
+    // Names of named types that have been defined for `$ref`s
+    named: BTreeMap<String, String>,
+    // `$ref`s whose definitions are currently being converted
+    in_progress: BTreeSet<String>,
+    used_names: BTreeSet<String>,
+}
+
+impl Converter<'_> {
+    // `name` is used if the schema becomes a named type. `reference` is the `$ref` that the
+    // schema is the definition of, if any.
+    fn convert(
+        &mut self,
+        schema: &Value,
+        path: &str,
+        name: &str,
+        reference: Option<&str>,
+    ) -> Result<Value, AvroError> {
+        let obj = match schema {
+            Value::Object(obj) => obj,
+            Value::Bool(true) => {
+                return error(path, "schemas that allow any value are not supported")
+            }
+            Value::Bool(false) => {
+                return error(path, "schemas that allow no values are not supported")
+            }
+            _ => return error(path, "invalid schema"),
+        };
+
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            return self.convert_ref(reference, path);
+        }
+
+        for keyword in ["allOf", "not", "if", "prefixItems", "patternProperties"] {
+            if obj.contains_key(keyword) {
+                let mut path = path.to_owned();
+                push_pointer_segment(&mut path, keyword);
+                return match keyword {
+                    "prefixItems" => error(&path, "tuples are not supported"),
+                    _ => error(&path, format!("`{keyword}` is not supported")),
+                };
+            }
+        }
+
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(subschemas) = obj.get(keyword) {
+                let mut path = path.to_owned();
+                push_pointer_segment(&mut path, keyword);
+                return self.convert_union(subschemas, &path, name, reference, obj);
+            }
+        }
+
+        if let Some(values) = obj.get("enum") {
+            let mut path = path.to_owned();
+            push_pointer_segment(&mut path, "enum");
+            let Some(values) = values.as_array() else {
+                return error(&path, "invalid `enum`");
+            };
+            return self.convert_enum(values, &path, name, reference, obj);
+        }
+
+        let types: Vec<&str> = match obj.get("type") {
+            Some(Value::String(ty)) => vec![ty],
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+            _ => match obj.get("const") {
+                Some(value) => vec![json_type(value)],
+                None => return error(path, "schemas without a `type` are not supported"),
+            },
+        };
+
+        let mut branches = Vec::with_capacity(types.len());
+        for ty in types {
+            branches.push(self.convert_type(ty, obj, path, name, reference)?);
+        }
+        union(branches, path)
+    }
+
+    fn convert_ref(&mut self, reference: &str, path: &str) -> Result<Value, AvroError> {
+        if let Some(name) = self.named.get(reference) {
+            return Ok(Value::String(name.clone()));
+        }
+
+        let Some(target) = self.root.resolve_ref(reference) else {
+            return error(path, format!("could not resolve `$ref` \"{reference}\""));
+        };
+
+        if self.in_progress.contains(reference) {
+            return error(
+                path,
+                format!("recursive schema \"{reference}\" must be an object schema"),
+            );
+        }
+
+        let def_path = percent_decode(&reference[1..]).unwrap_or_default();
+        let def_name = def_path
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .replace("~1", "/")
+            .replace("~0", "~");
+
+        self.in_progress.insert(reference.to_owned());
+        let result = self.convert(target.as_value(), &def_path, &def_name, Some(reference));
+        self.in_progress.remove(reference);
+
+        result
+    }
+
+    fn convert_type(
+        &mut self,
+        ty: &str,
+        obj: &Map<String, Value>,
+        path: &str,
+        name: &str,
+        reference: Option<&str>,
+    ) -> Result<Value, AvroError> {
+        let format = obj.get("format").and_then(Value::as_str);
+
+        Ok(match ty {
+            "null" | "boolean" => ty.into(),
+            "integer" => match format {
+                Some("int8" | "int16" | "int32" | "uint8" | "uint16") => "int".into(),
+                Some("int128" | "uint128") => {
+                    return error(path, "128-bit integers are not supported")
+                }
+                _ => "long".into(),
+            },
+            "number" => match format {
+                Some("float") => "float".into(),
+                _ => "double".into(),
+            },
+            "string" => match format {
+                Some("uuid") => json!({ "type": "string", "logicalType": "uuid" }),
+                _ => "string".into(),
+            },
+            "array" => {
+                let mut items_path = path.to_owned();
+                push_pointer_segment(&mut items_path, "items");
+                let Some(items) = obj.get("items") else {
+                    return error(path, "arrays without `items` are not supported");
+                };
+                if items.is_array() {
+                    return error(&items_path, "tuples are not supported");
+                }
+                let items = self.convert(items, &items_path, &format!("{name}Item"), None)?;
+                json!({ "type": "array", "items": items })
+            }
+            "object" => self.convert_object(obj, path, name, reference)?,
+            _ => return error(path, format!("unknown type \"{ty}\"")),
+        })
+    }
+
+    fn convert_object(
+        &mut self,
+        obj: &Map<String, Value>,
+        path: &str,
+        name: &str,
+        reference: Option<&str>,
+    ) -> Result<Value, AvroError> {
+        let properties = obj.get("properties").and_then(Value::as_object);
+        let additional_properties = obj
+            .get("additionalProperties")
+            .filter(|ap| **ap != Value::Bool(false));
+
+        let Some(properties) = properties.filter(|p| !p.is_empty()) else {
+            let mut values_path = path.to_owned();
+            push_pointer_segment(&mut values_path, "additionalProperties");
+            return match additional_properties {
+                Some(values) => {
+                    let values =
+                        self.convert(values, &values_path, &format!("{name}Value"), None)?;
+                    Ok(json!({ "type": "map", "values": values }))
+                }
+                None if properties.is_some() || obj.contains_key("additionalProperties") => {
+                    // An object that must be empty, e.g. for a struct with no fields
+                    let mut record = self.new_named("record", obj, name, reference);
+                    record.insert("fields".into(), Value::Array(Vec::new()));
+                    Ok(Value::Object(record))
+                }
+                None => error(
+                    path,
+                    "objects without `properties` or `additionalProperties` are not supported",
+                ),
+            };
+        };
+
+        if additional_properties.is_some() {
+            return error(
+                path,
+                "objects with both `properties` and `additionalProperties` are not supported",
+            );
+        }
+
+        let required: BTreeSet<&str> = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+
+        // Register the record's name before converting its fields, so that recursive references
+        // can refer to it by name.
+        let mut record = self.new_named("record", obj, name, reference);
+        let record_name = record["name"].as_str().unwrap_or_default().to_owned();
+
+        let mut fields = Vec::with_capacity(properties.len());
+        for (property_name, property) in properties {
+            let mut property_path = path.to_owned();
+            push_pointer_segment(&mut property_path, "properties");
+            push_pointer_segment(&mut property_path, property_name);
+
+            if !is_avro_name(property_name) {
+                return error(
+                    &property_path,
+                    format!("property name \"{property_name}\" is not a valid Avro field name"),
+                );
+            }
+
+            let field_type = self.convert(
+                property,
+                &property_path,
+                &format!("{record_name}{}", pascal_case(property_name)),
+                None,
+            )?;
+
+            let mut field = Map::new();
+            field.insert("name".into(), property_name.clone().into());
+            if let Some(doc) = property.get("description") {
+                field.insert("doc".into(), doc.clone());
+            }
+            if required.contains(property_name.as_str()) {
+                field.insert("type".into(), field_type);
+            } else {
+                field.insert(
+                    "type".into(),
+                    union(vec!["null".into(), field_type], &property_path)?,
+                );
+                field.insert("default".into(), Value::Null);
+            }
+            fields.push(Value::Object(field));
+        }
+
+        record.insert("fields".into(), Value::Array(fields));
+        Ok(Value::Object(record))
+    }
+
+    fn convert_union(
+        &mut self,
+        subschemas: &Value,
+        path: &str,
+        name: &str,
+        reference: Option<&str>,
+        obj: &Map<String, Value>,
+    ) -> Result<Value, AvroError> {
+        let Some(subschemas) = subschemas.as_array() else {
+            return error(path, "invalid subschemas");
+        };
+
+        // A union of string values (e.g. for unit-only enums with documented variants) can be
+        // represented as an enum
+        let mut values = Vec::new();
+        for subschema in subschemas {
+            match (subschema.get("const"), subschema.get("enum")) {
+                (Some(value), _) => values.push(value.clone()),
+                (None, Some(Value::Array(enum_values))) => values.extend(enum_values.clone()),
+                _ => {
+                    values.clear();
+                    break;
+                }
+            }
+        }
+        if !values.is_empty() && values.iter().all(|v| v.is_string() || v.is_null()) {
+            return self.convert_enum(&values, path, name, reference, obj);
+        }
+
+        let mut branches = Vec::with_capacity(subschemas.len());
+        for (index, subschema) in subschemas.iter().enumerate() {
+            let mut branch_path = path.to_owned();
+            push_pointer_segment(&mut branch_path, &index.to_string());
+            let branch_name = format!("{name}{}", variant_name(subschema, index));
+            branches.push(self.convert(subschema, &branch_path, &branch_name, None)?);
+        }
+
+        union(branches, path)
+    }
+
+    fn convert_enum(
+        &mut self,
+        values: &[Value],
+        path: &str,
+        name: &str,
+        reference: Option<&str>,
+        obj: &Map<String, Value>,
+    ) -> Result<Value, AvroError> {
+        let mut symbols = Vec::with_capacity(values.len());
+        let mut nullable = false;
+        for value in values {
+            match value {
+                Value::Null => nullable = true,
+                Value::String(symbol) if is_avro_name(symbol) => {
+                    if !symbols.contains(value) {
+                        symbols.push(value.clone());
+                    }
+                }
+                Value::String(symbol) => {
+                    return error(
+                        path,
+                        format!("enum value \"{symbol}\" is not a valid Avro enum symbol"),
+                    )
+                }
+                _ => {
+                    return error(
+                        path,
+                        format!("non-string enum value {value} is not supported"),
+                    )
+                }
+            }
+        }
+
+        let mut branches = Vec::with_capacity(2);
+        if nullable {
+            branches.push("null".into());
+        }
+        if !symbols.is_empty() {
+            let mut named_enum = self.new_named("enum", obj, name, reference);
+            named_enum.insert("symbols".into(), Value::Array(symbols));
+            branches.push(Value::Object(named_enum));
+        }
+
+        union(branches, path)
+    }
+
+    // Creates a named type of the given kind (e.g. "record"), choosing a unique name based on
+    // `name`.
+    fn new_named(
+        &mut self,
+        kind: &str,
+        obj: &Map<String, Value>,
+        name: &str,
+        reference: Option<&str>,
+    ) -> Map<String, Value> {
+        let base_name = avro_name(name);
+        let mut unique_name = base_name.clone();
+        let mut suffix = 2;
+        while !self.used_names.insert(unique_name.clone()) {
+            unique_name = format!("{base_name}{suffix}");
+            suffix += 1;
+        }
+
+        if let Some(reference) = reference {
+            self.named.insert(reference.to_owned(), unique_name.clone());
+        }
+
+        let mut named = Map::new();
+        named.insert("type".into(), kind.into());
+        named.insert("name".into(), unique_name.into());
+        if let Some(doc) = obj.get("description") {
+            named.insert("doc".into(), doc.clone());
+        }
+        named
+    }
+}
+
+// Combines Avro schemas into a union, flattening nested unions and moving any `"null"` to the
+// start. Returns the single schema if there is only one.
+fn union(branches: Vec<Value>, path: &str) -> Result<Value, AvroError> {
+    let mut result: Vec<Value> = Vec::with_capacity(branches.len());
+    let mut keys = BTreeSet::new();
+
+    for branch in branches {
+        let flattened = match branch {
+            Value::Array(nested) => nested,
+            branch => vec![branch],
+        };
+        for branch in flattened {
+            if result.contains(&branch) {
+                continue;
+            }
+            let key = match &branch {
+                Value::String(name) => name.clone(),
+                Value::Object(obj) => match obj.get("name").or_else(|| obj.get("type")) {
+                    Some(Value::String(key)) => key.clone(),
+                    _ => String::new(),
+                },
+                _ => String::new(),
+            };
+            if !keys.insert(key.clone()) {
+                return error(
+                    path,
+                    format!("unions containing multiple `{key}` schemas are not supported"),
+                );
+            }
+            if branch == "null" {
+                result.insert(0, branch);
+            } else {
+                result.push(branch);
+            }
+        }
+    }
+
+    match result.len() {
+        0 => error(path, "schemas that allow no values are not supported"),
+        1 => Ok(result.swap_remove(0)),
+        _ => Ok(Value::Array(result)),
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+// Chooses a name for a union variant, e.g. the tag value of an internally tagged enum variant.
+fn variant_name(schema: &Value, index: usize) -> String {
+    if let Some(title) = schema.get("title").and_then(Value::as_str) {
+        return pascal_case(title);
+    }
+
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        if let Some(tag) = properties
+            .values()
+            .find_map(|p| p.get("const").and_then(Value::as_str))
+        {
+            return pascal_case(tag);
+        }
+
+        if let Some(Value::Array(required)) = schema.get("required") {
+            if let [Value::String(property)] = required.as_slice() {
+                return pascal_case(property);
+            }
+        }
+    }
+
+    format!("Variant{index}")
+}
+
+fn is_avro_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn avro_name(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        result.insert(0, '_');
+    }
+    result
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
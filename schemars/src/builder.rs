@@ -0,0 +1,450 @@
+use crate::_alloc_prelude::*;
+use crate::Schema;
+use serde_json::{Map, Number, Value};
+
+/// A builder for constructing a [`Schema`] without needing to spell out JSON Schema keywords.
+///
+/// A `SchemaBuilder` is usually created by one of the constructors on [`Schema`] such as
+/// [`Schema::object()`] or [`Schema::string()`]. Each method sets a keyword and returns the
+/// builder, and the finished schema can be retrieved using [`build()`](Self::build) or
+/// [`Into::into`]. Methods that accept subschemas take any `impl Into<Schema>`, so builders,
+/// schemas and bools can be passed to them directly.
+///
+/// # Example
+/// ```
+/// use schemars::{json_schema, Schema};
+///
+/// let schema: Schema = Schema::object()
+///     .property("id", Schema::string().format("uuid").max_length(36))
+///     .property("tags", Schema::array().items(Schema::string()).unique_items(true))
+///     .property("count", Schema::any_of([Schema::integer().minimum(0), Schema::null()]))
+///     .required("id")
+///     .additional_properties(false)
+///     .into();
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "object",
+///         "properties": {
+///             "id": { "type": "string", "format": "uuid", "maxLength": 36 },
+///             "tags": { "type": "array", "items": { "type": "string" }, "uniqueItems": true },
+///             "count": { "anyOf": [{ "type": "integer", "minimum": 0 }, { "type": "null" }] }
+///         },
+///         "required": ["id"],
+///         "additionalProperties": false
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SchemaBuilder(Schema);
+
+impl Schema {
+    /// Creates a builder for a schema with `"type": "object"`.
+    #[must_use]
+    pub fn object() -> SchemaBuilder {
+        SchemaBuilder::of_type("object")
+    }
+
+    /// Creates a builder for a schema with `"type": "array"`.
+    #[must_use]
+    pub fn array() -> SchemaBuilder {
+        SchemaBuilder::of_type("array")
+    }
+
+    /// Creates a builder for a schema with `"type": "string"`.
+    #[must_use]
+    pub fn string() -> SchemaBuilder {
+        SchemaBuilder::of_type("string")
+    }
+
+    /// Creates a builder for a schema with `"type": "integer"`.
+    #[must_use]
+    pub fn integer() -> SchemaBuilder {
+        SchemaBuilder::of_type("integer")
+    }
+
+    /// Creates a builder for a schema with `"type": "number"`.
+    #[must_use]
+    pub fn number() -> SchemaBuilder {
+        SchemaBuilder::of_type("number")
+    }
+
+    /// Creates a builder for a schema with `"type": "boolean"`.
+    #[must_use]
+    pub fn boolean() -> SchemaBuilder {
+        SchemaBuilder::of_type("boolean")
+    }
+
+    /// Creates a builder for a schema with `"type": "null"`.
+    #[must_use]
+    pub fn null() -> SchemaBuilder {
+        SchemaBuilder::of_type("null")
+    }
+
+    /// Creates a builder for a schema that only allows the given value, using `const`.
+    #[must_use]
+    pub fn constant(value: impl Into<Value>) -> SchemaBuilder {
+        SchemaBuilder::default().keyword("const", value)
+    }
+
+    /// Creates a builder for a schema that only allows the given values, using `enum`.
+    #[must_use]
+    pub fn enumeration<V: Into<Value>>(values: impl IntoIterator<Item = V>) -> SchemaBuilder {
+        SchemaBuilder::default().keyword(
+            "enum",
+            values.into_iter().map(Into::into).collect::<Vec<_>>(),
+        )
+    }
+
+    /// Creates a builder for a schema that requires values to be valid against all of the given
+    /// subschemas, using `allOf`.
+    #[must_use]
+    pub fn all_of<S: Into<Schema>>(subschemas: impl IntoIterator<Item = S>) -> SchemaBuilder {
+        SchemaBuilder::default().subschemas("allOf", subschemas)
+    }
+
+    /// Creates a builder for a schema that requires values to be valid against at least one of the
+    /// given subschemas, using `anyOf`.
+    #[must_use]
+    pub fn any_of<S: Into<Schema>>(subschemas: impl IntoIterator<Item = S>) -> SchemaBuilder {
+        SchemaBuilder::default().subschemas("anyOf", subschemas)
+    }
+
+    /// Creates a builder for a schema that requires values to be valid against exactly one of the
+    /// given subschemas, using `oneOf`.
+    #[must_use]
+    pub fn one_of<S: Into<Schema>>(subschemas: impl IntoIterator<Item = S>) -> SchemaBuilder {
+        SchemaBuilder::default().subschemas("oneOf", subschemas)
+    }
+
+    /// Returns an iterator over the names and subschemas of the schema's `properties`.
+    ///
+    /// Any property values that are not valid schemas are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::{json_schema, Schema};
+    ///
+    /// let schema = json_schema!({
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "a": { "type": "string" },
+    ///         "b": true
+    ///     }
+    /// });
+    ///
+    /// let names = schema.properties().map(|(name, _)| name).collect::<Vec<_>>();
+    /// assert_eq!(names, ["a", "b"]);
+    /// ```
+    pub fn properties(&self) -> impl Iterator<Item = (&str, &Schema)> {
+        self.get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, value)| Some((name.as_str(), value.try_into().ok()?)))
+    }
+
+    /// Returns an iterator over the names and mutable subschemas of the schema's `properties`.
+    ///
+    /// Any property values that are not valid schemas are skipped.
+    pub fn properties_mut(&mut self) -> impl Iterator<Item = (&str, &mut Schema)> {
+        self.get_mut("properties")
+            .and_then(Value::as_object_mut)
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, value)| Some((name.as_str(), value.try_into().ok()?)))
+    }
+
+    /// Returns the subschema of the property with the given name, if the schema has one.
+    #[must_use]
+    pub fn get_property(&self, name: &str) -> Option<&Schema> {
+        self.get("properties")?.get(name)?.try_into().ok()
+    }
+
+    /// Returns an iterator over the property names in the schema's `required` array.
+    pub fn required(&self) -> impl Iterator<Item = &str> {
+        self.get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+    }
+
+    /// Returns true if the property with the given name is in the schema's `required` array.
+    #[must_use]
+    pub fn is_required(&self, name: &str) -> bool {
+        self.required().any(|r| r == name)
+    }
+
+    /// Returns the schema's `items` subschema, if it has one.
+    ///
+    /// This returns `None` if `items` is an array of subschemas, as used by older versions of JSON
+    /// Schema for tuples.
+    #[must_use]
+    pub fn items(&self) -> Option<&Schema> {
+        self.get("items")?.try_into().ok()
+    }
+
+    /// Mutably borrows the schema's `items` subschema, if it has one.
+    #[must_use]
+    pub fn items_mut(&mut self) -> Option<&mut Schema> {
+        self.get_mut("items")?.try_into().ok()
+    }
+
+    /// Returns the schema's `additionalProperties` subschema, if it has one.
+    #[must_use]
+    pub fn additional_properties(&self) -> Option<&Schema> {
+        self.get("additionalProperties")?.try_into().ok()
+    }
+
+    /// Returns the schema's `$ref`, if it has one.
+    #[must_use]
+    pub fn reference(&self) -> Option<&str> {
+        self.get("$ref")?.as_str()
+    }
+}
+
+impl SchemaBuilder {
+    /// Creates a builder that modifies an existing schema.
+    #[must_use]
+    pub fn new(schema: Schema) -> Self {
+        Self(schema)
+    }
+
+    fn of_type(instance_type: &str) -> Self {
+        Self::default().keyword("type", instance_type)
+    }
+
+    /// Returns the built schema.
+    #[must_use]
+    pub fn build(self) -> Schema {
+        self.0
+    }
+
+    /// Sets an arbitrary keyword, replacing any previous value.
+    ///
+    /// This can be used for keywords that do not have a dedicated method, such as extension
+    /// keywords.
+    #[must_use]
+    pub fn keyword(mut self, keyword: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.0.insert(keyword.into(), value.into());
+        self
+    }
+
+    fn subschemas<S: Into<Schema>>(
+        self,
+        keyword: &str,
+        subschemas: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let subschemas = subschemas
+            .into_iter()
+            .map(|s| s.into().to_value())
+            .collect::<Vec<_>>();
+        self.keyword(keyword, subschemas)
+    }
+
+    fn map_entry(mut self, keyword: &str, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        let map = self
+            .0
+            .ensure_object()
+            .entry(keyword)
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !map.is_object() {
+            *map = Value::Object(Map::new());
+        }
+        if let Value::Object(map) = map {
+            map.insert(key.into(), value.into());
+        }
+        self
+    }
+
+    /// Allows `null` values in addition to the schema's current `type`(s).
+    ///
+    /// This has no effect if the schema does not have a `type`.
+    #[must_use]
+    pub fn nullable(mut self) -> Self {
+        match self.0.get_mut("type") {
+            Some(Value::String(ty)) if ty != "null" => {
+                let ty = core::mem::take(ty);
+                self.keyword("type", vec![ty, "null".to_owned()])
+            }
+            Some(Value::Array(types)) => {
+                if !types.iter().any(|t| t == "null") {
+                    types.push("null".into());
+                }
+                self
+            }
+            _ => self,
+        }
+    }
+
+    /// Sets the `title`.
+    #[must_use]
+    pub fn title(self, title: impl Into<String>) -> Self {
+        self.keyword("title", title.into())
+    }
+
+    /// Sets the `description`.
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        self.keyword("description", description.into())
+    }
+
+    /// Sets the `default` value.
+    #[must_use]
+    pub fn default_value(self, value: impl Into<Value>) -> Self {
+        self.keyword("default", value)
+    }
+
+    /// Sets `deprecated`.
+    #[must_use]
+    pub fn deprecated(self, deprecated: bool) -> Self {
+        self.keyword("deprecated", deprecated)
+    }
+
+    /// Sets the `format`.
+    #[must_use]
+    pub fn format(self, format: impl Into<String>) -> Self {
+        self.keyword("format", format.into())
+    }
+
+    /// Adds a subschema to `properties`, replacing any previous subschema for the property.
+    #[must_use]
+    pub fn property(self, name: impl Into<String>, schema: impl Into<Schema>) -> Self {
+        self.map_entry("properties", name, schema.into())
+    }
+
+    /// Adds a property name to `required`, if it is not already present.
+    #[must_use]
+    pub fn required(mut self, name: impl Into<String>) -> Self {
+        let name = Value::String(name.into());
+        let required = self
+            .0
+            .ensure_object()
+            .entry("required")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if !required.is_array() {
+            *required = Value::Array(Vec::new());
+        }
+        if let Value::Array(required) = required {
+            if !required.contains(&name) {
+                required.push(name);
+            }
+        }
+        self
+    }
+
+    /// Sets `additionalProperties`. Use `false` to disallow properties not listed in
+    /// [`property`](Self::property).
+    #[must_use]
+    pub fn additional_properties(self, schema: impl Into<Schema>) -> Self {
+        self.keyword("additionalProperties", schema.into())
+    }
+
+    /// Adds a subschema to `patternProperties`.
+    #[must_use]
+    pub fn pattern_property(self, pattern: impl Into<String>, schema: impl Into<Schema>) -> Self {
+        self.map_entry("patternProperties", pattern, schema.into())
+    }
+
+    /// Sets `minProperties`.
+    #[must_use]
+    pub fn min_properties(self, min: u64) -> Self {
+        self.keyword("minProperties", min)
+    }
+
+    /// Sets `maxProperties`.
+    #[must_use]
+    pub fn max_properties(self, max: u64) -> Self {
+        self.keyword("maxProperties", max)
+    }
+
+    /// Sets the `items` subschema.
+    #[must_use]
+    pub fn items(self, schema: impl Into<Schema>) -> Self {
+        self.keyword("items", schema.into())
+    }
+
+    /// Sets `minItems`.
+    #[must_use]
+    pub fn min_items(self, min: u64) -> Self {
+        self.keyword("minItems", min)
+    }
+
+    /// Sets `maxItems`.
+    #[must_use]
+    pub fn max_items(self, max: u64) -> Self {
+        self.keyword("maxItems", max)
+    }
+
+    /// Sets `uniqueItems`.
+    #[must_use]
+    pub fn unique_items(self, unique: bool) -> Self {
+        self.keyword("uniqueItems", unique)
+    }
+
+    /// Sets `minLength`.
+    #[must_use]
+    pub fn min_length(self, min: u64) -> Self {
+        self.keyword("minLength", min)
+    }
+
+    /// Sets `maxLength`.
+    #[must_use]
+    pub fn max_length(self, max: u64) -> Self {
+        self.keyword("maxLength", max)
+    }
+
+    /// Sets the `pattern`.
+    #[must_use]
+    pub fn pattern(self, pattern: impl Into<String>) -> Self {
+        self.keyword("pattern", pattern.into())
+    }
+
+    /// Sets the inclusive `minimum`.
+    ///
+    /// Floating-point values can be converted to a [`Number`] using [`Number::from_f64`].
+    #[must_use]
+    pub fn minimum(self, min: impl Into<Number>) -> Self {
+        self.keyword("minimum", min.into())
+    }
+
+    /// Sets the inclusive `maximum`.
+    ///
+    /// Floating-point values can be converted to a [`Number`] using [`Number::from_f64`].
+    #[must_use]
+    pub fn maximum(self, max: impl Into<Number>) -> Self {
+        self.keyword("maximum", max.into())
+    }
+
+    /// Sets the `exclusiveMinimum`.
+    #[must_use]
+    pub fn exclusive_minimum(self, min: impl Into<Number>) -> Self {
+        self.keyword("exclusiveMinimum", min.into())
+    }
+
+    /// Sets the `exclusiveMaximum`.
+    #[must_use]
+    pub fn exclusive_maximum(self, max: impl Into<Number>) -> Self {
+        self.keyword("exclusiveMaximum", max.into())
+    }
+
+    /// Sets `multipleOf`.
+    #[must_use]
+    pub fn multiple_of(self, divisor: impl Into<Number>) -> Self {
+        self.keyword("multipleOf", divisor.into())
+    }
+}
+
+impl From<SchemaBuilder> for Schema {
+    fn from(builder: SchemaBuilder) -> Self {
+        builder.0
+    }
+}
+
+impl From<Schema> for SchemaBuilder {
+    fn from(schema: Schema) -> Self {
+        Self(schema)
+    }
+}
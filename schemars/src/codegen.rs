@@ -0,0 +1,1350 @@
+/*!
+Generation of Rust source code from a [`Schema`].
+
+This module is only available when the `codegen` feature flag is enabled.
+
+The root schema is emitted as a type named after the root type, followed by a type for each
+definition that it (transitively) references. Every type derives `Serialize`, `Deserialize` and
+`JsonSchema`, with serde attributes inferred from the shape of its schema:
+- schemas describing objects with a fixed set of properties become structs. Properties that are
+  not `required` become `Option`s (or use `#[serde(default)]` when the schema specifies a default
+  value matching the field type's [`Default`] value), and `additionalProperties: false` becomes
+  `#[serde(deny_unknown_fields)]`.
+- schemas with `enum` string values become unit-only enums.
+- schemas with `oneOf`/`anyOf` become enums, using whichever of serde's [enum
+  representations](https://serde.rs/enum-representations.html) matches the variant schemas (i.e.
+  `#[serde(tag = "...")]`, `#[serde(tag = "...", content = "...")]` or `#[serde(untagged)]`).
+- property and variant names that are not valid Rust identifiers are given a `#[serde(rename)]`
+  attribute.
+- `title` and `description` become doc comments.
+
+Instance types are mapped to the types that schemars already knows, using the `format` keyword
+where possible - for example, `{ "type": "integer", "format": "uint8" }` becomes `u8`. String
+formats for types from other crates are only used if the corresponding schemars feature is
+enabled, so `{ "type": "string", "format": "uuid" }` becomes `uuid::Uuid` when the `uuid1` feature
+is enabled, or `String` otherwise.
+
+Subschemas that need their own type declaration, but are not definitions in the root schema
+(e.g. an object schema nested within a property), are emitted as types with the
+`#[schemars(inline)]` attribute, so that the schema of the generated type matches the original
+schema as closely as possible. For schemas generated by schemars, this means that the schema of
+the generated type is usually identical to the original schema. Keywords that have no equivalent
+in the generated code (such as `pattern`) are ignored, and schemas that cannot be represented by
+any other type become `serde_json::Value`.
+
+# Example
+```
+use schemars::{codegen, JsonSchema, SchemaGenerator};
+
+/// A user of the application.
+#[derive(JsonSchema)]
+struct User {
+    id: u64,
+    #[serde(rename = "displayName")]
+    display_name: String,
+    nickname: Option<String>,
+    role: Role,
+}
+
+#[derive(JsonSchema)]
+enum Role {
+    Admin,
+    Member,
+}
+
+let mut generator = SchemaGenerator::default();
+let source = codegen::rust_source_for::<User>(&mut generator);
+
+assert_eq!(
+    source,
+    r#"use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A user of the application.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct User {
+    pub id: u64,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    pub nickname: Option<String>,
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum Role {
+    Admin,
+    Member,
+}
+"#
+);
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::{JsonSchema, Schema, SchemaGenerator};
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use core::fmt::Write as _;
+use serde_json::{Map, Value};
+
+/// Generates Rust source code for the type `T` and all types that its schema references, using
+/// the given generator.
+///
+/// The root type is named after [`T::schema_name()`](JsonSchema::schema_name).
+#[must_use]
+pub fn rust_source_for<T: ?Sized + JsonSchema>(generator: &mut SchemaGenerator) -> String {
+    let root = generator.root_schema_for::<T>();
+    rust_source(&T::schema_name(), &root)
+}
+
+/// Generates Rust source code for a root schema, giving it the type name `root_name`.
+///
+/// Types are also generated for all definitions that the schema references. Any `$ref`s within
+/// the schema are resolved against `root`, so it should contain its definitions (e.g. under
+/// `$defs`), as is the case for schemas returned by [`SchemaGenerator::root_schema_for`].
+#[must_use]
+pub fn rust_source(root_name: &str, root: &Schema) -> String {
+    let mut emitter = Emitter {
+        root,
+        ref_names: BTreeMap::new(),
+        used_names: BTreeSet::new(),
+        pending: VecDeque::new(),
+        current_ref: "#".to_owned(),
+        output: "use schemars::JsonSchema;\nuse serde::{Deserialize, Serialize};\n".to_owned(),
+    };
+
+    let name = emitter.unique_type_name(root_name);
+    emitter.ref_names.insert("#".to_owned(), name.clone());
+    emitter.emit_item(&Item {
+        rename: (name != root_name).then(|| root_name.to_owned()),
+        name,
+        schema: root.as_value().clone(),
+        reference: "#".to_owned(),
+        inline: false,
+        is_root: true,
+    });
+
+    while let Some(item) = emitter.pending.pop_front() {
+        emitter.emit_item(&item);
+    }
+
+    emitter.output
+}
+
+const DERIVES: &str = "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]";
+
+// Keywords that do not affect which values are valid for a schema
+const ANNOTATION_KEYWORDS: [&str; 9] = [
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "$comment",
+    "$schema",
+];
+
+struct Emitter<'a> {
+    root: &'a Schema,
+    // Names of the types emitted for each `$ref`
+    ref_names: BTreeMap<String, String>,
+    used_names: BTreeSet<String>,
+    pending: VecDeque<Item>,
+    // The `$ref` of the definition currently being emitted, used to determine whether references
+    // to other definitions need to be boxed
+    current_ref: String,
+    output: String,
+}
+
+// A type declaration that has yet to be emitted
+struct Item {
+    name: String,
+    rename: Option<String>,
+    schema: Value,
+    // The `$ref` of the definition that this item was generated for (which is the definition that
+    // contains the item's schema, for inline items)
+    reference: String,
+    inline: bool,
+    is_root: bool,
+}
+
+struct Field {
+    doc: Option<String>,
+    deprecated: bool,
+    serde_attrs: Vec<String>,
+    schemars_attrs: Vec<String>,
+    ident: String,
+    ty: String,
+}
+
+struct Variant {
+    doc: Option<String>,
+    rename: Option<String>,
+    ident: String,
+    kind: VariantKind,
+}
+
+enum VariantKind {
+    Unit,
+    Newtype(String),
+    Tuple(Vec<String>),
+    Struct(Vec<Field>),
+}
+
+enum EnumRepr {
+    External,
+    Internal(String),
+    Adjacent(String, String),
+    Untagged,
+}
+
+impl Emitter<'_> {
+    fn emit_item(&mut self, item: &Item) {
+        self.current_ref.clone_from(&item.reference);
+
+        let Some(obj) = item.schema.as_object() else {
+            let mut attrs = ItemAttrs::new(item);
+            self.write_item_header(None, &mut attrs);
+            _ = writeln!(
+                self.output,
+                "pub struct {}(pub serde_json::Value);",
+                item.name
+            );
+            return;
+        };
+
+        let title_to_skip = item
+            .is_root
+            .then(|| item.rename.as_deref().unwrap_or(&item.name));
+        let doc = doc_text(obj, title_to_skip);
+        let mut attrs = ItemAttrs::new(item);
+        if obj.get("deprecated") == Some(&Value::Bool(true)) {
+            attrs.deprecated = true;
+        }
+
+        if is_struct(obj) {
+            let (fields, deny_unknown_fields) = self.struct_fields(obj, &item.name);
+            if deny_unknown_fields {
+                attrs.serde.push("deny_unknown_fields".to_owned());
+            }
+            self.write_item_header(doc.as_deref(), &mut attrs);
+            _ = write!(self.output, "pub struct {}", item.name);
+            write_fields(&mut self.output, &fields, "", true);
+            self.output.push('\n');
+        } else if let Some(values) = string_enum_values(obj) {
+            let mut used_idents = BTreeSet::new();
+            let variants = values
+                .iter()
+                .map(|value| Variant::unit(value, None, &mut used_idents))
+                .collect::<Vec<_>>();
+            self.write_item_header(doc.as_deref(), &mut attrs);
+            self.write_enum(&item.name, &variants);
+        } else if let Some((repr, variants)) = self.enum_variants(obj, &item.name) {
+            match repr {
+                EnumRepr::External => {}
+                EnumRepr::Internal(tag) => attrs.serde.push(format!("tag = {}", quoted(&tag))),
+                EnumRepr::Adjacent(tag, content) => attrs.serde.push(format!(
+                    "tag = {}, content = {}",
+                    quoted(&tag),
+                    quoted(&content)
+                )),
+                EnumRepr::Untagged => attrs.serde.push("untagged".to_owned()),
+            }
+            if enum_denies_unknown_fields(obj) {
+                attrs.serde.push("deny_unknown_fields".to_owned());
+            }
+            self.write_item_header(doc.as_deref(), &mut attrs);
+            self.write_enum(&item.name, &variants);
+        } else if instance_type(obj) == Some("null") {
+            self.write_item_header(doc.as_deref(), &mut attrs);
+            _ = writeln!(self.output, "pub struct {};", item.name);
+        } else {
+            let ty = self.rust_type(&Value::Object(without_annotations(obj)), &item.name, true);
+            self.write_item_header(doc.as_deref(), &mut attrs);
+            _ = writeln!(self.output, "pub struct {}(pub {ty});", item.name);
+        }
+    }
+
+    fn write_item_header(&mut self, doc: Option<&str>, attrs: &mut ItemAttrs) {
+        self.output.push('\n');
+        if let Some(doc) = doc {
+            write_doc_comment(&mut self.output, doc, "");
+        }
+        if attrs.deprecated {
+            self.output.push_str("#[deprecated]\n");
+        }
+        self.output.push_str(DERIVES);
+        self.output.push('\n');
+        write_attr(&mut self.output, "serde", &attrs.serde, "");
+        write_attr(&mut self.output, "schemars", &attrs.schemars, "");
+    }
+
+    fn write_enum(&mut self, name: &str, variants: &[Variant]) {
+        _ = writeln!(self.output, "pub enum {name} {{");
+        for variant in variants {
+            if let Some(doc) = &variant.doc {
+                write_doc_comment(&mut self.output, doc, "    ");
+            }
+            if let Some(rename) = &variant.rename {
+                write_attr(
+                    &mut self.output,
+                    "serde",
+                    &[format!("rename = {}", quoted(rename))],
+                    "    ",
+                );
+            }
+            self.output.push_str("    ");
+            self.output.push_str(&variant.ident);
+            match &variant.kind {
+                VariantKind::Unit => {}
+                VariantKind::Newtype(ty) => _ = write!(self.output, "({ty})"),
+                VariantKind::Tuple(types) => {
+                    _ = write!(self.output, "({})", types.join(", "));
+                }
+                VariantKind::Struct(fields) => {
+                    write_fields(&mut self.output, fields, "    ", false);
+                }
+            }
+            self.output.push_str(",\n");
+        }
+        self.output.push_str("}\n");
+    }
+
+    // Returns the fields for an object schema's properties, and whether the object disallows
+    // unknown properties.
+    fn struct_fields(&mut self, obj: &Map<String, Value>, type_name: &str) -> (Vec<Field>, bool) {
+        self.object_fields(obj, type_name, None)
+    }
+
+    fn object_fields(
+        &mut self,
+        obj: &Map<String, Value>,
+        type_name: &str,
+        skip_property: Option<&str>,
+    ) -> (Vec<Field>, bool) {
+        let required = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect::<BTreeSet<_>>())
+            .unwrap_or_default();
+
+        let mut used_idents = BTreeSet::new();
+        let mut fields = Vec::new();
+
+        if let Some(Value::Object(properties)) = obj.get("properties") {
+            for (name, schema) in properties {
+                if Some(name.as_str()) != skip_property {
+                    let field = self.field(
+                        name,
+                        schema,
+                        required.contains(name.as_str()),
+                        type_name,
+                        &mut used_idents,
+                    );
+                    fields.push(field);
+                }
+            }
+        }
+
+        let mut deny_unknown_fields = false;
+        match obj.get("additionalProperties") {
+            Some(Value::Bool(false)) => deny_unknown_fields = true,
+            Some(schema) if !fields.is_empty() => {
+                let ident = unique_ident(field_ident("additional_properties"), &mut used_idents);
+                let value_type = self.rust_type(schema, &format!("{type_name}Value"), false);
+                fields.push(Field {
+                    doc: None,
+                    deprecated: false,
+                    serde_attrs: vec!["flatten".to_owned()],
+                    schemars_attrs: Vec::new(),
+                    ident,
+                    ty: format!("std::collections::BTreeMap<String, {value_type}>"),
+                });
+            }
+            _ => {}
+        }
+
+        (fields, deny_unknown_fields)
+    }
+
+    fn field(
+        &mut self,
+        name: &str,
+        schema: &Value,
+        required: bool,
+        type_name: &str,
+        used_idents: &mut BTreeSet<String>,
+    ) -> Field {
+        let ident = unique_ident(field_ident(name), used_idents);
+        let mut serde_attrs = Vec::new();
+        if ident.trim_start_matches("r#") != name {
+            serde_attrs.push(format!("rename = {}", quoted(name)));
+        }
+
+        let obj = schema.as_object();
+        let doc = obj.and_then(|o| doc_text(o, None));
+        let deprecated = obj.is_some_and(|o| o.get("deprecated") == Some(&Value::Bool(true)));
+        let default = obj.and_then(|o| o.get("default"));
+        let type_schema =
+            obj.map_or_else(|| schema.clone(), |o| Value::Object(without_annotations(o)));
+        let hint = format!("{type_name}{}", type_ident(name));
+
+        let mut schemars_attrs = Vec::new();
+        let mut ty = self.rust_type(&type_schema, &hint, true);
+
+        let mut default_handled = default.is_none();
+        if !required {
+            if ty.starts_with("Option<") {
+                if default == Some(&Value::Null) {
+                    serde_attrs.push("default".to_owned());
+                    default_handled = true;
+                }
+            } else if default.is_some_and(|d| is_default_value(&ty, d)) {
+                serde_attrs.push("default".to_owned());
+                default_handled = true;
+            } else {
+                serde_attrs.push("default".to_owned());
+                serde_attrs.push("skip_serializing_if = \"Option::is_none\"".to_owned());
+                schemars_attrs.push(format!("with = {}", quoted(&ty)));
+                ty = format!("Option<{ty}>");
+            }
+        }
+
+        // Defaults that serde can't produce are kept in the schema of the generated field
+        if let (Some(default), false) = (default, default_handled) {
+            schemars_attrs.push(format!("extend(\"default\" = {default})"));
+        }
+
+        Field {
+            doc,
+            deprecated,
+            serde_attrs,
+            schemars_attrs,
+            ident,
+            ty,
+        }
+    }
+
+    fn enum_variants(
+        &mut self,
+        obj: &Map<String, Value>,
+        type_name: &str,
+    ) -> Option<(EnumRepr, Vec<Variant>)> {
+        if let Some(Value::Array(one_of)) = obj.get("oneOf") {
+            if let Some(variants) = self.external_variants(one_of, type_name) {
+                return Some((EnumRepr::External, variants));
+            }
+            if let Some(tag) = common_tag(one_of) {
+                if let Some((content, variants)) = self.adjacent_variants(one_of, &tag, type_name) {
+                    return Some((EnumRepr::Adjacent(tag, content), variants));
+                }
+                let variants = self.internal_variants(one_of, &tag, type_name);
+                return Some((EnumRepr::Internal(tag), variants));
+            }
+            return Some((
+                EnumRepr::Untagged,
+                self.untagged_variants(one_of, type_name),
+            ));
+        }
+
+        if let Some(Value::Array(any_of)) = obj.get("anyOf") {
+            if nullable_inner(obj).is_none() {
+                return Some((
+                    EnumRepr::Untagged,
+                    self.untagged_variants(any_of, type_name),
+                ));
+            }
+        }
+
+        None
+    }
+
+    fn external_variants(&mut self, branches: &[Value], type_name: &str) -> Option<Vec<Variant>> {
+        let mut used_idents = BTreeSet::new();
+        let mut variants = Vec::new();
+
+        for branch in branches {
+            let obj = branch.as_object()?;
+            if let Some(values) = string_enum_values(obj) {
+                let doc = doc_text(obj, None);
+                let doc = if values.len() == 1 { doc } else { None };
+                for value in values {
+                    variants.push(Variant::unit(value, doc.clone(), &mut used_idents));
+                }
+                continue;
+            }
+
+            let properties = obj.get("properties")?.as_object()?;
+            let required = obj.get("required")?.as_array()?;
+            let (name, content) = properties.iter().next()?;
+            if properties.len() != 1
+                || required.len() != 1
+                || required[0] != **name
+                || obj.get("additionalProperties") != Some(&Value::Bool(false))
+            {
+                return None;
+            }
+
+            let hint = format!("{type_name}{}", type_ident(name));
+            let kind = self.variant_content(content, &hint);
+            variants.push(Variant::new(
+                name,
+                doc_text(obj, None),
+                kind,
+                &mut used_idents,
+            ));
+        }
+
+        Some(variants)
+    }
+
+    fn adjacent_variants(
+        &mut self,
+        branches: &[Value],
+        tag: &str,
+        type_name: &str,
+    ) -> Option<(String, Vec<Variant>)> {
+        let mut content_name = None;
+        for branch in branches {
+            let obj = branch.as_object()?;
+            if obj.contains_key("$ref") {
+                return None;
+            }
+            let properties = obj.get("properties")?.as_object()?;
+            for name in properties.keys().filter(|k| *k != tag) {
+                match &content_name {
+                    Some(content) if content != name => return None,
+                    _ => content_name = Some(name.clone()),
+                }
+            }
+        }
+        let content_name = content_name?;
+
+        let mut used_idents = BTreeSet::new();
+        let variants = branches
+            .iter()
+            .filter_map(Value::as_object)
+            .map(|obj| {
+                let name = tag_value(obj, tag).unwrap_or_default();
+                let hint = format!("{type_name}{}", type_ident(name));
+                let kind = match obj.get("properties").and_then(|p| p.get(&content_name)) {
+                    Some(content) => self.variant_content(content, &hint),
+                    None => VariantKind::Unit,
+                };
+                Variant::new(name, doc_text(obj, None), kind, &mut used_idents)
+            })
+            .collect();
+
+        Some((content_name, variants))
+    }
+
+    fn internal_variants(
+        &mut self,
+        branches: &[Value],
+        tag: &str,
+        type_name: &str,
+    ) -> Vec<Variant> {
+        let mut used_idents = BTreeSet::new();
+        branches
+            .iter()
+            .filter_map(Value::as_object)
+            .map(|obj| {
+                let name = tag_value(obj, tag).unwrap_or_default();
+                let hint = format!("{type_name}{}", type_ident(name));
+                let other_properties = obj
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .is_some_and(|p| p.keys().any(|k| k != tag));
+
+                let kind = match obj.get("$ref").and_then(Value::as_str) {
+                    Some(reference) if !other_properties => {
+                        VariantKind::Newtype(self.ref_type(reference, true))
+                    }
+                    _ if !other_properties => VariantKind::Unit,
+                    _ => VariantKind::Struct(self.object_fields(obj, &hint, Some(tag)).0),
+                };
+                Variant::new(name, doc_text(obj, None), kind, &mut used_idents)
+            })
+            .collect()
+    }
+
+    fn untagged_variants(&mut self, branches: &[Value], type_name: &str) -> Vec<Variant> {
+        let mut used_idents = BTreeSet::new();
+        branches
+            .iter()
+            .enumerate()
+            .map(|(index, branch)| {
+                let obj = branch.as_object();
+                let name = self.untagged_variant_name(branch, index);
+                let hint = format!("{type_name}{name}");
+                let doc = obj.and_then(|o| doc_text(o, None));
+
+                let kind = match obj {
+                    Some(obj) if instance_type(obj) == Some("null") => VariantKind::Unit,
+                    Some(obj) => {
+                        self.variant_content(&Value::Object(without_annotations(obj)), &hint)
+                    }
+                    None => VariantKind::Newtype(self.rust_type(branch, &hint, true)),
+                };
+                let ident = unique_ident(name, &mut used_idents);
+                Variant {
+                    doc,
+                    rename: None,
+                    ident,
+                    kind,
+                }
+            })
+            .collect()
+    }
+
+    fn untagged_variant_name(&mut self, branch: &Value, index: usize) -> String {
+        let Some(obj) = branch.as_object() else {
+            return format!("Variant{index}");
+        };
+
+        if let Some(title) = obj.get("title").and_then(Value::as_str) {
+            return type_ident(title);
+        }
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            return self.ref_type(reference, false);
+        }
+
+        match instance_type(obj) {
+            Some("string") => "String",
+            Some("integer") => "Integer",
+            Some("number") => "Number",
+            Some("boolean") => "Boolean",
+            Some("array") => "Array",
+            Some("object") => "Object",
+            Some("null") => "Null",
+            _ => return format!("Variant{index}"),
+        }
+        .to_owned()
+    }
+
+    // Returns the kind of an enum variant whose content is described by the given schema.
+    fn variant_content(&mut self, content: &Value, hint: &str) -> VariantKind {
+        match content.as_object() {
+            Some(obj) if is_struct(obj) && !obj.contains_key("title") => {
+                VariantKind::Struct(self.struct_fields(obj, hint).0)
+            }
+            Some(obj) => match tuple_items(obj) {
+                Some(items) if items.len() > 1 => VariantKind::Tuple(
+                    items
+                        .iter()
+                        .enumerate()
+                        .map(|(i, item)| self.rust_type(item, &format!("{hint}{i}"), true))
+                        .collect(),
+                ),
+                _ => VariantKind::Newtype(self.rust_type(content, hint, true)),
+            },
+            None => VariantKind::Newtype(self.rust_type(content, hint, true)),
+        }
+    }
+
+    // Returns the Rust type for a schema. `hint` is used as the name of any type that needs to be
+    // declared for the schema, and `direct` is false when values of the type will be stored on the
+    // heap (e.g. in a `Vec`), so do not need to be boxed in recursive types.
+    fn rust_type(&mut self, schema: &Value, hint: &str, direct: bool) -> String {
+        let Some(obj) = schema.as_object() else {
+            return "serde_json::Value".to_owned();
+        };
+
+        if let Some(inner) = nullable_inner(obj) {
+            return format!("Option<{}>", self.rust_type(&inner, hint, direct));
+        }
+
+        let assertions = without_annotations(obj);
+        if assertions.is_empty() {
+            return "serde_json::Value".to_owned();
+        }
+
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            if assertions.len() == 1 {
+                return self.ref_type(reference, direct);
+            }
+        }
+
+        if let Some(Value::Array(all_of)) = obj.get("allOf") {
+            if all_of.len() == 1 && assertions.len() == 1 {
+                return self.rust_type(&all_of[0], hint, direct);
+            }
+        }
+
+        if is_struct(obj)
+            || string_enum_values(obj).is_some()
+            || obj.contains_key("oneOf")
+            || obj.contains_key("anyOf")
+        {
+            return self.inline_item(hint, assertions);
+        }
+
+        match instance_type(obj) {
+            Some("null") => "()".to_owned(),
+            Some("boolean") => "bool".to_owned(),
+            Some("integer") => integer_type(obj).to_owned(),
+            Some("number") => {
+                if obj.get("format").and_then(Value::as_str) == Some("float") {
+                    "f32".to_owned()
+                } else {
+                    "f64".to_owned()
+                }
+            }
+            Some("string") => string_type(obj).to_owned(),
+            Some("array") => self.array_type(obj, hint, direct),
+            Some("object") => {
+                let value_type = match obj.get("additionalProperties") {
+                    Some(schema) => self.rust_type(schema, &format!("{hint}Value"), false),
+                    None => "serde_json::Value".to_owned(),
+                };
+                format!("std::collections::BTreeMap<String, {value_type}>")
+            }
+            _ => "serde_json::Value".to_owned(),
+        }
+    }
+
+    fn array_type(&mut self, obj: &Map<String, Value>, hint: &str, direct: bool) -> String {
+        if let Some(items) = tuple_items(obj) {
+            let types = items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| self.rust_type(item, &format!("{hint}{i}"), direct))
+                .collect::<Vec<_>>();
+            return if types.len() == 1 {
+                format!("({},)", types[0])
+            } else {
+                format!("({})", types.join(", "))
+            };
+        }
+
+        let item_type = match obj.get("items") {
+            Some(items) if items.is_object() || items == &Value::Bool(true) => {
+                self.rust_type(items, &format!("{hint}Item"), false)
+            }
+            _ => "serde_json::Value".to_owned(),
+        };
+
+        let min_items = obj.get("minItems").and_then(Value::as_u64);
+        let max_items = obj.get("maxItems").and_then(Value::as_u64);
+        match (min_items, max_items) {
+            (Some(min), Some(max)) if min == max && min > 0 => {
+                return format!("[{item_type}; {min}]");
+            }
+            _ => {}
+        }
+
+        if obj.get("uniqueItems") == Some(&Value::Bool(true)) && is_ord_type(&item_type) {
+            format!("std::collections::BTreeSet<{item_type}>")
+        } else {
+            format!("Vec<{item_type}>")
+        }
+    }
+
+    // Returns the name of the type for a `$ref`, queueing it to be emitted if it has not been seen
+    // before.
+    fn ref_type(&mut self, reference: &str, direct: bool) -> String {
+        let name = if let Some(name) = self.ref_names.get(reference) {
+            name.clone()
+        } else {
+            let schema_name = ref_schema_name(reference);
+            let name = self.unique_type_name(&schema_name);
+            self.ref_names.insert(reference.to_owned(), name.clone());
+            self.pending.push_back(Item {
+                rename: (name != schema_name).then_some(schema_name),
+                name: name.clone(),
+                schema: self
+                    .root
+                    .resolve_ref(reference)
+                    .map_or(Value::Bool(true), |schema| schema.as_value().clone()),
+                reference: reference.to_owned(),
+                inline: false,
+                is_root: false,
+            });
+            name
+        };
+
+        if direct && self.is_recursive(reference) {
+            format!("Box<{name}>")
+        } else {
+            name
+        }
+    }
+
+    // Whether the definition being emitted can be reached from `reference` without any
+    // indirection, in which case a field referencing it needs to be boxed.
+    fn is_recursive(&self, reference: &str) -> bool {
+        let mut seen = BTreeSet::new();
+        let mut stack = vec![reference.to_owned()];
+
+        while let Some(reference) = stack.pop() {
+            if reference == self.current_ref {
+                return true;
+            }
+            if seen.insert(reference.clone()) {
+                if let Some(schema) = self.root.resolve_ref(&reference) {
+                    collect_direct_refs(schema.as_value(), &mut stack);
+                }
+            }
+        }
+
+        false
+    }
+
+    fn inline_item(&mut self, hint: &str, schema: Map<String, Value>) -> String {
+        let name = self.unique_type_name(hint);
+        self.pending.push_back(Item {
+            name: name.clone(),
+            rename: None,
+            schema: Value::Object(schema),
+            reference: self.current_ref.clone(),
+            inline: true,
+            is_root: false,
+        });
+        name
+    }
+
+    fn unique_type_name(&mut self, name: &str) -> String {
+        unique_ident(type_ident(name), &mut self.used_names)
+    }
+}
+
+struct ItemAttrs {
+    deprecated: bool,
+    serde: Vec<String>,
+    schemars: Vec<String>,
+}
+
+impl ItemAttrs {
+    fn new(item: &Item) -> Self {
+        let mut schemars = Vec::new();
+        if let Some(rename) = &item.rename {
+            schemars.push(format!("rename = {}", quoted(rename)));
+        }
+        if item.inline {
+            schemars.push("inline".to_owned());
+        }
+
+        Self {
+            deprecated: false,
+            serde: Vec::new(),
+            schemars,
+        }
+    }
+}
+
+impl Variant {
+    fn new(
+        name: &str,
+        doc: Option<String>,
+        kind: VariantKind,
+        used: &mut BTreeSet<String>,
+    ) -> Self {
+        let ident = unique_ident(type_ident(name), used);
+        Self {
+            doc,
+            rename: (ident != name).then(|| name.to_owned()),
+            ident,
+            kind,
+        }
+    }
+
+    fn unit(name: &str, doc: Option<String>, used: &mut BTreeSet<String>) -> Self {
+        Self::new(name, doc, VariantKind::Unit, used)
+    }
+}
+
+fn write_fields(out: &mut String, fields: &[Field], indent: &str, is_pub: bool) {
+    if fields.is_empty() {
+        out.push_str(" {}");
+        return;
+    }
+
+    let inner_indent = format!("{indent}    ");
+    out.push_str(" {\n");
+    for field in fields {
+        if let Some(doc) = &field.doc {
+            write_doc_comment(out, doc, &inner_indent);
+        }
+        if field.deprecated {
+            _ = writeln!(out, "{inner_indent}#[deprecated]");
+        }
+        write_attr(out, "serde", &field.serde_attrs, &inner_indent);
+        write_attr(out, "schemars", &field.schemars_attrs, &inner_indent);
+        out.push_str(&inner_indent);
+        if is_pub {
+            out.push_str("pub ");
+        }
+        _ = writeln!(out, "{}: {},", field.ident, field.ty);
+    }
+    out.push_str(indent);
+    out.push('}');
+}
+
+fn write_attr(out: &mut String, name: &str, args: &[String], indent: &str) {
+    if !args.is_empty() {
+        _ = writeln!(out, "{indent}#[{name}({})]", args.join(", "));
+    }
+}
+
+fn write_doc_comment(out: &mut String, doc: &str, indent: &str) {
+    for line in doc.lines() {
+        out.push_str(indent);
+        if line.is_empty() {
+            out.push_str("///\n");
+        } else {
+            out.push_str("/// ");
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+}
+
+// Returns the text of a doc comment for the schema, omitting the title if it is the same as
+// `title_to_skip`.
+fn doc_text(obj: &Map<String, Value>, title_to_skip: Option<&str>) -> Option<String> {
+    let title = obj
+        .get("title")
+        .and_then(Value::as_str)
+        .filter(|t| !t.is_empty() && Some(*t) != title_to_skip);
+    let description = obj
+        .get("description")
+        .and_then(Value::as_str)
+        .filter(|d| !d.is_empty());
+
+    match (title, description) {
+        (Some(title), Some(description)) => Some(format!("# {title}\n\n{description}")),
+        (Some(title), None) => Some(format!("# {title}")),
+        (None, Some(description)) => Some(description.to_owned()),
+        (None, None) => None,
+    }
+}
+
+fn quoted(s: &str) -> String {
+    Value::from(s).to_string()
+}
+
+fn without_annotations(obj: &Map<String, Value>) -> Map<String, Value> {
+    obj.iter()
+        .filter(|(k, _)| !ANNOTATION_KEYWORDS.contains(&k.as_str()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+fn instance_type(obj: &Map<String, Value>) -> Option<&str> {
+    match obj.get("type") {
+        Some(Value::String(ty)) => Some(ty),
+        _ if obj.contains_key("properties") => Some("object"),
+        _ => None,
+    }
+}
+
+// Whether the schema describes an object with a fixed set of properties, which is emitted as a
+// struct.
+fn is_struct(obj: &Map<String, Value>) -> bool {
+    if instance_type(obj) != Some("object")
+        || [
+            "$ref",
+            "oneOf",
+            "anyOf",
+            "allOf",
+            "enum",
+            "const",
+            "patternProperties",
+        ]
+        .iter()
+        .any(|k| obj.contains_key(*k))
+    {
+        return false;
+    }
+
+    let has_properties = obj
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_some_and(|p| !p.is_empty());
+
+    match obj.get("additionalProperties") {
+        None | Some(Value::Bool(false)) => true,
+        Some(_) => has_properties,
+    }
+}
+
+// If the schema is a union of `null` and exactly one other schema, returns the other schema.
+fn nullable_inner(obj: &Map<String, Value>) -> Option<Value> {
+    if let Some(Value::Array(types)) = obj.get("type") {
+        if types.len() == 2 && types.contains(&Value::from("null")) {
+            let other = types.iter().find(|t| *t != "null")?.clone();
+            let mut inner = without_annotations(obj);
+            inner.insert("type".to_owned(), other);
+            return Some(Value::Object(inner));
+        }
+    }
+
+    if let Some(Value::Array(any_of)) = obj.get("anyOf") {
+        let is_null = |s: &Value| s.get("type").and_then(Value::as_str) == Some("null");
+        if any_of.len() == 2 && any_of.iter().any(is_null) && without_annotations(obj).len() == 1 {
+            return any_of.iter().find(|s| !is_null(s)).cloned();
+        }
+    }
+
+    None
+}
+
+// Returns the values of a schema that only allows particular strings
+fn string_enum_values(obj: &Map<String, Value>) -> Option<Vec<&str>> {
+    if obj.contains_key("oneOf") || obj.contains_key("anyOf") {
+        return None;
+    }
+
+    let values = match (obj.get("enum"), obj.get("const")) {
+        (Some(Value::Array(values)), _) => values.iter().map(Value::as_str).collect(),
+        (None, Some(Value::String(value))) => Some(vec![value.as_str()]),
+        _ => None,
+    };
+
+    values.filter(|v: &Vec<&str>| !v.is_empty())
+}
+
+// Returns the property that every variant schema uses as a tag, for internally or adjacently
+// tagged enums.
+fn common_tag(branches: &[Value]) -> Option<String> {
+    let first = branches.first()?.as_object()?;
+    let properties = first.get("properties")?.as_object()?;
+
+    properties
+        .keys()
+        .find(|key| {
+            branches.iter().all(|branch| {
+                branch.as_object().is_some_and(|obj| {
+                    tag_value(obj, key).is_some()
+                        && obj
+                            .get("required")
+                            .and_then(Value::as_array)
+                            .is_some_and(|r| r.iter().any(|v| v == *key))
+                })
+            })
+        })
+        .cloned()
+}
+
+fn tag_value<'a>(obj: &'a Map<String, Value>, tag: &str) -> Option<&'a str> {
+    let tag_schema = obj.get("properties")?.get(tag)?.as_object()?;
+    match string_enum_values(tag_schema)?.as_slice() {
+        [value] => Some(value),
+        _ => None,
+    }
+}
+
+fn enum_denies_unknown_fields(obj: &Map<String, Value>) -> bool {
+    let branches = obj
+        .get("oneOf")
+        .or_else(|| obj.get("anyOf"))
+        .and_then(Value::as_array);
+
+    branches.is_some_and(|branches| {
+        branches.iter().any(|branch| {
+            let content = match branch.get("properties").and_then(Value::as_object) {
+                Some(properties)
+                    if properties.len() == 1
+                        && branch.get("additionalProperties") == Some(&Value::Bool(false)) =>
+                {
+                    properties.values().next()
+                }
+                _ => Some(branch),
+            };
+            content.is_some_and(|c| {
+                c.get("properties")
+                    .is_some_and(|p| p.as_object().is_some_and(|p| !p.is_empty()))
+                    && c.get("additionalProperties") == Some(&Value::Bool(false))
+            })
+        })
+    })
+}
+
+// Returns the items of a fixed-length tuple schema
+fn tuple_items(obj: &Map<String, Value>) -> Option<&Vec<Value>> {
+    // Draft 2020-12 uses `prefixItems`, earlier drafts use `items` with an array value
+    let ((Some(Value::Array(items)), _) | (None, Some(Value::Array(items)))) =
+        (obj.get("prefixItems"), obj.get("items"))
+    else {
+        return None;
+    };
+
+    let len = items.len() as u64;
+    let min_items = obj.get("minItems").and_then(Value::as_u64);
+    let max_items = obj.get("maxItems").and_then(Value::as_u64);
+    (min_items == Some(len) && max_items == Some(len)).then_some(items)
+}
+
+fn integer_type(obj: &Map<String, Value>) -> &'static str {
+    match obj.get("format").and_then(Value::as_str) {
+        Some("int8") => "i8",
+        Some("int16") => "i16",
+        Some("int32") => "i32",
+        Some("int128") => "i128",
+        Some("int") => "isize",
+        Some("uint8") => "u8",
+        Some("uint16") => "u16",
+        Some("uint32") => "u32",
+        Some("uint64") => "u64",
+        Some("uint128") => "u128",
+        Some("uint") => "usize",
+        _ => "i64",
+    }
+}
+
+fn string_type(obj: &Map<String, Value>) -> &'static str {
+    let format = obj
+        .get("format")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if let Some(ty) = format_type(format) {
+        return ty;
+    }
+
+    let min_length = obj.get("minLength").and_then(Value::as_u64);
+    let max_length = obj.get("maxLength").and_then(Value::as_u64);
+    if min_length == Some(1) && max_length == Some(1) {
+        "char"
+    } else {
+        "String"
+    }
+}
+
+// Returns the type for a string `format`, if there is one that schemars knows about.
+fn format_type(format: &str) -> Option<&'static str> {
+    Some(match format {
+        "ip" => "std::net::IpAddr",
+        "ipv4" => "std::net::Ipv4Addr",
+        "ipv6" => "std::net::Ipv6Addr",
+        #[cfg(feature = "uuid1")]
+        "uuid" => "uuid::Uuid",
+        #[cfg(feature = "url2")]
+        "uri" => "url::Url",
+        #[cfg(feature = "chrono04")]
+        "date-time" => "chrono::DateTime<chrono::FixedOffset>",
+        #[cfg(feature = "chrono04")]
+        "date" => "chrono::NaiveDate",
+        #[cfg(feature = "chrono04")]
+        "partial-date-time" => "chrono::NaiveDateTime",
+        #[cfg(feature = "chrono04")]
+        "partial-time" => "chrono::NaiveTime",
+        _ => return None,
+    })
+}
+
+// Whether the type implements `Ord`, so can be used in a `BTreeSet`
+fn is_ord_type(ty: &str) -> bool {
+    matches!(
+        ty,
+        "bool"
+            | "char"
+            | "String"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "uuid::Uuid"
+    )
+}
+
+// Whether `value` is the serialized `Default` value of the type `ty`
+fn is_default_value(ty: &str, value: &Value) -> bool {
+    match value {
+        Value::Bool(b) => ty == "bool" && !b,
+        Value::Number(n) => integer_or_float(ty) && n.as_f64() == Some(0.0),
+        Value::String(s) => ty == "String" && s.is_empty(),
+        Value::Array(a) => {
+            a.is_empty()
+                && (ty.starts_with("Vec<") || ty.starts_with("std::collections::BTreeSet<"))
+        }
+        Value::Object(o) => o.is_empty() && ty.starts_with("std::collections::BTreeMap<"),
+        Value::Null => false,
+    }
+}
+
+fn integer_or_float(ty: &str) -> bool {
+    matches!(
+        ty,
+        "i8" | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "f32"
+            | "f64"
+    )
+}
+
+// Adds any `$ref`s within the schema that are not behind any indirection (e.g. within `items` or
+// `additionalProperties`) to `refs`.
+fn collect_direct_refs(schema: &Value, refs: &mut Vec<String>) {
+    let Some(obj) = schema.as_object() else {
+        return;
+    };
+
+    for (key, value) in obj {
+        match (key.as_str(), value) {
+            ("$ref", Value::String(reference)) => refs.push(reference.clone()),
+            ("properties", Value::Object(properties)) => {
+                for subschema in properties.values() {
+                    collect_direct_refs(subschema, refs);
+                }
+            }
+            ("prefixItems" | "items" | "allOf" | "anyOf" | "oneOf", Value::Array(subschemas)) => {
+                for subschema in subschemas {
+                    collect_direct_refs(subschema, refs);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Returns the name of the definition that a `$ref` points to
+fn ref_schema_name(reference: &str) -> String {
+    let decoded = reference
+        .strip_prefix('#')
+        .and_then(crate::encoding::percent_decode);
+    let pointer = decoded.as_deref().unwrap_or(reference);
+    let segment = pointer.rsplit('/').next().unwrap_or_default();
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+fn unique_ident(ident: String, used: &mut BTreeSet<String>) -> String {
+    if used.insert(ident.clone()) {
+        return ident;
+    }
+
+    let mut i = 2;
+    loop {
+        let candidate = format!("{ident}{i}");
+        if used.insert(candidate.clone()) {
+            return candidate;
+        }
+        i += 1;
+    }
+}
+
+// Converts a name into an UpperCamelCase identifier for a type or enum variant
+fn type_ident(name: &str) -> String {
+    let mut result = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            result.push(first.to_ascii_uppercase());
+            result.extend(chars);
+        }
+    }
+
+    if result.is_empty() || result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, 'T');
+    }
+    if result == "Self" {
+        result.push('_');
+    }
+    result
+}
+
+// Converts a name into a snake_case identifier for a field
+fn field_ident(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut result = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let word_start = prev.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+                || (prev.is_some_and(|p| p.is_ascii_uppercase())
+                    && next.is_some_and(char::is_ascii_lowercase));
+            if word_start && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            result.push(c);
+        } else if !result.ends_with('_') {
+            result.push('_');
+        }
+    }
+
+    if result.is_empty() || result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+
+    match result.as_str() {
+        "_" => "field".to_owned(),
+        "self" | "super" | "crate" => result + "_",
+        _ if is_keyword(&result) => format!("r#{result}"),
+        _ => result,
+    }
+}
+
+fn is_keyword(ident: &str) -> bool {
+    matches!(
+        ident,
+        "abstract"
+            | "as"
+            | "async"
+            | "await"
+            | "become"
+            | "box"
+            | "break"
+            | "const"
+            | "continue"
+            | "do"
+            | "dyn"
+            | "else"
+            | "enum"
+            | "extern"
+            | "false"
+            | "final"
+            | "fn"
+            | "for"
+            | "gen"
+            | "if"
+            | "impl"
+            | "in"
+            | "let"
+            | "loop"
+            | "macro"
+            | "match"
+            | "mod"
+            | "move"
+            | "mut"
+            | "override"
+            | "priv"
+            | "pub"
+            | "ref"
+            | "return"
+            | "static"
+            | "struct"
+            | "trait"
+            | "true"
+            | "try"
+            | "type"
+            | "typeof"
+            | "unsafe"
+            | "unsized"
+            | "use"
+            | "virtual"
+            | "where"
+            | "while"
+            | "yield"
+    )
+}
@@ -0,0 +1,995 @@
+/*!
+Detection of breaking changes between two versions of a [`Schema`].
+
+[`check`] compares an old and new version of a schema, and reports each change that could break
+clients of one of the versions. Whether a change is breaking depends on the direction that data
+flows, which is specified using a [`Contract`]:
+- a schema generated for the [`Deserialize`](Contract::Deserialize) contract describes data that
+  is _accepted_ by an application. Changes that reject data that was previously valid (e.g. a
+  newly-required property) are **backward-incompatible**, because existing clients may still send
+  data matching the old schema.
+- a schema generated for the [`Serialize`](Contract::Serialize) contract describes data that is
+  _produced_ by an application. Changes that allow data that was previously invalid (e.g. a new
+  enum value) are **backward-incompatible**, because existing clients may not be able to handle it.
+
+Changes that are incompatible in the opposite direction are reported as **forward-incompatible**,
+i.e. they would break a client built against the new schema that is talking to an application
+that still uses the old schema (for example, after a rollback).
+
+Any `$ref`s within each schema are resolved against that schema, so [`check`] should be called
+with root schemas (e.g. ones returned by [`schema_for!`](crate::schema_for)). Only `$ref`s that
+are URI fragments (e.g. `#/$defs/MyStruct`) can be followed.
+
+The comparison is structural and conservative - it covers the JSON Schema keywords that schemars
+generates, and does not attempt to prove whether two arbitrary schemas are equivalent. In
+particular, changes to `enum`/`const` values are only detected when both versions of a schema
+restrict values in this way, and properties that are removed from (or added to) an object schema
+are only reported when the other version of the schema disallows additional properties.
+
+# Example
+```
+use schemars::compat::{self, Change, Incompatibility};
+use schemars::{generate::Contract, json_schema};
+
+let old = json_schema!({
+    "type": "object",
+    "properties": {
+        "name": { "type": "string" },
+        "age": { "type": "integer", "minimum": 0 }
+    },
+    "required": ["name"]
+});
+let new = json_schema!({
+    "type": "object",
+    "properties": {
+        "name": { "type": "string" },
+        "age": { "type": "integer", "minimum": 18 }
+    },
+    "required": ["name", "age"]
+});
+
+let findings = compat::check(&old, &new, Contract::Deserialize);
+
+assert_eq!(findings.len(), 2);
+assert_eq!(
+    findings[0].change,
+    Change::PropertyNowRequired { property: "age".to_owned() }
+);
+assert_eq!(findings[0].path, "");
+assert_eq!(findings[0].incompatibility, Incompatibility::Backward);
+assert_eq!(findings[1].path, "/properties/age");
+assert!(findings.iter().all(|f| f.is_backward_incompatible()));
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::encoding::push_pointer_segment;
+use crate::generate::Contract;
+use crate::schema::{contains_ref, follow_value_refs};
+use crate::Schema;
+use alloc::borrow::Cow;
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+use serde_json::{Map, Value};
+
+/// Compares two versions of a schema, returning any changes that are incompatible in either
+/// direction for the given contract.
+///
+/// See the [module documentation](crate::compat) for more information.
+#[must_use]
+pub fn check(old: &Schema, new: &Schema, contract: Contract) -> Vec<Finding> {
+    let mut checker = Checker {
+        old_root: old,
+        new_root: new,
+        contract,
+        path: String::new(),
+        visited_refs: BTreeSet::from([("#".to_owned(), "#".to_owned())]),
+        in_progress: Vec::new(),
+        findings: Vec::new(),
+    };
+
+    checker.compare(old.as_value(), new.as_value());
+
+    checker.findings
+}
+
+/// A change between two versions of a schema that may break clients.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Finding {
+    /// A JSON Pointer to the changed part of the new schema.
+    ///
+    /// When the comparison follows a `$ref`, the path continues through the `$ref` keyword, e.g.
+    /// `/properties/inner/$ref/required`. For changes that remove part of a schema (e.g. a
+    /// removed property), this points to the schema that the removed part belonged to.
+    pub path: String,
+    /// The change that was made to the schema.
+    pub change: Change,
+    /// Which clients the change may break.
+    pub incompatibility: Incompatibility,
+}
+
+impl Finding {
+    /// Returns true if the change may break existing clients of the old schema.
+    #[must_use]
+    pub fn is_backward_incompatible(&self) -> bool {
+        matches!(
+            self.incompatibility,
+            Incompatibility::Backward | Incompatibility::Both
+        )
+    }
+
+    /// Returns true if the change may break clients of the new schema that are talking to an
+    /// application still using the old schema.
+    #[must_use]
+    pub fn is_forward_incompatible(&self) -> bool {
+        matches!(
+            self.incompatibility,
+            Incompatibility::Forward | Incompatibility::Both
+        )
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let incompatibility = match self.incompatibility {
+            Incompatibility::Backward => "backward-incompatible",
+            Incompatibility::Forward => "forward-incompatible",
+            Incompatibility::Both => "backward- and forward-incompatible",
+        };
+        write!(f, "{} ({incompatibility}", self.change)?;
+        if !self.path.is_empty() {
+            write!(f, ", at \"{}\"", self.path)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// Which clients a [`Change`] may break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Incompatibility {
+    /// The change may break existing clients of the old schema.
+    Backward,
+    /// The change may break clients of the new schema that are talking to an application still
+    /// using the old schema.
+    Forward,
+    /// The change may break clients of either schema.
+    Both,
+}
+
+/// A kind of change between two versions of a schema.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+#[non_exhaustive]
+pub enum Change {
+    /// The allowed `type`s of the schema changed. `None` indicates that any type was allowed.
+    TypeChanged {
+        old: Option<Vec<String>>,
+        new: Option<Vec<String>>,
+    },
+    /// A property was added to `required`.
+    PropertyNowRequired { property: String },
+    /// A property was removed from `required`.
+    PropertyNoLongerRequired { property: String },
+    /// A property was removed from a schema that does not allow additional properties.
+    PropertyRemoved { property: String },
+    /// A property was added to a schema that previously did not allow additional properties.
+    PropertyAdded { property: String },
+    /// A value was removed from the values allowed by `enum` or `const`.
+    EnumValueRemoved { value: Value },
+    /// A value was added to the values allowed by `enum` or `const`.
+    EnumValueAdded { value: Value },
+    /// A subschema of `oneOf` or `anyOf` was removed, e.g. because a variant was removed from a
+    /// rust enum.
+    VariantRemoved { variant: String },
+    /// A subschema was added to `oneOf` or `anyOf`, e.g. because a variant was added to a rust
+    /// enum.
+    VariantAdded { variant: String },
+    /// The value of another keyword was added, removed or changed, e.g. `minimum` or `format`.
+    KeywordChanged {
+        keyword: String,
+        old: Option<Value>,
+        new: Option<Value>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn fmt_types(types: Option<&Vec<String>>) -> String {
+            types.map_or_else(|| "any".to_owned(), |t| t.join(" | "))
+        }
+
+        fn fmt_value(value: Option<&Value>) -> String {
+            value.map_or_else(|| "(none)".to_owned(), Value::to_string)
+        }
+
+        match self {
+            Change::TypeChanged { old, new } => write!(
+                f,
+                "type changed from {} to {}",
+                fmt_types(old.as_ref()),
+                fmt_types(new.as_ref())
+            ),
+            Change::PropertyNowRequired { property } => {
+                write!(f, "property \"{property}\" is now required")
+            }
+            Change::PropertyNoLongerRequired { property } => {
+                write!(f, "property \"{property}\" is no longer required")
+            }
+            Change::PropertyRemoved { property } => write!(f, "property \"{property}\" removed"),
+            Change::PropertyAdded { property } => write!(f, "property \"{property}\" added"),
+            Change::EnumValueRemoved { value } => write!(f, "enum value {value} removed"),
+            Change::EnumValueAdded { value } => write!(f, "enum value {value} added"),
+            Change::VariantRemoved { variant } => write!(f, "variant \"{variant}\" removed"),
+            Change::VariantAdded { variant } => write!(f, "variant \"{variant}\" added"),
+            Change::KeywordChanged { keyword, old, new } => write!(
+                f,
+                "\"{keyword}\" changed from {} to {}",
+                fmt_value(old.as_ref()),
+                fmt_value(new.as_ref())
+            ),
+        }
+    }
+}
+
+// Keywords with a numeric value where increasing the value makes the schema stricter
+const LOWER_BOUNDS: [&str; 6] = [
+    "minimum",
+    "exclusiveMinimum",
+    "minLength",
+    "minItems",
+    "minProperties",
+    "minContains",
+];
+
+// Keywords with a numeric value where decreasing the value makes the schema stricter
+const UPPER_BOUNDS: [&str; 6] = [
+    "maximum",
+    "exclusiveMaximum",
+    "maxLength",
+    "maxItems",
+    "maxProperties",
+    "maxContains",
+];
+
+// Keywords where any change to the value may reject previously-valid values and allow
+// previously-invalid values
+const OPAQUE_KEYWORDS: [&str; 3] = ["format", "pattern", "multipleOf"];
+
+const JSON_TYPES: [&str; 7] = [
+    "null", "boolean", "object", "array", "number", "string", "integer",
+];
+
+struct Checker<'a> {
+    old_root: &'a Schema,
+    new_root: &'a Schema,
+    contract: Contract,
+    path: String,
+    // Pairs of (old, new) `$ref`s that have already been compared, to avoid infinite recursion on
+    // recursive schemas
+    visited_refs: BTreeSet<(String, String)>,
+    // Pairs of (old, new) schemas that are currently being compared where only one of them is a
+    // `$ref`. The other schema may be a temporary value (e.g. an `anyOf` branch merged with its
+    // siblings), so its address is only tracked until the comparison has finished.
+    in_progress: Vec<(*const Value, *const Value)>,
+    findings: Vec<Finding>,
+}
+
+// Whether a change rejects previously-valid values ("narrows") and/or allows previously-invalid
+// values ("widens") the set of values that are valid against the schema.
+#[derive(Clone, Copy)]
+struct Effect {
+    narrows: bool,
+    widens: bool,
+}
+
+const NARROWS: Effect = Effect {
+    narrows: true,
+    widens: false,
+};
+const WIDENS: Effect = Effect {
+    narrows: false,
+    widens: true,
+};
+const NARROWS_AND_WIDENS: Effect = Effect {
+    narrows: true,
+    widens: true,
+};
+
+impl<'a> Checker<'a> {
+    fn compare(&mut self, old: &Value, new: &Value) {
+        // Identical `$ref`s may still point to different definitions in each schema
+        if old == new && !contains_ref(new) {
+            return;
+        }
+
+        let len = self.path.len();
+
+        match self.resolve_refs(old, new) {
+            Resolved::Both(old, new) => self.compare_resolved(old, new),
+            Resolved::OneSided(old, new) => {
+                self.compare_resolved(old, new);
+                self.in_progress.pop();
+            }
+            Resolved::AlreadyVisited => return,
+            Resolved::Unresolvable => {
+                self.report_keyword_change("$ref", old, new, NARROWS_AND_WIDENS);
+                return;
+            }
+        }
+
+        self.path.truncate(len);
+    }
+
+    fn compare_resolved(&mut self, old: &Value, new: &Value) {
+        match (old, new) {
+            (Value::Object(old_obj), Value::Object(new_obj)) => {
+                if has_alternatives(old_obj) || has_alternatives(new_obj) {
+                    self.compare_alternatives(old, new);
+                } else {
+                    self.compare_objects(old_obj, new_obj);
+                }
+            }
+            _ => self.compare_bool_schemas(old, new),
+        }
+    }
+
+    fn resolve_refs<'v>(&mut self, old: &'v Value, new: &'v Value) -> Resolved<'v>
+    where
+        'a: 'v,
+    {
+        let old_ref = old.get("$ref").and_then(Value::as_str);
+        let new_ref = new.get("$ref").and_then(Value::as_str);
+
+        if old_ref.is_none() && new_ref.is_none() {
+            return Resolved::Both(old, new);
+        }
+
+        let old_target = match old_ref {
+            Some(r) => self.old_root.resolve_ref(r).map(Schema::as_value),
+            None => Some(old),
+        };
+        let new_target = match new_ref {
+            Some(r) => self.new_root.resolve_ref(r).map(Schema::as_value),
+            None => Some(new),
+        };
+
+        let (Some(old_target), Some(new_target)) = (old_target, new_target) else {
+            return Resolved::Unresolvable;
+        };
+
+        let one_sided = if let (Some(old_ref), Some(new_ref)) = (old_ref, new_ref) {
+            if !self
+                .visited_refs
+                .insert((old_ref.to_owned(), new_ref.to_owned()))
+            {
+                return Resolved::AlreadyVisited;
+            }
+            false
+        } else {
+            let pair = (old_target as *const Value, new_target as *const Value);
+            if self.in_progress.contains(&pair) {
+                return Resolved::AlreadyVisited;
+            }
+            self.in_progress.push(pair);
+            true
+        };
+
+        if new_ref.is_some() {
+            push_pointer_segment(&mut self.path, "$ref");
+        }
+
+        // Follow any further `$ref`s in the targets (or their siblings)
+        let old = follow_value_refs(old_target, |r| self.old_root.resolve_ref(r));
+        let new = follow_value_refs(new_target, |r| self.new_root.resolve_ref(r));
+        if one_sided {
+            Resolved::OneSided(old, new)
+        } else {
+            Resolved::Both(old, new)
+        }
+    }
+
+    fn compare_bool_schemas(&mut self, old: &Value, new: &Value) {
+        let effect = match (old, new) {
+            (Value::Bool(true), _) | (_, Value::Bool(false)) => NARROWS,
+            (Value::Bool(false), _) | (_, Value::Bool(true)) => WIDENS,
+            _ => NARROWS_AND_WIDENS,
+        };
+
+        self.report(
+            Change::TypeChanged {
+                old: bool_schema_types(old),
+                new: bool_schema_types(new),
+            },
+            effect,
+        );
+    }
+
+    fn compare_alternatives(&mut self, old: &Value, new: &Value) {
+        let (old_values, old_schemas) = partition_alternatives(old, self.old_root);
+        let (new_values, new_schemas) = partition_alternatives(new, self.new_root);
+
+        if let (Some(old_values), Some(new_values)) = (old_values, new_values) {
+            self.compare_enum_values(
+                &old_values.iter().collect::<Vec<_>>(),
+                &new_values.iter().collect::<Vec<_>>(),
+            );
+        }
+
+        if old_schemas.len() == 1 && new_schemas.len() == 1 {
+            self.compare_alternative(&old_schemas[0], &new_schemas[0]);
+            return;
+        }
+
+        let mut new_by_key = BTreeMap::<String, Vec<&Alternative>>::new();
+        for alternative in &new_schemas {
+            new_by_key
+                .entry(alternative_key(alternative.branch, self.new_root))
+                .or_default()
+                .push(alternative);
+        }
+        // Alternatives with the same key are matched up in order
+        for group in new_by_key.values_mut() {
+            group.reverse();
+        }
+
+        let mut removed = Vec::new();
+        for old in &old_schemas {
+            let key = alternative_key(old.branch, self.old_root);
+            match new_by_key.get_mut(&key).and_then(Vec::pop) {
+                Some(new) => self.compare_alternative(old, new),
+                None => removed.push(key),
+            }
+        }
+
+        for variant in removed {
+            self.report(Change::VariantRemoved { variant }, NARROWS);
+        }
+        for (variant, group) in new_by_key {
+            for _ in group {
+                self.report(
+                    Change::VariantAdded {
+                        variant: variant.clone(),
+                    },
+                    WIDENS,
+                );
+            }
+        }
+    }
+
+    fn compare_alternative(&mut self, old: &Alternative, new: &Alternative) {
+        let len = self.path.len();
+        if let Some((keyword, index)) = new.location {
+            push_pointer_segment(&mut self.path, keyword);
+            push_pointer_segment(&mut self.path, &index.to_string());
+        }
+        self.compare(&old.schema, &new.schema);
+        self.path.truncate(len);
+    }
+
+    fn compare_objects(&mut self, old: &Map<String, Value>, new: &Map<String, Value>) {
+        self.compare_types(old, new);
+
+        if let (Some(old_values), Some(new_values)) = (enum_values(old), enum_values(new)) {
+            self.compare_enum_values(&old_values, &new_values);
+        }
+
+        for keyword in LOWER_BOUNDS {
+            self.compare_bound(old, new, keyword, true);
+        }
+        for keyword in UPPER_BOUNDS {
+            self.compare_bound(old, new, keyword, false);
+        }
+
+        for keyword in OPAQUE_KEYWORDS {
+            let (old_value, new_value) = (old.get(keyword), new.get(keyword));
+            let effect = match (old_value, new_value) {
+                (Some(o), Some(n)) if o == n => continue,
+                (None, None) => continue,
+                (None, Some(_)) => NARROWS,
+                (Some(_), None) => WIDENS,
+                (Some(_), Some(_)) => NARROWS_AND_WIDENS,
+            };
+            self.report(
+                Change::KeywordChanged {
+                    keyword: keyword.to_owned(),
+                    old: old_value.cloned(),
+                    new: new_value.cloned(),
+                },
+                effect,
+            );
+        }
+
+        let old_unique = old.get("uniqueItems") == Some(&Value::Bool(true));
+        let new_unique = new.get("uniqueItems") == Some(&Value::Bool(true));
+        if old_unique != new_unique {
+            self.report_keyword_change(
+                "uniqueItems",
+                &Value::Bool(old_unique),
+                &Value::Bool(new_unique),
+                if new_unique { NARROWS } else { WIDENS },
+            );
+        }
+
+        self.compare_object_keywords(old, new);
+        self.compare_array_keywords(old, new);
+
+        for keyword in ["not", "if", "then", "else", "contains", "propertyNames"] {
+            if let (Some(old_sub), Some(new_sub)) = (old.get(keyword), new.get(keyword)) {
+                self.in_path(keyword, |c| c.compare(old_sub, new_sub));
+            }
+        }
+
+        if let (Some(Value::Array(old_all)), Some(Value::Array(new_all))) =
+            (old.get("allOf"), new.get("allOf"))
+        {
+            if old_all.len() == new_all.len() {
+                for (index, (old_sub, new_sub)) in old_all.iter().zip(new_all).enumerate() {
+                    self.in_path("allOf", |c| {
+                        c.in_path(&index.to_string(), |c| c.compare(old_sub, new_sub));
+                    });
+                }
+            }
+        }
+    }
+
+    fn compare_types(&mut self, old: &Map<String, Value>, new: &Map<String, Value>) {
+        let old_types = schema_types(old);
+        let new_types = schema_types(new);
+
+        if old_types.is_none() && new_types.is_none() {
+            return;
+        }
+
+        let all_types = || JSON_TYPES.iter().copied().collect::<BTreeSet<_>>();
+        let old_set = old_types.clone().unwrap_or_else(all_types);
+        let new_set = new_types.clone().unwrap_or_else(all_types);
+
+        let effect = Effect {
+            narrows: old_set.iter().any(|t| !covers_type(&new_set, t)),
+            widens: new_set.iter().any(|t| !covers_type(&old_set, t)),
+        };
+
+        if effect.narrows || effect.widens {
+            let to_vec = |types: BTreeSet<&str>| types.into_iter().map(str::to_owned).collect();
+            self.report(
+                Change::TypeChanged {
+                    old: old_types.map(to_vec),
+                    new: new_types.map(to_vec),
+                },
+                effect,
+            );
+        }
+    }
+
+    fn compare_enum_values(&mut self, old: &[&Value], new: &[&Value]) {
+        for value in old {
+            if !new.contains(value) {
+                self.report(
+                    Change::EnumValueRemoved {
+                        value: (*value).clone(),
+                    },
+                    NARROWS,
+                );
+            }
+        }
+        for value in new {
+            if !old.contains(value) {
+                self.report(
+                    Change::EnumValueAdded {
+                        value: (*value).clone(),
+                    },
+                    WIDENS,
+                );
+            }
+        }
+    }
+
+    fn compare_bound(
+        &mut self,
+        old: &Map<String, Value>,
+        new: &Map<String, Value>,
+        keyword: &str,
+        is_lower_bound: bool,
+    ) {
+        let old_value = old.get(keyword).filter(|v| v.is_number());
+        let new_value = new.get(keyword).filter(|v| v.is_number());
+
+        let stricter = match (
+            old_value.and_then(Value::as_f64),
+            new_value.and_then(Value::as_f64),
+        ) {
+            (None, Some(_)) => true,
+            (Some(_), None) => false,
+            (Some(o), Some(n)) if o < n => is_lower_bound,
+            (Some(o), Some(n)) if o > n => !is_lower_bound,
+            _ => return,
+        };
+
+        self.report(
+            Change::KeywordChanged {
+                keyword: keyword.to_owned(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+            },
+            if stricter { NARROWS } else { WIDENS },
+        );
+    }
+
+    fn compare_object_keywords(&mut self, old: &Map<String, Value>, new: &Map<String, Value>) {
+        let old_required = string_set(old.get("required"));
+        let new_required = string_set(new.get("required"));
+
+        for property in new_required.difference(&old_required) {
+            self.report(
+                Change::PropertyNowRequired {
+                    property: (*property).to_owned(),
+                },
+                NARROWS,
+            );
+        }
+        for property in old_required.difference(&new_required) {
+            self.report(
+                Change::PropertyNoLongerRequired {
+                    property: (*property).to_owned(),
+                },
+                WIDENS,
+            );
+        }
+
+        let empty = Map::new();
+        let old_properties = old
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        let new_properties = new
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        let old_additional = additional_properties(old);
+        let new_additional = additional_properties(new);
+
+        for (name, old_property) in old_properties {
+            if let Some(new_property) = new_properties.get(name) {
+                self.in_path("properties", |c| {
+                    c.in_path(name, |c| c.compare(old_property, new_property));
+                });
+            } else {
+                match new_additional {
+                    Some(Value::Bool(false)) => self.report(
+                        Change::PropertyRemoved {
+                            property: name.clone(),
+                        },
+                        NARROWS,
+                    ),
+                    Some(additional @ Value::Object(_)) => {
+                        self.in_path("additionalProperties", |c| {
+                            c.compare(old_property, additional);
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for (name, new_property) in new_properties {
+            if old_properties.contains_key(name) {
+                continue;
+            }
+            match old_additional {
+                Some(Value::Bool(false)) => self.report(
+                    Change::PropertyAdded {
+                        property: name.clone(),
+                    },
+                    WIDENS,
+                ),
+                Some(additional @ Value::Object(_)) => self.in_path("properties", |c| {
+                    c.in_path(name, |c| c.compare(additional, new_property));
+                }),
+                _ => {}
+            }
+        }
+
+        match (old_additional, new_additional) {
+            (Some(old_additional), Some(new_additional)) => {
+                let keyword = if new.contains_key("additionalProperties") {
+                    "additionalProperties"
+                } else {
+                    "unevaluatedProperties"
+                };
+                self.in_path(keyword, |c| c.compare(old_additional, new_additional));
+            }
+            (None, Some(new_additional)) if new_additional != &Value::Bool(true) => {
+                self.report_keyword_change(
+                    "additionalProperties",
+                    &Value::Bool(true),
+                    new_additional,
+                    NARROWS,
+                );
+            }
+            (Some(old_additional), None) if old_additional != &Value::Bool(true) => {
+                self.report_keyword_change(
+                    "additionalProperties",
+                    old_additional,
+                    &Value::Bool(true),
+                    WIDENS,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn compare_array_keywords(&mut self, old: &Map<String, Value>, new: &Map<String, Value>) {
+        if let (Some(Value::Array(old_prefix)), Some(Value::Array(new_prefix))) =
+            (old.get("prefixItems"), new.get("prefixItems"))
+        {
+            for (index, (old_item, new_item)) in old_prefix.iter().zip(new_prefix).enumerate() {
+                self.in_path("prefixItems", |c| {
+                    c.in_path(&index.to_string(), |c| c.compare(old_item, new_item));
+                });
+            }
+        }
+
+        match (old.get("items"), new.get("items")) {
+            (Some(Value::Array(old_items)), Some(Value::Array(new_items))) => {
+                for (index, (old_item, new_item)) in old_items.iter().zip(new_items).enumerate() {
+                    self.in_path("items", |c| {
+                        c.in_path(&index.to_string(), |c| c.compare(old_item, new_item));
+                    });
+                }
+            }
+            (Some(old_items), Some(new_items))
+                if !old_items.is_array() && !new_items.is_array() =>
+            {
+                self.in_path("items", |c| c.compare(old_items, new_items));
+            }
+            _ => {}
+        }
+    }
+
+    fn in_path(&mut self, segment: &str, f: impl FnOnce(&mut Self)) {
+        let len = self.path.len();
+        push_pointer_segment(&mut self.path, segment);
+        f(self);
+        self.path.truncate(len);
+    }
+
+    fn report_keyword_change(&mut self, keyword: &str, old: &Value, new: &Value, effect: Effect) {
+        self.report(
+            Change::KeywordChanged {
+                keyword: keyword.to_owned(),
+                old: Some(old.clone()),
+                new: Some(new.clone()),
+            },
+            effect,
+        );
+    }
+
+    fn report(&mut self, change: Change, effect: Effect) {
+        // For the deserialize contract, rejecting previously-valid data breaks existing clients.
+        // For the serialize contract, producing previously-invalid data breaks existing clients.
+        let (backward, forward) = if self.contract.is_serialize() {
+            (effect.widens, effect.narrows)
+        } else {
+            (effect.narrows, effect.widens)
+        };
+
+        let incompatibility = match (backward, forward) {
+            (true, true) => Incompatibility::Both,
+            (true, false) => Incompatibility::Backward,
+            (false, true) => Incompatibility::Forward,
+            (false, false) => return,
+        };
+
+        self.findings.push(Finding {
+            path: self.path.clone(),
+            change,
+            incompatibility,
+        });
+    }
+}
+
+enum Resolved<'v> {
+    Both(&'v Value, &'v Value),
+    // Only one of the schemas was a `$ref`, and the pair has been pushed to `in_progress`
+    OneSided(&'v Value, &'v Value),
+    AlreadyVisited,
+    Unresolvable,
+}
+
+fn alternative_key(branch: &Value, root: &Schema) -> String {
+    let schema = follow_value_refs(branch, |r| root.resolve_ref(r));
+    let Some(obj) = schema.as_object() else {
+        return schema.to_string();
+    };
+
+    let properties = obj.get("properties").and_then(Value::as_object);
+    let required = string_set(obj.get("required"));
+
+    // Internally/adjacently tagged enum variants have a required tag property with a `const`
+    if let Some(properties) = properties {
+        for (name, property) in properties {
+            let tag = follow_value_refs(property, |r| root.resolve_ref(r))
+                .get("const")
+                .filter(|_| required.contains(name.as_str()));
+            if let Some(tag) = tag {
+                return match tag {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+            }
+        }
+    }
+
+    if let Some(title) = obj.get("title").and_then(Value::as_str) {
+        return title.to_owned();
+    }
+
+    // Externally tagged enum variants have a single required property
+    if let Some(properties) = properties {
+        if properties.len() == 1 && required.len() == 1 {
+            if let Some(name) = properties.keys().next() {
+                if required.contains(name.as_str()) {
+                    return name.clone();
+                }
+            }
+        }
+    }
+
+    schema_types(obj).map_or_else(String::new, |t| {
+        t.into_iter().collect::<Vec<_>>().join(" | ")
+    })
+}
+
+// A subschema of `oneOf`/`anyOf`, merged with the other keywords of the schema containing it
+// (which also apply to values matching the subschema). A schema without `oneOf`/`anyOf` is
+// treated as having a single alternative.
+struct Alternative<'v> {
+    schema: Cow<'v, Value>,
+    branch: &'v Value,
+    location: Option<(&'static str, usize)>,
+}
+
+// Splits a schema's alternatives into the values allowed by those that only allow specific
+// values (via `enum`/`const`), and all other alternatives.
+fn partition_alternatives<'v>(
+    schema: &'v Value,
+    root: &'v Schema,
+) -> (Option<Vec<Value>>, Vec<Alternative<'v>>) {
+    let mut values: Option<Vec<Value>> = None;
+    let mut others = Vec::new();
+
+    for alternative in alternatives(schema) {
+        let resolved = match &alternative.schema {
+            Cow::Borrowed(schema) => follow_value_refs(schema, |r| root.resolve_ref(r)),
+            Cow::Owned(schema) => schema,
+        };
+        match resolved.as_object().and_then(enum_values) {
+            Some(v) => values
+                .get_or_insert_with(Vec::new)
+                .extend(v.into_iter().cloned()),
+            None => others.push(alternative),
+        }
+    }
+
+    (values, others)
+}
+
+fn has_alternatives(obj: &Map<String, Value>) -> bool {
+    obj.contains_key("oneOf") || obj.contains_key("anyOf")
+}
+
+fn alternatives(schema: &Value) -> Vec<Alternative<'_>> {
+    let Some(obj) = schema.as_object() else {
+        return vec![Alternative {
+            schema: Cow::Borrowed(schema),
+            branch: schema,
+            location: None,
+        }];
+    };
+
+    let siblings = obj
+        .iter()
+        .filter(|(k, _)| !matches!(k.as_str(), "oneOf" | "anyOf" | "title" | "description"))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect::<Map<_, _>>();
+
+    for keyword in ["oneOf", "anyOf"] {
+        if let Some(Value::Array(branches)) = obj.get(keyword) {
+            return branches
+                .iter()
+                .enumerate()
+                .map(|(index, branch)| Alternative {
+                    schema: if siblings.is_empty() {
+                        Cow::Borrowed(branch)
+                    } else {
+                        Cow::Owned(merge_alternative(siblings.clone(), branch))
+                    },
+                    branch,
+                    location: Some((keyword, index)),
+                })
+                .collect();
+        }
+    }
+
+    vec![Alternative {
+        schema: Cow::Borrowed(schema),
+        branch: schema,
+        location: None,
+    }]
+}
+
+fn merge_alternative(mut merged: Map<String, Value>, branch: &Value) -> Value {
+    match branch.as_object() {
+        Some(obj) if !obj.contains_key("$ref") => {
+            for (key, value) in obj {
+                match (key.as_str(), merged.get_mut(key), value) {
+                    ("properties", Some(Value::Object(existing)), Value::Object(properties)) => {
+                        existing.extend(properties.iter().map(|(k, v)| (k.clone(), v.clone())));
+                    }
+                    ("required", Some(Value::Array(existing)), Value::Array(required)) => {
+                        for r in required {
+                            if !existing.contains(r) {
+                                existing.push(r.clone());
+                            }
+                        }
+                    }
+                    _ => {
+                        merged.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        // `$ref`s (and bool schemas) are kept intact so that they can be followed with cycle
+        // detection
+        _ => match merged.get_mut("allOf") {
+            Some(Value::Array(all_of)) => all_of.push(branch.clone()),
+            _ => {
+                merged.insert("allOf".to_owned(), Value::Array(vec![branch.clone()]));
+            }
+        },
+    }
+
+    Value::Object(merged)
+}
+
+fn enum_values(obj: &Map<String, Value>) -> Option<Vec<&Value>> {
+    match (obj.get("enum"), obj.get("const")) {
+        (Some(Value::Array(values)), _) => Some(values.iter().collect()),
+        (_, Some(value)) => Some(vec![value]),
+        _ => None,
+    }
+}
+
+fn schema_types(obj: &Map<String, Value>) -> Option<BTreeSet<&str>> {
+    match obj.get("type")? {
+        Value::String(ty) => Some(BTreeSet::from([ty.as_str()])),
+        Value::Array(types) => Some(types.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn bool_schema_types(schema: &Value) -> Option<Vec<String>> {
+    match schema {
+        Value::Bool(false) => Some(Vec::new()),
+        Value::Object(obj) => schema_types(obj).map(|t| t.into_iter().map(str::to_owned).collect()),
+        _ => None,
+    }
+}
+
+fn covers_type(types: &BTreeSet<&str>, ty: &str) -> bool {
+    types.contains(ty) || (ty == "integer" && types.contains("number"))
+}
+
+fn string_set(value: Option<&Value>) -> BTreeSet<&str> {
+    value
+        .and_then(Value::as_array)
+        .map(|a| a.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn additional_properties(obj: &Map<String, Value>) -> Option<&Value> {
+    obj.get("additionalProperties")
+        .or_else(|| obj.get("unevaluatedProperties"))
+}
@@ -4,6 +4,14 @@ Constants associated with JSON Schema generation.
 
 /// Known values of the `$schema` property.
 pub mod meta_schemas {
+    /// The meta-schema for [JSON Schema Draft 4](https://json-schema.org/specification-links#draft-4)
+    /// (`http://json-schema.org/draft-04/schema#`).
+    pub const DRAFT04: &str = "http://json-schema.org/draft-04/schema#";
+
+    /// The meta-schema for [JSON Schema Draft 6](https://json-schema.org/specification-links#draft-6)
+    /// (`http://json-schema.org/draft-06/schema#`).
+    pub const DRAFT06: &str = "http://json-schema.org/draft-06/schema#";
+
     /// The meta-schema for [JSON Schema Draft 7](https://json-schema.org/specification-links#draft-7)
     /// (`http://json-schema.org/draft-07/schema#`).
     pub const DRAFT07: &str = "http://json-schema.org/draft-07/schema#";
@@ -23,4 +31,12 @@ pub mod meta_schemas {
     /// embedded within OpenAPI documents, so do not have a `$schema` property set.
     pub const OPENAPI3: &str =
         "https://spec.openapis.org/oas/3.0/schema/2024-10-18#/definitions/Schema";
+
+    /// The meta-schema for [OpenAPI 3.1 schemas](https://spec.openapis.org/oas/v3.1.1.html#schema-object)
+    /// (`https://spec.openapis.org/oas/3.1/dialect/base`).
+    ///
+    /// This is also the default value of the `jsonSchemaDialect` field of OpenAPI 3.1 documents,
+    /// so schemas embedded within such a document do not need to have their `$schema` property
+    /// set unless the document overrides `jsonSchemaDialect`.
+    pub const OPENAPI3_1: &str = "https://spec.openapis.org/oas/3.1/dialect/base";
 }
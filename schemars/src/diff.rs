@@ -0,0 +1,463 @@
+/*!
+Structural comparison of two versions of a [`Schema`].
+
+Where the [`compat`](crate::compat) module reports whether changes to a schema may break clients,
+[`compare`] reports _every_ change between two schemas, for reviewing what changed (e.g. in a pull
+request that changes a generated schema). The result can be converted into an [RFC 6902 JSON
+Patch](https://datatracker.ietf.org/doc/html/rfc6902) using [`Diff::to_json_patch`], or into a
+Markdown report using [`Diff::to_markdown`].
+
+The comparison is structural rather than textual, so formatting and the order of keywords within
+schema objects are ignored. The values of `required`, `enum` and `type` are compared as sets, so
+reordering their items is not reported as a change. Definitions (under `$defs` or `definitions`)
+are matched by name, and the Markdown report groups changes by the definition that they were
+made in.
+
+# Example
+```
+use schemars::{diff, json_schema};
+
+let old = json_schema!({
+    "type": "object",
+    "properties": {
+        "name": { "type": "string" },
+        "nickname": { "type": "string" }
+    },
+    "required": ["name"]
+});
+let new = json_schema!({
+    "type": "object",
+    "properties": {
+        "name": { "type": "string", "maxLength": 100 },
+        "age": { "type": "integer" }
+    },
+    "required": ["age", "name"]
+});
+
+let diff = diff::compare(&old, &new);
+
+assert_eq!(
+    diff.to_json_patch(),
+    serde_json::json!([
+        { "op": "add", "path": "/properties/age", "value": { "type": "integer" } },
+        { "op": "add", "path": "/properties/name/maxLength", "value": 100 },
+        { "op": "remove", "path": "/properties/nickname" },
+        { "op": "add", "path": "/required/-", "value": "age" }
+    ])
+);
+
+assert_eq!(
+    diff.to_markdown(),
+    concat!(
+        "## Root schema\n",
+        "\n",
+        "**Added properties**\n",
+        "- `age` (required)\n",
+        "\n",
+        "**Removed properties**\n",
+        "- `nickname`\n",
+        "\n",
+        "**Changed properties**\n",
+        "- `name`: `maxLength` added: `100`\n",
+    )
+);
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::encoding::push_pointer_segment;
+use crate::Schema;
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt::{self, Write as _};
+use serde_json::{json, Map, Value};
+
+/// Compares two versions of a schema, returning all changes needed to turn `old` into `new`.
+///
+/// See the [module documentation](crate::diff) for more information.
+#[must_use]
+pub fn compare(old: &Schema, new: &Schema) -> Diff {
+    let mut differ = Differ {
+        path: String::new(),
+        operations: Vec::new(),
+    };
+    differ.compare(old.as_value(), new.as_value());
+
+    Diff {
+        root_title: new
+            .get("title")
+            .or_else(|| old.get("title"))
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned),
+        operations: differ.operations,
+    }
+}
+
+/// The changes between two versions of a schema, returned by [`compare`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diff {
+    root_title: Option<String>,
+    operations: Vec<Operation>,
+}
+
+/// A single change between two versions of a schema.
+///
+/// Each operation corresponds to a JSON Patch operation, but also records the value that was
+/// removed or replaced.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+#[non_exhaustive]
+pub enum Operation {
+    /// A value was added at `path`.
+    ///
+    /// When a value was added to a keyword that is compared as a set (e.g. `required`), the final
+    /// segment of `path` is `-`.
+    Add { path: String, value: Value },
+    /// The value `old` at `path` was removed.
+    Remove { path: String, old: Value },
+    /// The value `old` at `path` was replaced with `value`.
+    Replace {
+        path: String,
+        old: Value,
+        value: Value,
+    },
+}
+
+impl Operation {
+    /// Returns the JSON Pointer to the value that the operation changes.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        match self {
+            Operation::Add { path, .. }
+            | Operation::Remove { path, .. }
+            | Operation::Replace { path, .. } => path,
+        }
+    }
+
+    /// Converts the operation into a JSON Patch operation.
+    #[must_use]
+    pub fn to_json_patch(&self) -> Value {
+        match self {
+            Operation::Add { path, value } => json!({ "op": "add", "path": path, "value": value }),
+            Operation::Remove { path, .. } => json!({ "op": "remove", "path": path }),
+            Operation::Replace { path, value, .. } => {
+                json!({ "op": "replace", "path": path, "value": value })
+            }
+        }
+    }
+}
+
+impl Diff {
+    /// Returns true if the schemas are structurally identical.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Returns the changes between the schemas, in the order that they should be applied.
+    #[must_use]
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// Returns the changes as an RFC 6902 JSON Patch document, which turns the old schema into
+    /// the new schema when applied to it.
+    #[must_use]
+    pub fn to_json_patch(&self) -> Value {
+        Value::Array(
+            self.operations
+                .iter()
+                .map(Operation::to_json_patch)
+                .collect(),
+        )
+    }
+
+    /// Returns a Markdown report of the changes, with a section for the root schema and each
+    /// changed definition.
+    ///
+    /// Within each section, changes are grouped into added, removed and changed properties,
+    /// followed by any other changes to the schema.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        if self.is_empty() {
+            return "No changes.\n".to_owned();
+        }
+
+        let mut sections = BTreeMap::<Option<&str>, Section>::new();
+        for operation in &self.operations {
+            let (definition, relative_path) = split_definition(operation.path());
+            sections
+                .entry(definition)
+                .or_default()
+                .add(operation, relative_path);
+        }
+
+        let mut out = String::new();
+        for (definition, section) in sections {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            match definition {
+                Some(name) => _ = writeln!(out, "## `{}`", decode_segment(name)),
+                None => match &self.root_title {
+                    Some(title) => _ = writeln!(out, "## Root schema (`{title}`)"),
+                    None => out.push_str("## Root schema\n"),
+                },
+            }
+            section.write(&mut out);
+        }
+        out
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_markdown())
+    }
+}
+
+// Keywords whose array values are compared as sets rather than item-by-item
+const SET_KEYWORDS: [&str; 3] = ["required", "enum", "type"];
+
+struct Differ {
+    path: String,
+    operations: Vec<Operation>,
+}
+
+impl Differ {
+    fn compare(&mut self, old: &Value, new: &Value) {
+        if old == new {
+            return;
+        }
+
+        match (old, new) {
+            (Value::Object(old_obj), Value::Object(new_obj)) => {
+                self.compare_objects(old_obj, new_obj);
+            }
+            (Value::Array(old_items), Value::Array(new_items)) => {
+                if is_set_path(&self.path) {
+                    self.compare_sets(old_items, new_items);
+                } else {
+                    self.compare_arrays(old_items, new_items);
+                }
+            }
+            _ => self.operations.push(Operation::Replace {
+                path: self.path.clone(),
+                old: old.clone(),
+                value: new.clone(),
+            }),
+        }
+    }
+
+    fn compare_objects(&mut self, old: &Map<String, Value>, new: &Map<String, Value>) {
+        // Keys are visited in sorted order, so that the result does not depend on whether the
+        // `preserve_order` feature is enabled
+        let keys = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+
+        for key in keys {
+            match (old.get(key), new.get(key)) {
+                (Some(old_value), Some(new_value)) => {
+                    self.in_path(key, |d| d.compare(old_value, new_value));
+                }
+                (Some(old_value), None) => self.in_path(key, |d| d.remove(old_value)),
+                (None, Some(new_value)) => self.in_path(key, |d| d.add(new_value)),
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn compare_arrays(&mut self, old: &[Value], new: &[Value]) {
+        for (index, (old_item, new_item)) in old.iter().zip(new).enumerate() {
+            self.in_path(&index.to_string(), |d| d.compare(old_item, new_item));
+        }
+
+        // Removals are applied from the end, so that each index is still valid
+        for index in (new.len()..old.len()).rev() {
+            self.in_path(&index.to_string(), |d| d.remove(&old[index]));
+        }
+        for (index, new_item) in new.iter().enumerate().skip(old.len()) {
+            self.in_path(&index.to_string(), |d| d.add(new_item));
+        }
+    }
+
+    fn compare_sets(&mut self, old: &[Value], new: &[Value]) {
+        for (index, old_item) in old.iter().enumerate().rev() {
+            if !new.contains(old_item) {
+                self.in_path(&index.to_string(), |d| d.remove(old_item));
+            }
+        }
+        for new_item in new {
+            if !old.contains(new_item) {
+                self.in_path("-", |d| d.add(new_item));
+            }
+        }
+    }
+
+    fn add(&mut self, value: &Value) {
+        self.operations.push(Operation::Add {
+            path: self.path.clone(),
+            value: value.clone(),
+        });
+    }
+
+    fn remove(&mut self, old: &Value) {
+        self.operations.push(Operation::Remove {
+            path: self.path.clone(),
+            old: old.clone(),
+        });
+    }
+
+    fn in_path(&mut self, segment: &str, f: impl FnOnce(&mut Self)) {
+        let len = self.path.len();
+        push_pointer_segment(&mut self.path, segment);
+        f(self);
+        self.path.truncate(len);
+    }
+}
+
+// The changes within the root schema or a single definition
+#[derive(Default)]
+struct Section {
+    added_properties: Vec<String>,
+    removed_properties: Vec<String>,
+    changed_properties: Vec<String>,
+    other_changes: Vec<String>,
+}
+
+impl Section {
+    fn add(&mut self, operation: &Operation, relative_path: &str) {
+        let segments = relative_path
+            .split('/')
+            .skip(1)
+            .map(decode_segment)
+            .collect::<Vec<_>>();
+
+        match (segments.as_slice(), operation) {
+            ([], Operation::Add { .. }) => self.other_changes.push("definition added".to_owned()),
+            ([], Operation::Remove { .. }) => {
+                self.other_changes.push("definition removed".to_owned());
+            }
+            ([keyword, name], Operation::Add { .. }) if keyword == "properties" => {
+                self.added_properties.push(format!("`{name}`"));
+            }
+            ([keyword, name], Operation::Remove { .. }) if keyword == "properties" => {
+                self.removed_properties.push(format!("`{name}`"));
+            }
+            ([keyword, name, rest @ ..], _) if keyword == "properties" => {
+                let change = describe(rest, operation);
+                self.changed_properties.push(format!("`{name}`: {change}"));
+            }
+            ([keyword, _], Operation::Add { value, .. } | Operation::Remove { old: value, .. })
+                if keyword == "required" =>
+            {
+                let property = value.as_str().unwrap_or_default();
+                let added = matches!(operation, Operation::Add { .. });
+                self.required_changed(property, added);
+            }
+            _ => self.other_changes.push(describe(&segments, operation)),
+        }
+    }
+
+    fn required_changed(&mut self, property: &str, added: bool) {
+        let entry = format!("`{property}`");
+        if let Some(existing) = self
+            .added_properties
+            .iter_mut()
+            .chain(&mut self.removed_properties)
+            .find(|p| **p == entry)
+        {
+            existing.push_str(if added {
+                " (required)"
+            } else {
+                " (was required)"
+            });
+        } else if added {
+            self.changed_properties
+                .push(format!("{entry}: now required"));
+        } else {
+            self.changed_properties
+                .push(format!("{entry}: no longer required"));
+        }
+    }
+
+    fn write(&self, out: &mut String) {
+        for (heading, items) in [
+            ("Added properties", &self.added_properties),
+            ("Removed properties", &self.removed_properties),
+            ("Changed properties", &self.changed_properties),
+            ("Other changes", &self.other_changes),
+        ] {
+            if !items.is_empty() {
+                _ = write!(out, "\n**{heading}**\n");
+                for item in items {
+                    _ = writeln!(out, "- {item}");
+                }
+            }
+        }
+    }
+}
+
+// Describes an operation on the value at `segments`, relative to the schema being reported on.
+fn describe(segments: &[String], operation: &Operation) -> String {
+    let (keyword, set_value) = match segments {
+        [parent @ .., keyword, last] if is_set_keyword(keyword) => {
+            let index_or_append = last == "-" || last.parse::<usize>().is_ok();
+            (join_segments(parent, keyword), index_or_append)
+        }
+        [] => (String::new(), false),
+        [parent @ .., last] => (join_segments(parent, last), false),
+    };
+    let subject = if keyword.is_empty() {
+        "schema".to_owned()
+    } else {
+        format!("`{keyword}`")
+    };
+
+    match operation {
+        Operation::Add { value, .. } if set_value => format!("{subject} value `{value}` added"),
+        Operation::Remove { old, .. } if set_value => format!("{subject} value `{old}` removed"),
+        Operation::Add { value, .. } => format!("{subject} added: `{value}`"),
+        Operation::Remove { old, .. } => format!("{subject} removed (was `{old}`)"),
+        Operation::Replace { old, value, .. } => {
+            format!("{subject} changed from `{old}` to `{value}`")
+        }
+    }
+}
+
+fn join_segments(parent: &[String], last: &str) -> String {
+    let mut joined = parent.join("/");
+    if !joined.is_empty() {
+        joined.push('/');
+    }
+    joined.push_str(last);
+    joined
+}
+
+// Splits a JSON Pointer into the name of the definition that it points within (if any), and the
+// remainder of the pointer relative to that definition.
+fn split_definition(path: &str) -> (Option<&str>, &str) {
+    for keyword in ["/$defs/", "/definitions/"] {
+        if let Some(rest) = path.strip_prefix(keyword) {
+            let (name, relative) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+            return (Some(name), relative);
+        }
+    }
+
+    (None, path)
+}
+
+fn decode_segment(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+fn is_set_keyword(keyword: &str) -> bool {
+    SET_KEYWORDS.contains(&keyword)
+}
+
+// Whether the pointer is to the value of a keyword that is compared as a set, rather than e.g. a
+// property named "required"
+fn is_set_path(path: &str) -> bool {
+    let mut segments = path.rsplit('/');
+    let last = segments.next().unwrap_or_default();
+    let parent = segments.next().unwrap_or_default();
+    is_set_keyword(last) && !matches!(parent, "properties" | "$defs" | "definitions")
+}
@@ -68,6 +68,18 @@ pub fn percent_decode(s: &str) -> Option<Cow<'_, str>> {
     }
 }
 
+/// Appends a `/`-prefixed reference token to a JSON Pointer, escaping `~` and `/`.
+pub fn push_pointer_segment(pointer: &mut String, segment: &str) {
+    pointer.push('/');
+    for c in segment.chars() {
+        match c {
+            '~' => pointer.push_str("~0"),
+            '/' => pointer.push_str("~1"),
+            c => pointer.push(c),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
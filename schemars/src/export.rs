@@ -0,0 +1,439 @@
+/*!
+Exporting schemas to files, and checking that previously exported files are up to date.
+
+An [`Exporter`] collects a list of named types, and either [writes](Exporter::write) their schemas
+to a directory or [checks](Exporter::check) that the files in a directory match the schemas that
+would be written. This is useful in build scripts, or in tests that fail in CI when the committed
+schema files are stale.
+
+Schemas are written as pretty-printed JSON, with keywords in the same stable order as when
+serializing a [`Schema`].
+
+# Example
+```
+use schemars::export::Exporter;
+use schemars::JsonSchema;
+
+#[derive(JsonSchema)]
+struct User {
+    name: String,
+}
+
+#[derive(JsonSchema)]
+struct Order {
+    user: User,
+    total: u64,
+}
+
+let exporter = Exporter::default()
+    .with_type::<User>("user")
+    .with_type::<Order>("order");
+
+let dir = std::env::temp_dir().join("schemars-export-doctest");
+exporter.write(&dir)?;
+
+assert!(exporter.check(&dir)?.is_empty());
+assert!(dir.join("user.json").is_file());
+assert!(dir.join("order.json").is_file());
+# std::fs::remove_dir_all(&dir)?;
+# Ok::<(), std::io::Error>(())
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::encoding::push_pointer_segment;
+use crate::{JsonSchema, Schema, SchemaGenerator};
+use core::fmt;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Collects named types whose schemas should be exported to files.
+///
+/// Schemas are generated using the [`SchemaGenerator`] that the `Exporter` was created from, or
+/// a default `SchemaGenerator` when using [`Exporter::default()`].
+#[derive(Debug, Clone)]
+pub struct Exporter {
+    generator: SchemaGenerator,
+    entries: Vec<Entry>,
+    layout: Layout,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    root_schema: fn(SchemaGenerator) -> Schema,
+    subschema: fn(&mut SchemaGenerator) -> Schema,
+}
+
+/// How the schemas of an [`Exporter`] are arranged into files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Layout {
+    /// Each schema is written to its own file named `<name>.json`, as generated by
+    /// [`SchemaGenerator::into_root_schema_for()`].
+    ///
+    /// This is the default layout.
+    FilePerSchema,
+    /// All schemas are written to a single file with the given name, which contains every schema
+    /// in its definitions (e.g. under `$defs`).
+    ///
+    /// Types that are not [inlined](JsonSchema::inline_schema) are included under their usual
+    /// definition name, along with a `$ref` to that definition under the name they were added to
+    /// the `Exporter` with (unless that name is already used by another definition). Inlined types
+    /// are included under the name they were added to the `Exporter` with.
+    Bundle(PathBuf),
+}
+
+/// The contents of a file that would be written by an [`Exporter`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ExportedFile {
+    /// The path of the file, relative to the export directory.
+    pub path: PathBuf,
+    /// The schema contained in the file.
+    pub schema: Schema,
+    /// The serialized contents of the file.
+    pub contents: String,
+}
+
+/// An exported file that does not match the schema that would be written by an [`Exporter`], as
+/// returned by [`Exporter::check()`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct StaleFile {
+    /// The path of the file, including the export directory.
+    pub path: PathBuf,
+    /// Why the file is stale.
+    pub reason: StaleReason,
+}
+
+/// The reason that an exported file is stale.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum StaleReason {
+    /// The file does not exist.
+    Missing,
+    /// The file does not contain valid JSON. The string contains the parsing error.
+    InvalidJson(String),
+    /// The file contains a different schema. Each [`Difference`] is a value within the file that
+    /// differs from the expected schema.
+    Changed(Vec<Difference>),
+    /// The file contains the expected schema, but it is formatted differently (e.g. keywords are
+    /// in a different order).
+    Reformatted,
+}
+
+/// A value within an exported file that differs from the expected schema.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Difference {
+    /// A JSON pointer to the differing value within the file.
+    pub pointer: String,
+    /// The value within the existing file, or `None` if the value is missing.
+    pub existing: Option<Value>,
+    /// The value within the expected schema, or `None` if the value should be removed.
+    pub expected: Option<Value>,
+}
+
+impl Default for Exporter {
+    fn default() -> Self {
+        Self::new(SchemaGenerator::default())
+    }
+}
+
+impl From<SchemaGenerator> for Exporter {
+    fn from(generator: SchemaGenerator) -> Self {
+        Self::new(generator)
+    }
+}
+
+impl Exporter {
+    /// Creates an `Exporter` that generates schemas using the given [`SchemaGenerator`].
+    ///
+    /// Any definitions that have already been added to the `SchemaGenerator` are included in the
+    /// exported schemas.
+    #[must_use]
+    pub fn new(generator: SchemaGenerator) -> Self {
+        Self {
+            generator,
+            entries: Vec::new(),
+            layout: Layout::FilePerSchema,
+        }
+    }
+
+    /// Adds the type `T` to the schemas that will be exported, using the given name.
+    ///
+    /// With the default [`Layout::FilePerSchema`], the schema for `T` is written to a file named
+    /// `<name>.json`.
+    #[must_use]
+    pub fn with_type<T: ?Sized + JsonSchema>(mut self, name: impl Into<String>) -> Self {
+        self.entries.push(Entry {
+            name: name.into(),
+            root_schema: SchemaGenerator::into_root_schema_for::<T>,
+            subschema: SchemaGenerator::subschema_for::<T>,
+        });
+        self
+    }
+
+    /// Adds the types in the [registry](crate::registry) to the schemas that will be exported,
+    /// using each type's [schema name](JsonSchema::schema_name) as its name.
+    ///
+    /// If `group` is `Some`, then only the types in that group are added.
+    #[cfg(feature = "registry")]
+    #[must_use]
+    pub fn with_registered_types(mut self, group: Option<&str>) -> Self {
+        let registered = crate::registry::iter().filter(|r| group.is_none() || r.group() == group);
+        for registered in registered {
+            self.entries.push(Entry {
+                name: registered.schema_name().into_owned(),
+                root_schema: registered.root_schema_fn(),
+                subschema: registered.subschema_fn(),
+            });
+        }
+        self
+    }
+
+    /// Sets how the schemas are arranged into files.
+    #[must_use]
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Writes all schemas into a single file with the given name.
+    ///
+    /// This is equivalent to calling [`with_layout()`](Exporter::with_layout) with
+    /// [`Layout::Bundle`].
+    #[must_use]
+    pub fn bundled(self, file_name: impl Into<PathBuf>) -> Self {
+        self.with_layout(Layout::Bundle(file_name.into()))
+    }
+
+    /// Generates the files that would be written by this `Exporter`, without writing them.
+    #[must_use]
+    pub fn files(&self) -> Vec<ExportedFile> {
+        match &self.layout {
+            Layout::FilePerSchema => self
+                .entries
+                .iter()
+                .map(|entry| {
+                    let schema = (entry.root_schema)(self.generator.clone());
+                    ExportedFile::new(format!("{}.json", entry.name).into(), schema)
+                })
+                .collect(),
+            Layout::Bundle(file_name) => {
+                vec![ExportedFile::new(file_name.clone(), self.bundle())]
+            }
+        }
+    }
+
+    /// Writes the schemas to files within the given directory, creating the directory if it does
+    /// not exist.
+    ///
+    /// Files whose contents are already up to date are not re-written.
+    pub fn write(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+
+        for file in self.files() {
+            let path = dir.join(&file.path);
+            match fs::read_to_string(&path) {
+                Ok(existing) if existing == file.contents => continue,
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, file.contents)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the files within the given directory match the schemas that would be written
+    /// by [`write()`](Exporter::write), without modifying any files.
+    ///
+    /// Returns a list of the files that are missing or out of date, which will be empty if all
+    /// files are up to date. Files in the directory that would not be written by this `Exporter`
+    /// are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::export::{Exporter, StaleReason};
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// let dir = std::env::temp_dir().join("schemars-check-doctest");
+    /// # let _ = std::fs::remove_dir_all(&dir);
+    /// let exporter = Exporter::default().with_type::<User>("user");
+    ///
+    /// let stale = exporter.check(&dir)?;
+    /// assert_eq!(stale.len(), 1);
+    /// assert_eq!(stale[0].path, dir.join("user.json"));
+    /// assert_eq!(stale[0].reason, StaleReason::Missing);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn check(&self, dir: impl AsRef<Path>) -> io::Result<Vec<StaleFile>> {
+        let dir = dir.as_ref();
+        let mut stale = Vec::new();
+
+        for file in self.files() {
+            let path = dir.join(&file.path);
+            let existing = match fs::read_to_string(&path) {
+                Ok(existing) if existing == file.contents => continue,
+                Ok(existing) => existing,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    stale.push(StaleFile {
+                        path,
+                        reason: StaleReason::Missing,
+                    });
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let reason = match serde_json::from_str::<Value>(&existing) {
+                Err(e) => StaleReason::InvalidJson(e.to_string()),
+                Ok(existing) => {
+                    let mut differences = Vec::new();
+                    diff_values(
+                        &mut String::new(),
+                        Some(&existing),
+                        Some(file.schema.as_value()),
+                        &mut differences,
+                    );
+                    if differences.is_empty() {
+                        StaleReason::Reformatted
+                    } else {
+                        StaleReason::Changed(differences)
+                    }
+                }
+            };
+            stale.push(StaleFile { path, reason });
+        }
+
+        Ok(stale)
+    }
+
+    fn bundle(&self) -> Schema {
+        let mut generator = self.generator.clone();
+        let mut named_schemas = Vec::new();
+        for entry in &self.entries {
+            let schema = (entry.subschema)(&mut generator);
+            named_schemas.push((entry.name.clone(), schema));
+        }
+
+        let mut definitions = generator.take_definitions(false);
+        for (name, schema) in named_schemas {
+            definitions.entry(name).or_insert(schema.to_value());
+        }
+
+        let mut bundle = Schema::default();
+        let object = bundle.ensure_object();
+        if let Some(meta_schema) = generator.settings().meta_schema.as_deref() {
+            object.insert("$schema".into(), meta_schema.into());
+        }
+        generator.add_definitions(object, definitions);
+        generator.apply_transforms(&mut bundle);
+
+        bundle
+    }
+}
+
+impl ExportedFile {
+    #[allow(clippy::missing_panics_doc)] // should never panic - schemas always have string keys
+    fn new(path: PathBuf, schema: Schema) -> Self {
+        let mut contents = serde_json::to_string_pretty(&schema)
+            .expect("serializing a schema to a string should never fail");
+        contents.push('\n');
+
+        Self {
+            path,
+            schema,
+            contents,
+        }
+    }
+}
+
+impl fmt::Display for StaleFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path.display();
+        match &self.reason {
+            StaleReason::Missing => write!(f, "{path} is missing"),
+            StaleReason::InvalidJson(error) => write!(f, "{path} is not valid JSON: {error}"),
+            StaleReason::Reformatted => write!(f, "{path} is formatted differently"),
+            StaleReason::Changed(differences) => {
+                write!(f, "{path} is out of date:")?;
+                for difference in differences {
+                    write!(f, "\n  {difference}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display =
+            |value: Option<&Value>| value.map_or_else(|| "(none)".to_owned(), Value::to_string);
+        let pointer = if self.pointer.is_empty() {
+            "(root)"
+        } else {
+            &self.pointer
+        };
+
+        write!(
+            f,
+            "{pointer}: {} -> {}",
+            display(self.existing.as_ref()),
+            display(self.expected.as_ref())
+        )
+    }
+}
+
+// Adds a `Difference` for each value that differs between `existing` and `expected`. Objects are
+// compared key-by-key, and arrays are compared item-by-item when they have the same length.
+fn diff_values(
+    pointer: &mut String,
+    existing: Option<&Value>,
+    expected: Option<&Value>,
+    differences: &mut Vec<Difference>,
+) {
+    let mut diff_child = |segment: &str, existing, expected| {
+        let len = pointer.len();
+        push_pointer_segment(pointer, segment);
+        diff_values(pointer, existing, expected, differences);
+        pointer.truncate(len);
+    };
+
+    match (existing, expected) {
+        (Some(a), Some(b)) if a == b => {}
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            for (key, a_value) in a {
+                diff_child(key, Some(a_value), b.get(key));
+            }
+            for (key, b_value) in b.iter().filter(|(k, _)| !a.contains_key(*k)) {
+                diff_child(key, None, Some(b_value));
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b))) if a.len() == b.len() => {
+            for (index, (a_item, b_item)) in a.iter().zip(b).enumerate() {
+                diff_child(&index.to_string(), Some(a_item), Some(b_item));
+            }
+        }
+        _ => differences.push(Difference {
+            pointer: pointer.clone(),
+            existing: existing.cloned(),
+            expected: expected.cloned(),
+        }),
+    }
+}
@@ -61,6 +61,42 @@ pub struct SchemaSettings {
     ///
     /// Defaults to `false`.
     pub untagged_enum_variant_titles: bool,
+    /// Whether to add an [OpenAPI
+    /// `discriminator`](https://spec.openapis.org/oas/v3.1.1.html#discriminator-object) to
+    /// schemas for [internally tagged enums](https://serde.rs/enum-representations.html#internally-tagged).
+    ///
+    /// When enabled, each variant of an internally tagged enum is moved into its own definition
+    /// named after the enum and the variant (e.g. `Animal_Dog`), and the enum's `oneOf` schema gets
+    /// a `discriminator` whose `mapping` associates each tag value with the `$ref` to its variant's
+    /// definition. Enums with `#[serde(untagged)]` or
+    /// `#[serde(other)]` variants are not given a `discriminator`, since their tag does not
+    /// determine which variant's schema applies.
+    ///
+    /// This setting is respected by `#[derive(JsonSchema)]` on enums, but manual implementations
+    /// of `JsonSchema` may ignore this setting.
+    ///
+    /// Defaults to `false`.
+    pub internally_tagged_enum_discriminators: bool,
+    /// The suffix appended to the name of a definition generated for the `Deserialize` contract
+    /// when it differs from the definition of the same type for the `Serialize` contract.
+    ///
+    /// This is only used by [`SchemaGenerator::subschemas_for_contracts()`].
+    ///
+    /// Defaults to `"Request"`.
+    pub deserialize_name_suffix: CowStr,
+    /// The suffix appended to the name of a definition generated for the `Serialize` contract
+    /// when it differs from the definition of the same type for the `Deserialize` contract.
+    ///
+    /// This is only used by [`SchemaGenerator::subschemas_for_contracts()`].
+    ///
+    /// Defaults to `"Response"`.
+    pub serialize_name_suffix: CowStr,
+    /// Chooses the names of non-inlined schemas within the definitions of the root schema.
+    ///
+    /// Defaults to [`NumericSuffix`], which uses each type's [schema
+    /// name](JsonSchema::schema_name), appending a number to it if the name has already been used
+    /// by another type.
+    pub definition_namer: Box<dyn DefinitionNamer>,
 }
 
 impl Default for SchemaSettings {
@@ -73,6 +109,55 @@ impl Default for SchemaSettings {
 }
 
 impl SchemaSettings {
+    /// Creates `SchemaSettings` that conform to [JSON Schema Draft 4](https://json-schema.org/specification-links#draft-4).
+    #[must_use]
+    pub fn draft04() -> SchemaSettings {
+        SchemaSettings {
+            definitions_path: "/definitions".into(),
+            meta_schema: Some(meta_schemas::DRAFT04.into()),
+            transforms: vec![
+                Box::new(ReplaceUnevaluatedProperties),
+                Box::new(ReplaceBoolSchemas {
+                    skip_additional_properties: true,
+                }),
+                Box::new(RemoveRefSiblings),
+                Box::new(ReplaceConstValue),
+                Box::new(ReplaceExclusiveBounds),
+                Box::new(ReplacePrefixItems),
+                Box::new(ReplaceDefs),
+            ],
+            inline_subschemas: false,
+            contract: Contract::Deserialize,
+            untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
+        }
+    }
+
+    /// Creates `SchemaSettings` that conform to [JSON Schema Draft 6](https://json-schema.org/specification-links#draft-6).
+    #[must_use]
+    pub fn draft06() -> SchemaSettings {
+        SchemaSettings {
+            definitions_path: "/definitions".into(),
+            meta_schema: Some(meta_schemas::DRAFT06.into()),
+            transforms: vec![
+                Box::new(ReplaceUnevaluatedProperties),
+                Box::new(RemoveRefSiblings),
+                Box::new(ReplacePrefixItems),
+                Box::new(ReplaceDefs),
+            ],
+            inline_subschemas: false,
+            contract: Contract::Deserialize,
+            untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
+        }
+    }
+
     /// Creates `SchemaSettings` that conform to [JSON Schema Draft 7](https://json-schema.org/specification-links#draft-7).
     #[must_use]
     pub fn draft07() -> SchemaSettings {
@@ -87,6 +172,10 @@ impl SchemaSettings {
             inline_subschemas: false,
             contract: Contract::Deserialize,
             untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
         }
     }
 
@@ -100,6 +189,10 @@ impl SchemaSettings {
             inline_subschemas: false,
             contract: Contract::Deserialize,
             untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
         }
     }
 
@@ -113,6 +206,10 @@ impl SchemaSettings {
             inline_subschemas: false,
             contract: Contract::Deserialize,
             untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
         }
     }
 
@@ -136,6 +233,91 @@ impl SchemaSettings {
             inline_subschemas: false,
             contract: Contract::Deserialize,
             untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
+        }
+    }
+
+    /// Creates `SchemaSettings` that conform to [OpenAPI 3.1](https://spec.openapis.org/oas/v3.1.1.html#schema-object).
+    ///
+    /// OpenAPI 3.1 schemas are a superset of JSON Schema 2020-12, so unlike
+    /// [`openapi3()`](SchemaSettings::openapi3), these settings keep `examples`, `const`, boolean
+    /// schemas and `null` in `type` arrays as they are. Properties alongside `$ref` are also
+    /// preserved, since they are allowed in OpenAPI 3.1 Schema Objects (but not in Reference
+    /// Objects, which are never produced by schemars).
+    #[must_use]
+    pub fn openapi3_1() -> SchemaSettings {
+        SchemaSettings {
+            definitions_path: "/components/schemas".into(),
+            meta_schema: Some(meta_schemas::OPENAPI3_1.into()),
+            transforms: Vec::new(),
+            inline_subschemas: false,
+            contract: Contract::Deserialize,
+            untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
+        }
+    }
+
+    /// Creates `SchemaSettings` that produce schemas accepted by the "strict" structured output
+    /// mode of LLM APIs, e.g. for tool-call parameters or response formats.
+    ///
+    /// This is based on [`draft2020_12`](Self::draft2020_12), but omits `$schema` and applies the
+    /// [`StrictStructuredOutput`] transform. Constructs that cannot be expressed in strict mode
+    /// (such as maps or tuples) are left as they are, and can be found using
+    /// [`structured_output::check`](crate::structured_output::check).
+    #[must_use]
+    pub fn strict_structured_output() -> SchemaSettings {
+        SchemaSettings {
+            definitions_path: "/$defs".into(),
+            meta_schema: None,
+            transforms: vec![Box::new(StrictStructuredOutput::default())],
+            inline_subschemas: false,
+            contract: Contract::Deserialize,
+            untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
+        }
+    }
+
+    /// Creates `SchemaSettings` that produce Kubernetes [structural
+    /// schemas](https://kubernetes.io/docs/tasks/extend-kubernetes/custom-resources/custom-resource-definitions/#specifying-a-structural-schema),
+    /// e.g. for the `openAPIV3Schema` of a `CustomResourceDefinition`.
+    ///
+    /// This is based on [`openapi3`](Self::openapi3), but omits `$schema`, inlines all `$ref`s
+    /// (cutting any cycles with `"x-kubernetes-preserve-unknown-fields": true`) and applies the
+    /// [`KubernetesStructural`] transform. Constructs that cannot be made structural are left as
+    /// they are, and can be found using [`kubernetes::check`](crate::kubernetes::check).
+    #[must_use]
+    pub fn kubernetes_structural() -> SchemaSettings {
+        SchemaSettings {
+            definitions_path: "/components/schemas".into(),
+            meta_schema: None,
+            transforms: vec![
+                Box::new(ReplaceUnevaluatedProperties),
+                Box::new(Dereference {
+                    max_recursion: 0,
+                    keep_cycle_refs: false,
+                }),
+                Box::new(SetSingleExample),
+                Box::new(ReplaceExclusiveBounds),
+                Box::new(KubernetesStructural),
+                Box::new(AddNullable::default()),
+                Box::new(ReplaceConstValue),
+            ],
+            inline_subschemas: false,
+            contract: Contract::Deserialize,
+            untagged_enum_variant_titles: false,
+            internally_tagged_enum_discriminators: false,
+            deserialize_name_suffix: "Request".into(),
+            serialize_name_suffix: "Response".into(),
+            definition_namer: Box::new(NumericSuffix),
         }
     }
 
@@ -165,6 +347,13 @@ impl SchemaSettings {
         self
     }
 
+    /// Sets the [definition namer](SchemaSettings::definition_namer) for these `SchemaSettings`.
+    #[must_use]
+    pub fn with_definition_namer(mut self, namer: impl DefinitionNamer + 'static) -> Self {
+        self.definition_namer = Box::new(namer);
+        self
+    }
+
     /// Creates a new [`SchemaGenerator`] using these settings.
     #[must_use]
     pub fn into_generator(self) -> SchemaGenerator {
@@ -211,6 +400,13 @@ impl Contract {
     pub fn is_serialize(&self) -> bool {
         self == &Contract::Serialize
     }
+
+    fn opposite(&self) -> Contract {
+        match self {
+            Contract::Deserialize => Contract::Serialize,
+            Contract::Serialize => Contract::Deserialize,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -237,9 +433,14 @@ pub struct SchemaGenerator {
     pending_schema_ids: BTreeSet<SchemaUid>,
     schema_id_to_name: BTreeMap<SchemaUid, CowStr>,
     used_schema_names: BTreeSet<CowStr>,
+    // The schema name and schema ID that each definition name was allocated for
+    definition_types: BTreeMap<CowStr, (CowStr, CowStr)>,
     // It's unlikely that `root_schema_id_stack` will ever contain more than one item, but it is
     // possible, e.g. if a `json_schema()` implementation calls `generator.root_schema_for<...>()`
     root_schema_id_stack: Vec<SchemaUid>,
+    // The base names of definitions added during `subschemas_for_contracts()`, which may need to
+    // be renamed once schemas for both contracts have been generated
+    new_definition_base_names: Option<Vec<(SchemaUid, CowStr)>>,
 }
 
 impl Default for SchemaGenerator {
@@ -256,7 +457,9 @@ impl Clone for SchemaGenerator {
             pending_schema_ids: BTreeSet::new(),
             schema_id_to_name: BTreeMap::new(),
             used_schema_names: BTreeSet::new(),
+            definition_types: self.definition_types.clone(),
             root_schema_id_stack: Vec::new(),
+            new_definition_base_names: None,
         }
     }
 }
@@ -277,7 +480,9 @@ impl SchemaGenerator {
             pending_schema_ids: BTreeSet::new(),
             schema_id_to_name: BTreeMap::new(),
             used_schema_names: BTreeSet::new(),
+            definition_types: BTreeMap::new(),
             root_schema_id_stack: Vec::new(),
+            new_definition_base_names: None,
         }
     }
 
@@ -335,20 +540,11 @@ impl SchemaGenerator {
 
             let name = this.schema_id_to_name.get(uid).cloned().unwrap_or_else(|| {
                 let base_name = schema_name();
-                let mut name = CowStr::Borrowed("");
-
-                if this.used_schema_names.contains(base_name.as_ref()) {
-                    for i in 2.. {
-                        name = format!("{base_name}{i}").into();
-                        if !this.used_schema_names.contains(&name) {
-                            break;
-                        }
-                    }
-                } else {
-                    name = base_name;
+                if let Some(new_names) = &mut this.new_definition_base_names {
+                    new_names.push((uid.clone(), base_name.clone()));
                 }
 
-                this.used_schema_names.insert(name.clone());
+                let name = this.allocate_name(base_name, &uid.0);
                 this.schema_id_to_name.insert(uid.clone(), name.clone());
                 name
             });
@@ -384,9 +580,6 @@ impl SchemaGenerator {
     }
 
     fn insert_new_subschema_for<T: ?Sized + JsonSchema>(&mut self, name: CowStr, uid: &SchemaUid) {
-        // TODO: If we've already added a schema for T with the "opposite" contract, then check
-        // whether the new schema is identical. If so, re-use the original for both contracts.
-
         let dummy = false.into();
         // insert into definitions BEFORE calling json_schema to avoid infinite recursion
         self.definitions.insert(name.clone().into(), dummy);
@@ -396,6 +589,298 @@ impl SchemaGenerator {
         self.definitions.insert(name.into(), schema.to_value());
     }
 
+    /// Generates JSON Schemas for the type `T` for both the `Deserialize` and `Serialize`
+    /// [contracts](SchemaSettings::contract), and returns them as a `(deserialize, serialize)`
+    /// pair. As with [`subschema_for()`](Self::subschema_for), each returned schema may be a
+    /// `$ref` schema referencing `T`'s schema.
+    ///
+    /// This is useful for documents that describe how types are both deserialized and serialized,
+    /// e.g. an OpenAPI document containing request and response schemas.
+    ///
+    /// When a type's schema is identical for both contracts, both contracts share a single
+    /// definition. Otherwise, each contract's definition is named with a suffix, as configured by
+    /// [`deserialize_name_suffix`](SchemaSettings::deserialize_name_suffix) and
+    /// [`serialize_name_suffix`](SchemaSettings::serialize_name_suffix). Definitions added before
+    /// this method was called are never renamed, so if a type's schema was already defined for
+    /// only one contract, then only the other contract's (differing) definition gets a suffix.
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::{json_schema, JsonSchema, SchemaGenerator};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(JsonSchema, Deserialize, Serialize)]
+    /// struct User {
+    ///     name: String,
+    ///     #[serde(skip_deserializing)]
+    ///     id: u64,
+    ///     address: Address,
+    /// }
+    ///
+    /// #[derive(JsonSchema, Deserialize, Serialize)]
+    /// struct Address {
+    ///     street: String,
+    /// }
+    ///
+    /// let mut generator = SchemaGenerator::default();
+    /// let (request, response) = generator.subschemas_for_contracts::<User>();
+    ///
+    /// assert_eq!(request, json_schema!({ "$ref": "#/$defs/UserRequest" }));
+    /// assert_eq!(response, json_schema!({ "$ref": "#/$defs/UserResponse" }));
+    ///
+    /// let mut names = generator.definitions().keys().collect::<Vec<_>>();
+    /// names.sort();
+    /// assert_eq!(names, ["Address", "UserRequest", "UserResponse"]);
+    /// ```
+    pub fn subschemas_for_contracts<T: ?Sized + JsonSchema>(&mut self) -> (Schema, Schema) {
+        let original_contract = self.settings.contract.clone();
+        let outer_new_names = self.new_definition_base_names.replace(Vec::new());
+
+        self.settings.contract = Contract::Deserialize;
+        let mut deserialize_schema = self.subschema_for::<T>();
+        self.settings.contract = Contract::Serialize;
+        let mut serialize_schema = self.subschema_for::<T>();
+        self.settings.contract = original_contract;
+
+        let new_names = core::mem::replace(&mut self.new_definition_base_names, outer_new_names);
+        let renames = self.share_contract_definitions(new_names.unwrap_or_default());
+
+        rename_refs(deserialize_schema.as_value_mut(), &renames);
+        rename_refs(serialize_schema.as_value_mut(), &renames);
+
+        (deserialize_schema, serialize_schema)
+    }
+
+    // Merges identical definitions that were added for different contracts, and gives the
+    // remaining new definitions their final names. Returns a map of the `$ref`s that have changed.
+    fn share_contract_definitions(
+        &mut self,
+        new_names: Vec<(SchemaUid, CowStr)>,
+    ) -> BTreeMap<String, String> {
+        let mut new_definitions = new_names
+            .into_iter()
+            .filter_map(|(uid, base_name)| {
+                let provisional_name = self.schema_id_to_name.get(&uid)?.clone();
+                Some(NewDefinition {
+                    uid,
+                    base_name,
+                    provisional_name,
+                    group: None,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut groups = self.group_contract_definitions(&mut new_definitions);
+        self.split_differing_groups(&mut groups);
+
+        for definition in &new_definitions {
+            self.used_schema_names.remove(&definition.provisional_name);
+            self.definition_types.remove(&definition.provisional_name);
+        }
+
+        let mut renames = BTreeMap::new();
+        let mut renamed_definitions = Vec::new();
+        for definition in new_definitions {
+            let schema = self
+                .definitions
+                .remove(definition.provisional_name.as_ref());
+            let group = definition.group.and_then(|i| groups.get_mut(i));
+
+            let final_name = match group {
+                Some(group) if group.shared => {
+                    if let Some(final_name) = &group.final_name {
+                        final_name.clone()
+                    } else {
+                        let name = self.allocate_name(definition.base_name, &definition.uid.0);
+                        group.final_name = Some(name.clone());
+                        renamed_definitions.extend(schema.map(|s| (name.clone(), s)));
+                        name
+                    }
+                }
+                group => {
+                    let base_name = if group.is_some() {
+                        let suffix = match definition.uid.1 {
+                            Contract::Deserialize => &self.settings.deserialize_name_suffix,
+                            Contract::Serialize => &self.settings.serialize_name_suffix,
+                        };
+                        format!("{}{suffix}", definition.base_name).into()
+                    } else {
+                        definition.base_name
+                    };
+                    let name = self.allocate_name(base_name, &definition.uid.0);
+                    renamed_definitions.extend(schema.map(|s| (name.clone(), s)));
+                    name
+                }
+            };
+
+            renames.insert(
+                self.ref_for_name(&definition.provisional_name),
+                self.ref_for_name(&final_name),
+            );
+            self.schema_id_to_name.insert(definition.uid, final_name);
+        }
+
+        renames.retain(|from, to| from != to);
+        for (name, mut schema) in renamed_definitions {
+            rename_refs(&mut schema, &renames);
+            self.definitions.insert(name.into(), schema);
+        }
+
+        renames
+    }
+
+    // Pairs up each new definition with the definition of the same type for the opposite
+    // contract, assuming for now that each pair can be shared.
+    fn group_contract_definitions(&self, new_definitions: &mut [NewDefinition]) -> Vec<Group> {
+        let mut groups = Vec::<Group>::new();
+        for i in 0..new_definitions.len() {
+            if new_definitions[i].group.is_some() {
+                continue;
+            }
+            let SchemaUid(id, contract) = &new_definitions[i].uid;
+            let opposite_uid = SchemaUid(id.clone(), contract.opposite());
+            let Some(opposite_name) = self.schema_id_to_name.get(&opposite_uid) else {
+                continue;
+            };
+
+            let group = groups.len();
+            let mut final_name = None;
+            new_definitions[i].group = Some(group);
+            if let Some(opposite) = new_definitions.iter_mut().find(|d| d.uid == opposite_uid) {
+                opposite.group = Some(group);
+            } else {
+                // The opposite definition was added previously, so it keeps its name
+                final_name = Some(opposite_name.clone());
+            }
+            groups.push(Group {
+                names: [
+                    new_definitions[i].provisional_name.clone(),
+                    opposite_name.clone(),
+                ],
+                shared: true,
+                final_name,
+            });
+        }
+        groups
+    }
+
+    // Optimistically assumes that all groups can be shared, and then splits any groups whose
+    // definitions differ (even if only because they reference definitions that are split),
+    // until no more groups need splitting.
+    fn split_differing_groups(&self, groups: &mut [Group]) {
+        loop {
+            let shared_refs = groups
+                .iter()
+                .enumerate()
+                .filter(|(_, g)| g.shared)
+                .flat_map(|(i, g)| g.names.iter().map(move |n| (n, i)))
+                .map(|(name, i)| (self.ref_for_name(name), format!("\0{i}")))
+                .collect::<BTreeMap<_, _>>();
+
+            let mut changed = false;
+            for group in groups.iter_mut().filter(|g| g.shared) {
+                let [a, b] = group.names.clone().map(|name| {
+                    let mut value = self.definitions.get(name.as_ref()).cloned();
+                    if let Some(value) = &mut value {
+                        rename_refs(value, &shared_refs);
+                    }
+                    value
+                });
+                if a.is_none() || a != b {
+                    group.shared = false;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    // Chooses a name for a new definition using the configured `DefinitionNamer`, ensuring that
+    // the name is unique, and marks the returned name as used.
+    fn allocate_name(&mut self, schema_name: CowStr, schema_id: &str) -> CowStr {
+        let mut name = self.settings.definition_namer.definition_name(
+            schema_name.clone(),
+            schema_id,
+            &self.used_schema_names,
+        );
+        if self.used_schema_names.contains(&name) {
+            name = with_numeric_suffix(name, &self.used_schema_names);
+        }
+
+        self.used_schema_names.insert(name.clone());
+        self.definition_types
+            .insert(name.clone(), (schema_name, schema_id.to_owned().into()));
+        name
+    }
+
+    // Lets the configured `DefinitionNamer` change the names of definitions now that all of them
+    // are known, ensuring that names remain unique. Returns a map of the `$ref`s that have changed.
+    fn finalize_definition_names(
+        &self,
+        definitions: &mut JsonMap<String, Value>,
+    ) -> BTreeMap<String, String> {
+        let mut names = definitions
+            .keys()
+            .filter_map(|name| {
+                let (schema_name, schema_id) = self.definition_types.get(name.as_str())?;
+                Some(DefinitionName {
+                    name: name.clone().into(),
+                    schema_name: schema_name.clone(),
+                    schema_id: schema_id.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let original_names = names.iter().map(|d| d.name.clone()).collect::<Vec<_>>();
+
+        self.settings.definition_namer.finalize_names(&mut names);
+
+        let mut used_names = definitions
+            .keys()
+            .filter(|name| !self.definition_types.contains_key(name.as_str()))
+            .map(|name| CowStr::from(name.clone()))
+            .collect::<BTreeSet<_>>();
+        let mut new_names = BTreeMap::new();
+        for (definition, original_name) in names.into_iter().zip(original_names) {
+            let name = with_numeric_suffix(definition.name, &used_names);
+            used_names.insert(name.clone());
+            if name != original_name {
+                new_names.insert(original_name, name);
+            }
+        }
+
+        if new_names.is_empty() {
+            return BTreeMap::new();
+        }
+
+        let renames = new_names
+            .iter()
+            .map(|(from, to)| (self.ref_for_name(from), self.ref_for_name(to)))
+            .collect();
+        *definitions = core::mem::take(definitions)
+            .into_iter()
+            .map(|(name, mut schema)| {
+                rename_refs(&mut schema, &renames);
+                let name = new_names
+                    .get(name.as_str())
+                    .map_or(name, |n| n.clone().into_owned());
+                (name, schema)
+            })
+            .collect();
+
+        renames
+    }
+
+    fn ref_for_name(&self, name: &str) -> String {
+        format!(
+            "#{}/{}",
+            self.definitions_path_stripped(),
+            crate::encoding::encode_ref_name(name)
+        )
+    }
+
     /// Borrows the collection of all [non-inlined](JsonSchema::inline_schema) schemas that
     /// have been generated.
     ///
@@ -406,6 +891,55 @@ impl SchemaGenerator {
         &self.definitions
     }
 
+    /// If `schema` has a `$ref` that points to one of this generator's
+    /// [`definitions`](Self::definitions), returns that definition. `$ref`s are followed
+    /// repeatedly until reaching a schema without a `$ref`, so this can be used to inspect the
+    /// schema returned by [`subschema_for`](Self::subschema_for). If `schema` does not have a
+    /// `$ref`, then it is returned unchanged.
+    ///
+    /// Any keywords alongside a `$ref` are ignored. This returns `None` if any `$ref` in the chain
+    /// does not point to a definition (e.g. a `$ref` to the root schema), or if the chain of
+    /// `$ref`s forms a cycle.
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::{json_schema, JsonSchema, SchemaGenerator};
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct MyStruct {
+    ///     my_int: i32,
+    /// }
+    ///
+    /// let mut generator = SchemaGenerator::default();
+    /// let schema = generator.subschema_for::<MyStruct>();
+    ///
+    /// assert_eq!(schema, json_schema!({ "$ref": "#/$defs/MyStruct" }));
+    /// assert_eq!(
+    ///     generator.resolve(&schema).and_then(|s| s.get("type")),
+    ///     Some(&"object".into())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn resolve<'a>(&'a self, schema: &'a Schema) -> Option<&'a Schema> {
+        crate::schema::follow_refs(schema, |reference| {
+            self.definition_for_ref(reference)?.try_into().ok()
+        })
+    }
+
+    // Returns the definition that the given `$ref` value points to, without following any
+    // further `$ref`s within that definition.
+    pub(crate) fn definition_for_ref(&self, reference: &str) -> Option<&Value> {
+        let fragment = crate::encoding::percent_decode(reference.strip_prefix('#')?)?;
+        let name = fragment
+            .strip_prefix(self.definitions_path_stripped())?
+            .strip_prefix('/')?;
+        if name.contains('/') {
+            return None;
+        }
+        let name = name.replace("~1", "/").replace("~0", "~");
+        self.definitions.get(&name)
+    }
+
     /// Mutably borrows the collection of all [non-inlined](JsonSchema::inline_schema)
     /// schemas that have been generated.
     ///
@@ -586,7 +1120,7 @@ impl SchemaGenerator {
         schema
     }
 
-    fn add_definitions(
+    pub(crate) fn add_definitions(
         &mut self,
         schema_object: &mut JsonMap<String, Value>,
         mut definitions: JsonMap<String, Value>,
@@ -595,6 +1129,11 @@ impl SchemaGenerator {
             return;
         }
 
+        let renames = self.finalize_definition_names(&mut definitions);
+        if !renames.is_empty() {
+            rename_refs_in_object(schema_object, &renames);
+        }
+
         let pointer = self.definitions_path_stripped();
         let Some(target) = json_pointer_mut(schema_object, pointer, true) else {
             return;
@@ -603,7 +1142,7 @@ impl SchemaGenerator {
         target.append(&mut definitions);
     }
 
-    fn apply_transforms(&mut self, schema: &mut Schema) {
+    pub(crate) fn apply_transforms(&mut self, schema: &mut Schema) {
         for transform in self.transforms_mut() {
             transform.transform(schema);
         }
@@ -622,6 +1161,74 @@ impl SchemaGenerator {
     }
 }
 
+// A definition added by `subschemas_for_contracts`, before it has been given its final name
+struct NewDefinition {
+    uid: SchemaUid,
+    base_name: CowStr,
+    provisional_name: CowStr,
+    group: Option<usize>,
+}
+
+// A definition for each contract that may be shared, if they turn out to be identical
+struct Group {
+    names: [CowStr; 2],
+    shared: bool,
+    final_name: Option<CowStr>,
+}
+
+// Returns `base_name` if it has not already been used, otherwise appends a number to make it
+// unique.
+fn with_numeric_suffix(base_name: CowStr, used_names: &BTreeSet<CowStr>) -> CowStr {
+    if !used_names.contains(&base_name) {
+        return base_name;
+    }
+
+    let mut i = 2;
+    loop {
+        let name = CowStr::from(format!("{base_name}{i}"));
+        if !used_names.contains(&name) {
+            return name;
+        }
+        i += 1;
+    }
+}
+
+// Replaces any `$ref` values within the given schema that are keys of `renames`.
+fn rename_refs(value: &mut Value, renames: &BTreeMap<String, String>) {
+    match value {
+        Value::Object(obj) => rename_refs_in_object(obj, renames),
+        Value::Array(array) => array.iter_mut().for_each(|v| rename_refs(v, renames)),
+        _ => {}
+    }
+}
+
+fn rename_refs_in_object(obj: &mut JsonMap<String, Value>, renames: &BTreeMap<String, String>) {
+    for (key, value) in obj.iter_mut() {
+        match value {
+            Value::String(reference) if key == "$ref" => {
+                if let Some(renamed) = renames.get(reference.as_str()) {
+                    renamed.clone_into(reference);
+                }
+            }
+            // OpenAPI discriminator mappings contain references outside of `$ref`
+            Value::Object(discriminator) if key == "discriminator" => {
+                if let Some(Value::Object(mapping)) = discriminator.get_mut("mapping") {
+                    for reference in mapping.values_mut() {
+                        if let Some(renamed) = reference.as_str().and_then(|r| renames.get(r)) {
+                            *reference = renamed.clone().into();
+                        }
+                    }
+                }
+                // This may also be a property named "discriminator"
+                discriminator
+                    .values_mut()
+                    .for_each(|v| rename_refs(v, renames));
+            }
+            _ => rename_refs(value, renames),
+        }
+    }
+}
+
 fn json_pointer_mut<'a>(
     mut object: &'a mut JsonMap<String, Value>,
     pointer: &str,
@@ -786,6 +1393,269 @@ impl Debug for Box<dyn GenTransform> {
     }
 }
 
+/// Chooses the names of non-inlined schemas within the definitions of a root schema generated by a
+/// [`SchemaGenerator`].
+///
+/// The chosen name is used as the key of the schema within the definitions (e.g. `$defs`), and
+/// so also appears in any `$ref`s to that schema. If the returned name has already been used by a
+/// different type, the `SchemaGenerator` will append a number to it to make it unique.
+///
+/// Schemars includes these implementations:
+/// - [`NumericSuffix`] (the default) - uses the type's [schema name](JsonSchema::schema_name),
+///   appending a number if it has already been used
+/// - [`FullModulePath`] - prefixes the schema name with the full module path of the type
+/// - [`ShortestUniqueModulePath`] - prefixes the schema name with as few trailing segments of the
+///   type's module path as are needed to make it unique
+///
+/// A provisional name is chosen by [`definition_name()`](DefinitionNamer::definition_name) as
+/// soon as the `SchemaGenerator` first encounters a type, without knowing which other types it
+/// will encounter later. Once all definitions are known, the names can then be changed by
+/// [`finalize_names()`](DefinitionNamer::finalize_names).
+///
+/// # Example
+/// ```
+/// use schemars::generate::{DefinitionNamer, SchemaSettings};
+/// use schemars::{json_schema, JsonSchema};
+/// use std::borrow::Cow;
+/// use std::collections::BTreeSet;
+///
+/// #[derive(Debug, Clone)]
+/// struct Prefixed;
+///
+/// impl DefinitionNamer for Prefixed {
+///     fn definition_name(
+///         &self,
+///         schema_name: Cow<'static, str>,
+///         _schema_id: &str,
+///         _used_names: &BTreeSet<Cow<'static, str>>,
+///     ) -> Cow<'static, str> {
+///         format!("My{schema_name}").into()
+///     }
+/// }
+///
+/// #[derive(JsonSchema)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// let mut generator = SchemaSettings::default()
+///     .with_definition_namer(Prefixed)
+///     .into_generator();
+///
+/// let schema = generator.subschema_for::<Point>();
+/// assert_eq!(schema, json_schema!({ "$ref": "#/$defs/MyPoint" }));
+/// ```
+pub trait DefinitionNamer: DynClone + Debug + Send {
+    /// Returns the name to use for the definition of a type.
+    ///
+    /// `schema_name` and `schema_id` are the values returned by the type's
+    /// [`JsonSchema::schema_name()`] and [`JsonSchema::schema_id()`] respectively. `used_names`
+    /// contains the names that have already been given to other definitions.
+    fn definition_name(
+        &self,
+        schema_name: CowStr,
+        schema_id: &str,
+        used_names: &BTreeSet<CowStr>,
+    ) -> CowStr;
+
+    /// Changes the names of definitions once all of them are known.
+    ///
+    /// This is called when a root schema is generated, with every definition that was named by
+    /// [`definition_name()`](DefinitionNamer::definition_name). Any changed names are used as the
+    /// keys of the definitions within the root schema, and all `$ref`s to them are updated.
+    /// References returned by [`SchemaGenerator::subschema_for()`] and the keys returned by
+    /// [`SchemaGenerator::definitions()`] always use the provisional names.
+    ///
+    /// Names should be unique - if a name has already been used, a number is appended to it.
+    ///
+    /// The default implementation does not change any names.
+    fn finalize_names(&self, definitions: &mut [DefinitionName]) {
+        let _ = definitions;
+    }
+}
+
+/// The name of a definition, as passed to [`DefinitionNamer::finalize_names()`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DefinitionName {
+    /// The name of the definition, which may be changed.
+    pub name: CowStr,
+    /// The [schema name](JsonSchema::schema_name) of the type that the definition is for,
+    /// including any suffix added for its contract.
+    pub schema_name: CowStr,
+    /// The [schema ID](JsonSchema::schema_id) of the type that the definition is for.
+    pub schema_id: CowStr,
+}
+
+dyn_clone::clone_trait_object!(DefinitionNamer);
+
+/// A [`DefinitionNamer`] that uses each type's [schema name](JsonSchema::schema_name), appending
+/// a number to it if the name has already been used.
+///
+/// For example, if two different types both have the schema name `Foo`, then their definitions
+/// will be named `Foo` and `Foo2`. Which type gets which name depends on the order in which the
+/// types are first encountered by the [`SchemaGenerator`].
+///
+/// This is the default definition namer.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct NumericSuffix;
+
+impl DefinitionNamer for NumericSuffix {
+    fn definition_name(
+        &self,
+        schema_name: CowStr,
+        _schema_id: &str,
+        used_names: &BTreeSet<CowStr>,
+    ) -> CowStr {
+        with_numeric_suffix(schema_name, used_names)
+    }
+}
+
+/// A [`DefinitionNamer`] that prefixes each type's [schema name](JsonSchema::schema_name) with
+/// the full path of the module that the type is defined in.
+///
+/// For example, the definition of a type `Foo` defined in the module `my_crate::models` will be
+/// named `my_crate.models.Foo`. Definition names therefore do not depend on the order in which
+/// types are encountered.
+///
+/// The module path is taken from the type's [schema ID](JsonSchema::schema_id), so it is only
+/// available for types that derive `JsonSchema` (or otherwise include their module path in their
+/// schema ID). Other types just use their schema name.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FullModulePath {
+    /// The string used to join the segments of the module path and the schema name.
+    ///
+    /// Defaults to `"."`.
+    pub separator: CowStr,
+}
+
+impl Default for FullModulePath {
+    fn default() -> Self {
+        Self {
+            separator: ".".into(),
+        }
+    }
+}
+
+impl DefinitionNamer for FullModulePath {
+    fn definition_name(
+        &self,
+        schema_name: CowStr,
+        schema_id: &str,
+        _used_names: &BTreeSet<CowStr>,
+    ) -> CowStr {
+        let module_path = module_path_segments(schema_id);
+        join_module_path(&module_path, schema_name, &self.separator)
+    }
+}
+
+/// A [`DefinitionNamer`] that uses each type's [schema name](JsonSchema::schema_name) if it is
+/// unique, otherwise prefixes it with as few trailing segments of the type's module path as are
+/// required to make it unique.
+///
+/// For example, if the types `my_crate::a::Foo` and `my_crate::b::Foo` are both used, then their
+/// definitions will be named `a.Foo` and `b.Foo`. The names are chosen once all definitions are
+/// known, so they do not depend on the order in which types are encountered.
+///
+/// As with [`FullModulePath`], the module path is only available for types that derive
+/// `JsonSchema`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ShortestUniqueModulePath {
+    /// The string used to join the segments of the module path and the schema name.
+    ///
+    /// Defaults to `"."`.
+    pub separator: CowStr,
+}
+
+impl Default for ShortestUniqueModulePath {
+    fn default() -> Self {
+        Self {
+            separator: ".".into(),
+        }
+    }
+}
+
+impl DefinitionNamer for ShortestUniqueModulePath {
+    fn definition_name(
+        &self,
+        schema_name: CowStr,
+        schema_id: &str,
+        _used_names: &BTreeSet<CowStr>,
+    ) -> CowStr {
+        let module_path = module_path_segments(schema_id);
+        join_module_path(&module_path, schema_name, &self.separator)
+    }
+
+    fn finalize_names(&self, definitions: &mut [DefinitionName]) {
+        let paths = definitions
+            .iter()
+            .map(|d| module_path_segments(&d.schema_id))
+            .collect::<Vec<_>>();
+
+        let mut new_names = Vec::with_capacity(definitions.len());
+        for (i, definition) in definitions.iter().enumerate() {
+            let path = &paths[i];
+            let others = (0..definitions.len())
+                .filter(|&j| j != i && definitions[j].schema_name == definition.schema_name)
+                .collect::<Vec<_>>();
+            let len = (0..=path.len())
+                .find(|&len| {
+                    others.iter().all(|&j| {
+                        module_path_suffix(&paths[j], len) != module_path_suffix(path, len)
+                    })
+                })
+                .unwrap_or(path.len());
+            new_names.push(join_module_path(
+                module_path_suffix(path, len),
+                definition.schema_name.clone(),
+                &self.separator,
+            ));
+        }
+
+        for (definition, name) in definitions.iter_mut().zip(new_names) {
+            definition.name = name;
+        }
+    }
+}
+
+// Returns the segments of the module path within a derived type's schema ID, e.g. `["my_crate",
+// "models"]` for `my_crate::models::Foo<i32>`. Returns an empty vec if the schema ID does not
+// look like a module path.
+fn module_path_segments(schema_id: &str) -> Vec<&str> {
+    let type_path = schema_id
+        .split_once('<')
+        .map_or(schema_id, |(path, _)| path);
+    let mut segments = type_path.split("::").collect::<Vec<_>>();
+    segments.pop();
+
+    let is_ident = |s: &&str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if segments.iter().all(is_ident) {
+        segments
+    } else {
+        Vec::new()
+    }
+}
+
+// Returns up to `len` trailing segments of a module path.
+fn module_path_suffix<'a>(module_path: &'a [&'a str], len: usize) -> &'a [&'a str] {
+    &module_path[module_path.len() - len.min(module_path.len())..]
+}
+
+fn join_module_path(module_path: &[&str], schema_name: CowStr, separator: &str) -> CowStr {
+    if module_path.is_empty() {
+        return schema_name;
+    }
+
+    let mut name = module_path.join(separator);
+    name.push_str(separator);
+    name.push_str(&schema_name);
+    name.into()
+}
+
 fn _assert_send() {
     fn assert<T: Send>() {}
 
@@ -81,6 +81,9 @@ mod jiff02;
 #[cfg(feature = "semver1")]
 mod semver1;
 
+#[cfg(feature = "serde_with3")]
+mod serde_with3;
+
 #[cfg(feature = "smallvec1")]
 forward_impl!((<A: smallvec1::Array> crate::JsonSchema for smallvec1::SmallVec<A> where A::Item: crate::JsonSchema) => alloc::vec::Vec<A::Item>);
 
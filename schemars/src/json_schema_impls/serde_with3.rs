@@ -0,0 +1,195 @@
+use crate::_alloc_prelude::*;
+use crate::SchemaGenerator;
+use crate::{json_schema, JsonSchema, Schema};
+use alloc::borrow::Cow;
+use serde_with3::base64::{Alphabet, Base64};
+use serde_with3::formats::{Flexible, Format, PreferMany, PreferOne, Strict};
+use serde_with3::{
+    As, BoolFromInt, DisplayFromStr, NoneAsEmptyString, OneOrMany, StringWithSeparator,
+    VecSkipError,
+};
+
+// `#[serde_as(as = "T")]` is rewritten to `#[serde(with = "::serde_with::As::<T>")]`, so the schema
+// of `As<T>` is the schema of the `serde_as` type `T`.
+forward_impl!((<T: ?Sized> JsonSchema for As<T> where T: JsonSchema) => T);
+
+forward_impl!(DisplayFromStr => String);
+forward_impl!((<Sep, T> JsonSchema for StringWithSeparator<Sep, T>) => String);
+// `None` is serialized as an empty string, and `Some` is serialized using its `Display` impl
+forward_impl!(NoneAsEmptyString => String);
+
+impl<A: Alphabet, F: Format> JsonSchema for Base64<A, F> {
+    inline_schema!();
+
+    fn schema_name() -> Cow<'static, str> {
+        "Base64".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "serde_with::base64::Base64".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "contentEncoding": "base64",
+        })
+    }
+}
+
+impl JsonSchema for BoolFromInt<Strict> {
+    inline_schema!();
+
+    fn schema_name() -> Cow<'static, str> {
+        "BoolFromInt".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "serde_with::BoolFromInt".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "integer",
+            "minimum": 0,
+            "maximum": 1,
+        })
+    }
+}
+
+impl JsonSchema for BoolFromInt<Flexible> {
+    inline_schema!();
+
+    fn schema_name() -> Cow<'static, str> {
+        "BoolFromInt".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "serde_with::BoolFromInt<Flexible>".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        // Any integer can be deserialized, but only 0 or 1 is serialized
+        if generator.contract().is_deserialize() {
+            json_schema!({
+                "type": "integer",
+            })
+        } else {
+            BoolFromInt::<Strict>::json_schema(generator)
+        }
+    }
+}
+
+macro_rules! one_or_many_impl {
+    ($format:ty, $always_many:literal) => {
+        impl<T: JsonSchema> JsonSchema for OneOrMany<T, $format> {
+            inline_schema!();
+
+            fn schema_name() -> Cow<'static, str> {
+                format!("OneOrMany_{}", T::schema_name()).into()
+            }
+
+            fn schema_id() -> Cow<'static, str> {
+                format!(
+                    "serde_with::OneOrMany<{}, {}>",
+                    T::schema_id(),
+                    stringify!($format)
+                )
+                .into()
+            }
+
+            fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+                let single = generator.subschema_for::<T>();
+                let many = json_schema!({
+                    "type": "array",
+                    "items": single,
+                });
+
+                if $always_many && generator.contract().is_serialize() {
+                    many
+                } else {
+                    json_schema!({
+                        "anyOf": [single, many],
+                    })
+                }
+            }
+        }
+    };
+}
+
+one_or_many_impl!(PreferOne, false);
+one_or_many_impl!(PreferMany, true);
+
+impl<T: JsonSchema> JsonSchema for VecSkipError<T> {
+    inline_schema!();
+
+    fn schema_name() -> Cow<'static, str> {
+        format!("Array_of_{}", T::schema_name()).into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        format!("serde_with::VecSkipError<{}>", T::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        // Elements that fail to deserialize are skipped, so any array can be deserialized
+        if generator.contract().is_deserialize() {
+            json_schema!({
+                "type": "array",
+            })
+        } else {
+            Vec::<T>::json_schema(generator)
+        }
+    }
+}
+
+macro_rules! timespan_impl {
+    ($($ty:ident)+) => {
+        $(
+            forward_impl!((<F: Format + JsonSchema> JsonSchema for serde_with3::$ty<F, Strict>) => F);
+
+            impl<F: Format + JsonSchema> JsonSchema for serde_with3::$ty<F, Flexible> {
+                inline_schema!();
+
+                fn schema_name() -> Cow<'static, str> {
+                    format!("Flexible_{}", F::schema_name()).into()
+                }
+
+                fn schema_id() -> Cow<'static, str> {
+                    format!("serde_with::FlexibleTimespan<{}>", F::schema_id()).into()
+                }
+
+                fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+                    // Numbers and numeric strings can be deserialized regardless of the format
+                    if generator.contract().is_deserialize() {
+                        json_schema!({
+                            "type": ["number", "string"],
+                            "pattern": r"^-?[0-9]+(\.[0-9]+)?([eE][+-]?[0-9]+)?$",
+                        })
+                    } else {
+                        F::json_schema(generator)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+timespan_impl!(
+    DurationSeconds
+    DurationSecondsWithFrac
+    DurationMilliSeconds
+    DurationMilliSecondsWithFrac
+    DurationMicroSeconds
+    DurationMicroSecondsWithFrac
+    DurationNanoSeconds
+    DurationNanoSecondsWithFrac
+    TimestampSeconds
+    TimestampSecondsWithFrac
+    TimestampMilliSeconds
+    TimestampMilliSecondsWithFrac
+    TimestampMicroSeconds
+    TimestampMicroSecondsWithFrac
+    TimestampNanoSeconds
+    TimestampNanoSecondsWithFrac
+);
@@ -0,0 +1,315 @@
+/*!
+Checking whether a [`Schema`] is a Kubernetes structural schema.
+
+The `openAPIV3Schema` of a Kubernetes `CustomResourceDefinition` must be a [structural
+schema](https://kubernetes.io/docs/tasks/extend-kubernetes/custom-resources/custom-resource-definitions/#specifying-a-structural-schema).
+In particular:
+- the root schema, every property, every `additionalProperties` schema and every `items` schema
+  must specify a single `type`, unless it sets `x-kubernetes-int-or-string` or
+  `x-kubernetes-preserve-unknown-fields`
+- `$ref`, `const` and `null` in `type` are not supported
+- subschemas of `allOf`, `anyOf`, `oneOf` and `not` must not set `description`, `type`,
+  `default`, `additionalProperties` or `nullable`, and any properties or items that they specify
+  must also be specified outside of them
+
+The [`KubernetesStructural`](crate::transform::KubernetesStructural) transform (which is used by
+[`SchemaSettings::kubernetes_structural()`](crate::generate::SchemaSettings::kubernetes_structural))
+rewrites schemas to fit these restrictions where possible. [`check`] reports any constructs that
+remain in a schema that are not structural, such as tuples or enums that mix unit and non-unit
+variants.
+
+# Example
+```
+use schemars::generate::SchemaSettings;
+use schemars::kubernetes::{self, Issue};
+use schemars::JsonSchema;
+
+#[derive(JsonSchema)]
+struct BackupSpec {
+    schedule: String,
+    retention: Option<u32>,
+    window: (u8, u8),
+}
+
+let generator = SchemaSettings::kubernetes_structural().into_generator();
+let schema = generator.into_root_schema_for::<BackupSpec>();
+
+let findings = kubernetes::check(&schema);
+
+assert!(!findings.is_empty());
+assert_eq!(findings[0].path, "/properties/window/prefixItems");
+assert_eq!(
+    findings[0].issue,
+    Issue::UnsupportedKeyword {
+        keyword: "prefixItems".to_owned()
+    }
+);
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::encoding::push_pointer_segment;
+use crate::transform::{
+    transform_subschemas_with_path, PathTransform, SchemaLocation, INT_OR_STRING,
+    KUBERNETES_KEYWORDS, PRESERVE_UNKNOWN_FIELDS,
+};
+use crate::Schema;
+use core::fmt;
+use serde_json::{Map, Value};
+
+const JUNCTORS: [&str; 4] = ["allOf", "anyOf", "oneOf", "not"];
+
+// Keywords that must not be set within `allOf`, `anyOf`, `oneOf` or `not`
+const JUNCTOR_FORBIDDEN_KEYWORDS: [&str; 5] = [
+    "description",
+    "type",
+    "default",
+    "additionalProperties",
+    "nullable",
+];
+
+/// Checks whether the given schema is a Kubernetes structural schema, returning any constructs
+/// that are not allowed.
+///
+/// See the [module documentation](crate::kubernetes) for more information.
+#[must_use]
+pub fn check(schema: &Schema) -> Vec<Finding> {
+    let mut checker = Checker {
+        findings: Vec::new(),
+        in_junctor: false,
+        int_or_string_parent: false,
+    };
+
+    checker.transform_at(&mut schema.clone(), SchemaLocation::ROOT);
+
+    checker.findings
+}
+
+struct Checker {
+    findings: Vec<Finding>,
+    // Whether the current schema is within `allOf`, `anyOf`, `oneOf` or `not`
+    in_junctor: bool,
+    // Whether the parent of the current schema sets `x-kubernetes-int-or-string`, which allows
+    // `type` within its immediate `allOf`/`anyOf`/`oneOf`/`not` subschemas
+    int_or_string_parent: bool,
+}
+
+impl Checker {
+    fn add_finding(&mut self, pointer: &str, keyword: Option<&str>, issue: Issue) {
+        let mut path = pointer.to_owned();
+        if let Some(keyword) = keyword {
+            push_pointer_segment(&mut path, keyword);
+        }
+        self.findings.push(Finding { path, issue });
+    }
+
+    fn check_junctor_subschemas(&mut self, obj: &Map<String, Value>, pointer: &str) {
+        let properties = obj.get("properties").and_then(Value::as_object);
+
+        for keyword in JUNCTORS {
+            let subschemas = match obj.get(keyword) {
+                Some(Value::Array(subschemas)) => subschemas.iter().enumerate().collect(),
+                Some(subschema) => vec![(0, subschema)],
+                None => continue,
+            };
+
+            for (index, subschema) in subschemas {
+                let mut path = pointer.to_owned();
+                push_pointer_segment(&mut path, keyword);
+                if keyword != "not" {
+                    push_pointer_segment(&mut path, &index.to_string());
+                }
+
+                if let Some(Value::Object(sub_properties)) = subschema.get("properties") {
+                    for property in sub_properties.keys() {
+                        if !properties.is_some_and(|p| p.contains_key(property)) {
+                            self.add_finding(
+                                &path,
+                                None,
+                                Issue::PropertyOnlyInJunctor {
+                                    property: property.clone(),
+                                },
+                            );
+                        }
+                    }
+                }
+
+                if subschema.get("items").is_some() && !obj.contains_key("items") {
+                    self.add_finding(&path, None, Issue::ItemsOnlyInJunctor);
+                }
+            }
+        }
+    }
+}
+
+impl PathTransform for Checker {
+    fn transform_at(&mut self, schema: &mut Schema, location: SchemaLocation) {
+        let pointer = location.pointer;
+        let entering_junctor = location.keyword.is_some_and(|k| JUNCTORS.contains(&k));
+        let in_junctor = self.in_junctor || entering_junctor;
+
+        let Some(obj) = schema.as_object() else {
+            self.add_finding(
+                pointer,
+                None,
+                Issue::BooleanSchema {
+                    value: schema.as_bool() == Some(true),
+                },
+            );
+            return;
+        };
+
+        for keyword in obj.keys() {
+            if !KUBERNETES_KEYWORDS.contains(&keyword.as_str())
+                && !keyword.starts_with("x-kubernetes-")
+            {
+                self.add_finding(
+                    pointer,
+                    Some(keyword),
+                    Issue::UnsupportedKeyword {
+                        keyword: keyword.clone(),
+                    },
+                );
+            }
+        }
+
+        let is_set = |keyword: &str| obj.get(keyword) == Some(&Value::Bool(true));
+        let int_or_string = is_set(INT_OR_STRING);
+
+        if in_junctor {
+            for keyword in JUNCTOR_FORBIDDEN_KEYWORDS {
+                let allowed = keyword == "type" && entering_junctor && self.int_or_string_parent;
+                if obj.contains_key(keyword) && !allowed {
+                    self.add_finding(
+                        pointer,
+                        Some(keyword),
+                        Issue::KeywordInJunctor {
+                            keyword: keyword.to_owned(),
+                        },
+                    );
+                }
+            }
+        } else {
+            let requires_type = location.is_root()
+                || matches!(
+                    location.keyword,
+                    Some("properties" | "additionalProperties" | "items")
+                );
+
+            match obj.get("type") {
+                None => {
+                    if requires_type && !int_or_string && !is_set(PRESERVE_UNKNOWN_FIELDS) {
+                        self.add_finding(pointer, None, Issue::MissingType);
+                    }
+                }
+                Some(Value::String(ty)) => {
+                    if location.is_root() && ty != "object" {
+                        self.add_finding(pointer, None, Issue::RootNotObject);
+                    }
+                    if ty == "array" && !obj.contains_key("items") {
+                        self.add_finding(pointer, None, Issue::MissingItems);
+                    }
+                }
+                Some(_) => self.add_finding(pointer, None, Issue::TypeNotString),
+            }
+
+            if obj.contains_key("properties") && obj.contains_key("additionalProperties") {
+                self.add_finding(pointer, None, Issue::PropertiesWithAdditionalProperties);
+            }
+
+            self.check_junctor_subschemas(obj, pointer);
+        }
+
+        let previous = (self.in_junctor, self.int_or_string_parent);
+        self.in_junctor = in_junctor;
+        self.int_or_string_parent = !in_junctor && int_or_string;
+        transform_subschemas_with_path(self, schema, pointer);
+        (self.in_junctor, self.int_or_string_parent) = previous;
+    }
+}
+
+/// A construct in a schema that is not allowed in a Kubernetes structural schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Finding {
+    /// A JSON Pointer to the part of the schema that is not allowed.
+    ///
+    /// For [`Issue::UnsupportedKeyword`] and [`Issue::KeywordInJunctor`], this points to the
+    /// keyword itself. For other issues, this points to the schema containing the issue, which is
+    /// an empty string for the root schema.
+    pub path: String,
+    /// The construct that is not allowed.
+    pub issue: Issue,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.issue)?;
+        if !self.path.is_empty() {
+            write!(f, " (at \"{}\")", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// A kind of construct that is not allowed in a Kubernetes structural schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+#[non_exhaustive]
+pub enum Issue {
+    /// The root schema is not an object schema.
+    RootNotObject,
+    /// The schema uses a keyword that is not supported, e.g. `$ref` or `const`.
+    UnsupportedKeyword { keyword: String },
+    /// A boolean schema (`true` or `false`) is used.
+    BooleanSchema { value: bool },
+    /// The schema does not specify a `type`, `x-kubernetes-int-or-string` or
+    /// `x-kubernetes-preserve-unknown-fields`, e.g. the schema for an untagged enum.
+    MissingType,
+    /// The schema's `type` is not a single string, e.g. because it includes `"null"`.
+    TypeNotString,
+    /// An array schema does not specify `items`.
+    MissingItems,
+    /// An object schema specifies both `properties` and `additionalProperties`.
+    PropertiesWithAdditionalProperties,
+    /// A subschema of `allOf`, `anyOf`, `oneOf` or `not` uses a keyword that is not allowed
+    /// there.
+    KeywordInJunctor { keyword: String },
+    /// A subschema of `allOf`, `anyOf`, `oneOf` or `not` specifies a property that is not
+    /// specified outside of it.
+    PropertyOnlyInJunctor { property: String },
+    /// A subschema of `allOf`, `anyOf`, `oneOf` or `not` specifies `items`, but the schema
+    /// containing it does not.
+    ItemsOnlyInJunctor,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::RootNotObject => f.write_str("root schema must be an object schema"),
+            Issue::UnsupportedKeyword { keyword } => {
+                write!(f, "keyword \"{keyword}\" is not supported")
+            }
+            Issue::BooleanSchema { value } => {
+                write!(f, "boolean schema `{value}` is not supported")
+            }
+            Issue::MissingType => f.write_str("schema does not specify a type"),
+            Issue::TypeNotString => f.write_str("\"type\" must be a single string"),
+            Issue::MissingItems => f.write_str("array schema does not specify \"items\""),
+            Issue::PropertiesWithAdditionalProperties => {
+                f.write_str("\"properties\" and \"additionalProperties\" must not be used together")
+            }
+            Issue::KeywordInJunctor { keyword } => write!(
+                f,
+                "keyword \"{keyword}\" is not allowed within a logical junctor"
+            ),
+            Issue::PropertyOnlyInJunctor { property } => write!(
+                f,
+                "property \"{property}\" must also be specified outside of the logical junctor"
+            ),
+            Issue::ItemsOnlyInJunctor => {
+                f.write_str("\"items\" must also be specified outside of the logical junctor")
+            }
+        }
+    }
+}
@@ -15,10 +15,13 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+mod builder;
 mod encoding;
 mod json_schema_impls;
 mod schema;
 mod ser;
+#[cfg(feature = "tool")]
+mod tool;
 #[macro_use]
 mod macros;
 
@@ -27,9 +30,27 @@ mod macros;
 #[doc(hidden)]
 #[allow(clippy::exhaustive_structs)]
 pub mod _private;
+#[cfg(feature = "avro")]
+pub mod avro;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+pub mod compat;
 pub mod consts;
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod export;
 pub mod generate;
+pub mod kubernetes;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "sample")]
+pub mod sample;
+pub mod structured_output;
 pub mod transform;
+#[cfg(feature = "typescript")]
+pub mod typescript;
+#[cfg(feature = "validate")]
+pub mod validate;
 
 #[cfg(feature = "schemars_derive")]
 extern crate schemars_derive;
@@ -38,9 +59,12 @@ use alloc::borrow::Cow;
 #[cfg(feature = "schemars_derive")]
 pub use schemars_derive::*;
 
+pub use builder::SchemaBuilder;
 #[doc(inline)]
 pub use generate::SchemaGenerator;
 pub use schema::Schema;
+#[cfg(feature = "tool")]
+pub use tool::ToolSchema;
 
 mod _alloc_prelude {
     pub use alloc::borrow::ToOwned;
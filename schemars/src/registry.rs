@@ -0,0 +1,120 @@
+/*!
+A registry of all types that derive [`JsonSchema`] with the `#[schemars(export)]` attribute.
+
+Types are collected at link time, so the registry contains every exported type from every crate
+linked into the final binary, without needing to list them by hand. This requires the `registry`
+feature, and is only supported on platforms supported by the
+[linkme](https://crates.io/crates/linkme) crate.
+
+Types can optionally be placed in a named group using `#[schemars(export = "group")]`, and then
+enumerated using [`group()`].
+
+Generic types cannot be exported, because the registry has no way of knowing which type parameters
+to use.
+
+# Example
+```
+use schemars::{registry, JsonSchema, SchemaGenerator};
+
+#[derive(JsonSchema)]
+#[schemars(export)]
+struct User {
+    name: String,
+}
+
+#[derive(JsonSchema)]
+#[schemars(export = "orders")]
+struct Order {
+    user: User,
+    total: u64,
+}
+
+let mut generator = SchemaGenerator::default();
+for registered in registry::iter() {
+    registered.subschema_fn()(&mut generator);
+}
+
+assert!(generator.definitions().contains_key("User"));
+assert!(generator.definitions().contains_key("Order"));
+
+let orders = registry::group("orders")
+    .map(|r| r.schema_name())
+    .collect::<Vec<_>>();
+assert_eq!(orders, ["Order"]);
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::{JsonSchema, Schema, SchemaGenerator};
+use alloc::borrow::Cow;
+
+/// A type that was registered with the `#[schemars(export)]` attribute.
+#[derive(Debug, Clone, Copy)]
+pub struct RegisteredType {
+    group: Option<&'static str>,
+    schema_name: fn() -> Cow<'static, str>,
+    schema_id: fn() -> Cow<'static, str>,
+    subschema: fn(&mut SchemaGenerator) -> Schema,
+    root_schema: fn(SchemaGenerator) -> Schema,
+}
+
+impl RegisteredType {
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn new<T: ?Sized + JsonSchema>(group: Option<&'static str>) -> Self {
+        Self {
+            group,
+            schema_name: T::schema_name,
+            schema_id: T::schema_id,
+            subschema: SchemaGenerator::subschema_for::<T>,
+            root_schema: SchemaGenerator::into_root_schema_for::<T>,
+        }
+    }
+
+    /// Returns the group given in the `#[schemars(export = "group")]` attribute, or `None` if the
+    /// type was registered using `#[schemars(export)]`.
+    #[must_use]
+    pub fn group(&self) -> Option<&'static str> {
+        self.group
+    }
+
+    /// Returns the type's [`JsonSchema::schema_name()`].
+    #[must_use]
+    pub fn schema_name(&self) -> Cow<'static, str> {
+        (self.schema_name)()
+    }
+
+    /// Returns the type's [`JsonSchema::schema_id()`].
+    #[must_use]
+    pub fn schema_id(&self) -> Cow<'static, str> {
+        (self.schema_id)()
+    }
+
+    /// Returns a function that generates a schema for the type using
+    /// [`SchemaGenerator::subschema_for()`], which adds the type's definition to the generator.
+    #[must_use]
+    pub fn subschema_fn(&self) -> fn(&mut SchemaGenerator) -> Schema {
+        self.subschema
+    }
+
+    /// Returns a function that generates a root schema for the type using
+    /// [`SchemaGenerator::into_root_schema_for()`].
+    #[must_use]
+    pub fn root_schema_fn(&self) -> fn(SchemaGenerator) -> Schema {
+        self.root_schema
+    }
+}
+
+/// Returns an iterator over all registered types, ordered by their [schema
+/// ID](JsonSchema::schema_id).
+pub fn iter() -> impl Iterator<Item = &'static RegisteredType> {
+    let mut types = crate::_private::REGISTERED_TYPES.iter().collect::<Vec<_>>();
+    types.sort_by_cached_key(|t| t.schema_id());
+    types.into_iter()
+}
+
+/// Returns an iterator over the registered types in the given group, ordered by their [schema
+/// ID](JsonSchema::schema_id).
+pub fn group(group: &str) -> impl Iterator<Item = &'static RegisteredType> + '_ {
+    iter().filter(move |t| t.group == Some(group))
+}
@@ -0,0 +1,895 @@
+/*!
+Generation of sample JSON values that conform to a [`Schema`].
+
+This module is only available when the `sample` feature flag is enabled.
+
+A [`Sampler`] deterministically generates values from a schema using a seedable pseudo-random
+number generator, so the same seed always produces the same sequence of values. This is useful
+for property-based testing, e.g. checking that all values that conform to a type's schema can be
+deserialized into that type. When the `proptest1` feature flag is also enabled, [`strategy`] can
+be used to create a [`proptest`](https://docs.rs/proptest) strategy.
+
+Generated values respect the following keywords: `$ref`, `type`, `enum`, `const`, `properties`,
+`required`, `additionalProperties`, `patternProperties` (for the patterns that schemars
+generates), `minProperties`, `maxProperties`, `dependentRequired`, `items`, `prefixItems`,
+`minItems`, `maxItems`, `uniqueItems`, `anyOf`, `oneOf`, `allOf`, `minimum`, `maximum`,
+`exclusiveMinimum`, `exclusiveMaximum`, `multipleOf`, `minLength`, `maxLength` and the `format`
+values that schemars generates. Other keywords (such as `pattern`, `not` or `if`) are ignored, so
+values generated from schemas that use them may not be valid.
+
+To avoid generating infinitely-large values from recursive schemas, once values are nested within
+a certain number of `$ref`s (the [depth budget](Sampler::with_max_depth)), the sampler only
+generates the smallest values that it can, e.g. by omitting optional properties and choosing
+`anyOf`/`oneOf` subschemas that do not contain a `$ref`.
+
+# Example
+```
+use schemars::{sample::Sampler, schema_for, JsonSchema};
+use serde::Deserialize;
+
+#[derive(JsonSchema, Deserialize)]
+struct MyStruct {
+    #[schemars(range(min = 1, max = 10))]
+    my_int: u8,
+    my_string: Option<String>,
+    my_children: Vec<MyStruct>,
+}
+
+let schema = schema_for!(MyStruct);
+
+for value in Sampler::new(&schema).with_seed(123).take(20) {
+    let my_struct: MyStruct = serde_json::from_value(value).unwrap();
+    assert!((1..=10).contains(&my_struct.my_int));
+}
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::schema::{contains_ref, follow_value_refs};
+use crate::Schema;
+use alloc::collections::BTreeSet;
+use serde_json::{Map, Number, Value};
+
+const DEFAULT_MAX_DEPTH: usize = 4;
+// How much further than `max_depth` values may be nested before giving up
+const DEPTH_LIMIT_MARGIN: usize = 64;
+// The range of integers generated when a schema does not specify any bounds
+const DEFAULT_INTEGER_RANGE: i128 = 1000;
+// The maximum number of array items/object properties generated beyond the minimum
+const MAX_EXTRA_ITEMS: u64 = 4;
+// The maximum number of characters generated beyond `minLength`
+const MAX_EXTRA_CHARS: u64 = 10;
+// How many times to try generating a value that isn't already in an array with `uniqueItems`
+const UNIQUE_ITEM_ATTEMPTS: usize = 10;
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const HEX: &[u8] = b"0123456789abcdef";
+
+/// A deterministic generator of JSON values that conform to a [`Schema`].
+///
+/// The sampler is also an [`Iterator`], which yields values until the schema is found to be
+/// unsatisfiable.
+///
+/// See the [module documentation](crate::sample) for more information.
+#[derive(Debug, Clone)]
+pub struct Sampler<'a> {
+    root: &'a Schema,
+    definitions: Option<&'a Map<String, Value>>,
+    rng: Rng,
+    max_depth: usize,
+}
+
+impl<'a> Sampler<'a> {
+    /// Creates a new `Sampler` for the given schema, with a seed of 0.
+    ///
+    /// Any `$ref`s within the schema are resolved against `schema`, so this should typically be
+    /// called with a root schema (e.g. one returned by [`schema_for!`](crate::schema_for)) rather
+    /// than a subschema. To also resolve `$ref`s against a generator's definitions, use
+    /// [`with_definitions`](Sampler::with_definitions).
+    #[must_use]
+    pub fn new(schema: &'a Schema) -> Self {
+        Self {
+            root: schema,
+            definitions: None,
+            rng: Rng::new(0),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets the seed of the sampler's pseudo-random number generator.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Sets the number of nested `$ref`s after which the sampler only generates the smallest
+    /// values that it can. Defaults to 4.
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the definitions used to resolve `$ref`s that cannot be resolved against the schema
+    /// itself, e.g. those returned by
+    /// [`SchemaGenerator::definitions()`](crate::SchemaGenerator::definitions).
+    ///
+    /// Definitions are looked up by the last segment of the `$ref`, e.g. a `$ref` of
+    /// `#/$defs/MyStruct` is resolved to the definition named `MyStruct`.
+    #[must_use]
+    pub fn with_definitions(mut self, definitions: &'a Map<String, Value>) -> Self {
+        self.definitions = Some(definitions);
+        self
+    }
+
+    /// Generates a value that conforms to the schema, or returns `None` if no conforming value
+    /// could be generated (e.g. because the schema is `false`).
+    pub fn sample(&mut self) -> Option<Value> {
+        self.generate(self.root.as_value(), 0)
+    }
+
+    fn generate<'s>(&mut self, schema: &'s Value, depth: usize) -> Option<Value>
+    where
+        'a: 's,
+    {
+        let obj = match schema {
+            Value::Object(obj) => obj,
+            Value::Bool(true) => return Some(self.any_value()),
+            _ => return None,
+        };
+
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            if depth >= self.max_depth + DEPTH_LIMIT_MARGIN {
+                return None;
+            }
+            let target = self.resolve_ref(reference)?.as_value();
+            return match siblings(obj, &["$ref"]) {
+                Some(siblings) => self.generate(&merge(siblings, target), depth + 1),
+                None => self.generate(target, depth + 1),
+            };
+        }
+
+        if let Some(Value::Array(subschemas)) = obj.get("allOf") {
+            let mut merged = obj.clone();
+            merged.remove("allOf");
+            for subschema in subschemas {
+                match merge(merged, self.resolve_chain(subschema)) {
+                    Value::Object(m) => merged = m,
+                    _ => return None,
+                }
+            }
+            return self.generate(&Value::Object(merged), depth);
+        }
+
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(Value::Array(subschemas)) = obj.get(keyword) {
+                let branch = self.choose_branch(subschemas, depth)?;
+                return match siblings(obj, &["oneOf", "anyOf"]) {
+                    Some(siblings) => {
+                        let branch = self.resolve_chain(branch);
+                        self.generate(&merge(siblings, branch), depth)
+                    }
+                    None => self.generate(branch, depth),
+                };
+            }
+        }
+
+        if let Some(value) = obj.get("const") {
+            return Some(value.clone());
+        }
+
+        if let Some(Value::Array(values)) = obj.get("enum") {
+            return self.rng.choose(values).cloned();
+        }
+
+        match self.choose_type(obj, depth) {
+            Some("null") => Some(Value::Null),
+            Some("boolean") => Some(Value::Bool(self.rng.chance(1, 2))),
+            Some("integer") => self.integer(obj),
+            Some("number") => self.number(obj),
+            Some("string") => Some(Value::String(self.string(obj))),
+            Some("array") => self.array(obj, depth),
+            Some("object") => self.object(obj, depth),
+            Some(_) => None,
+            None => Some(self.any_value()),
+        }
+    }
+
+    fn resolve_ref(&self, reference: &str) -> Option<&'a Schema> {
+        if let Some(target) = self.root.resolve_ref(reference) {
+            return Some(target);
+        }
+
+        let name = reference.rsplit('/').next()?;
+        let name = crate::encoding::percent_decode(name)?;
+        self.definitions?
+            .get(&name.replace("~1", "/").replace("~0", "~"))?
+            .try_into()
+            .ok()
+    }
+
+    fn resolve_chain<'s>(&self, schema: &'s Value) -> &'s Value
+    where
+        'a: 's,
+    {
+        follow_value_refs(schema, |reference| self.resolve_ref(reference))
+    }
+
+    fn choose_branch<'s>(&mut self, subschemas: &'s [Value], depth: usize) -> Option<&'s Value> {
+        if depth >= self.max_depth {
+            // Prefer subschemas that don't recurse any deeper
+            if let Some(branch) = subschemas.iter().find(|s| !contains_ref(s)) {
+                return Some(branch);
+            }
+        }
+        self.rng.choose(subschemas)
+    }
+
+    fn choose_type(&mut self, obj: &Map<String, Value>, depth: usize) -> Option<&'static str> {
+        let types: Vec<&str> = match obj.get("type") {
+            Some(Value::String(ty)) => vec![ty.as_str()],
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+            _ => return infer_type(obj),
+        };
+
+        let ty = if depth >= self.max_depth {
+            // Prefer types that can't contain any further values
+            ["null", "boolean", "integer", "number", "string"]
+                .into_iter()
+                .find(|t| types.contains(t))
+                .or_else(|| types.first().copied())
+        } else {
+            self.rng.choose(&types).copied()
+        };
+
+        ty.map(|ty| match ty {
+            "null" => "null",
+            "boolean" => "boolean",
+            "integer" => "integer",
+            "number" => "number",
+            "string" => "string",
+            "array" => "array",
+            "object" => "object",
+            _ => "unknown",
+        })
+    }
+
+    fn any_value(&mut self) -> Value {
+        match self.rng.below(4) {
+            0 => Value::Null,
+            1 => Value::Bool(self.rng.chance(1, 2)),
+            2 => integer_value(
+                self.rng
+                    .range(-DEFAULT_INTEGER_RANGE, DEFAULT_INTEGER_RANGE),
+            )
+            .unwrap_or(Value::Null),
+            _ => Value::String(self.chars(0, 8)),
+        }
+    }
+
+    // `multipleOf` is only used when it is a whole number
+    #[allow(clippy::cast_possible_truncation)]
+    fn integer(&mut self, obj: &Map<String, Value>) -> Option<Value> {
+        let (min, max) = integer_bounds(obj)?;
+
+        let value = match obj.get("multipleOf").and_then(Value::as_f64) {
+            Some(step) if step >= 1.0 && is_integral(step) => {
+                let step = step as i128;
+                let first = div_ceil(min, step);
+                let last = div_floor(max, step);
+                if first > last {
+                    return None;
+                }
+                self.rng.range(first, last) * step
+            }
+            _ => self.rng.range(min, max),
+        };
+
+        integer_value(value)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn number(&mut self, obj: &Map<String, Value>) -> Option<Value> {
+        // Generated numbers are multiples of the step, which defaults to 0.25 so that they are
+        // exactly representable by both `f32` and `f64`.
+        let step = obj
+            .get("multipleOf")
+            .and_then(Value::as_f64)
+            .filter(|s| *s > 0.0)
+            .unwrap_or(0.25);
+
+        let bound = |keyword: &str| obj.get(keyword).and_then(Value::as_f64);
+        let min = bound("minimum");
+        let max = bound("maximum");
+        let exclusive_min = bound("exclusiveMinimum");
+        let exclusive_max = bound("exclusiveMaximum");
+
+        let lower = [min, exclusive_min].into_iter().flatten().reduce(f64::max);
+        let upper = [max, exclusive_max].into_iter().flatten().reduce(f64::min);
+        let default_range = DEFAULT_INTEGER_RANGE as f64;
+        let (lower, upper) = match (lower, upper) {
+            (Some(l), Some(u)) => (l, u),
+            (Some(l), None) => (l, l + 2.0 * default_range),
+            (None, Some(u)) => (u - 2.0 * default_range, u),
+            (None, None) => (-default_range, default_range),
+        };
+
+        let first = ceil(lower / step);
+        let last = floor(upper / step);
+        if first > last || !first.is_finite() || !last.is_finite() {
+            return None;
+        }
+
+        let is_valid = |n: f64| {
+            min.map_or(true, |m| n >= m)
+                && max.map_or(true, |m| n <= m)
+                && exclusive_min.map_or(true, |m| n > m)
+                && exclusive_max.map_or(true, |m| n < m)
+        };
+
+        let (first, last) = (first as i128, last as i128);
+        let candidate = self.rng.range(first, last) as f64 * step;
+        let value = [candidate, first as f64 * step, (first + 1) as f64 * step]
+            .into_iter()
+            .find(|n| is_valid(*n))?;
+
+        Number::from_f64(value).map(Value::Number)
+    }
+
+    fn string(&mut self, obj: &Map<String, Value>) -> String {
+        let length = |keyword: &str| obj.get(keyword).and_then(Value::as_u64);
+        let min = length("minLength").unwrap_or(0);
+        let max = length("maxLength").unwrap_or(u64::MAX);
+
+        if let Some(formatted) = obj
+            .get("format")
+            .and_then(Value::as_str)
+            .and_then(|f| self.formatted_string(f))
+        {
+            let len = formatted.chars().count() as u64;
+            if len >= min && len <= max {
+                return formatted;
+            }
+        }
+
+        self.chars(min, max)
+    }
+
+    fn chars(&mut self, min: u64, max: u64) -> String {
+        const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 _-";
+
+        let max = max.min(min.saturating_add(MAX_EXTRA_CHARS));
+        let len = self.rng.range(i128::from(min), i128::from(max));
+        (0..len)
+            .map(|_| char::from(*self.rng.choose(CHARS).unwrap_or(&b'a')))
+            .collect()
+    }
+
+    fn formatted_string(&mut self, format: &str) -> Option<String> {
+        let digits = |rng: &mut Rng, min: i128, max: i128, width: usize| {
+            format!("{:0width$}", rng.range(min, max))
+        };
+        let rng = &mut self.rng;
+
+        let date = |rng: &mut Rng| {
+            format!(
+                "{}-{}-{}",
+                digits(rng, 1970, 2099, 4),
+                digits(rng, 1, 12, 2),
+                digits(rng, 1, 28, 2)
+            )
+        };
+        let time = |rng: &mut Rng| {
+            format!(
+                "{}:{}:{}",
+                digits(rng, 0, 23, 2),
+                digits(rng, 0, 59, 2),
+                digits(rng, 0, 59, 2)
+            )
+        };
+        let word = |rng: &mut Rng| {
+            (0..rng.range(1, 8))
+                .map(|_| char::from(*rng.choose(LOWERCASE).unwrap_or(&b'a')))
+                .collect::<String>()
+        };
+
+        Some(match format {
+            "date-time" => format!("{}T{}Z", date(rng), time(rng)),
+            "partial-date-time" => format!("{}T{}", date(rng), time(rng)),
+            "zoned-date-time" => format!("{}T{}+00:00[UTC]", date(rng), time(rng)),
+            "date" => date(rng),
+            "time" => format!("{}Z", time(rng)),
+            "partial-time" => time(rng),
+            "duration" => format!("PT{}S", rng.range(0, 100_000)),
+            "uuid" => {
+                let hex = |rng: &mut Rng, len: usize| {
+                    (0..len)
+                        .map(|_| char::from(*rng.choose(HEX).unwrap_or(&b'0')))
+                        .collect::<String>()
+                };
+                format!(
+                    "{}-{}-4{}-{}{}-{}",
+                    hex(rng, 8),
+                    hex(rng, 4),
+                    hex(rng, 3),
+                    char::from(*rng.choose(b"89ab").unwrap_or(&b'8')),
+                    hex(rng, 3),
+                    hex(rng, 12)
+                )
+            }
+            "ipv4" | "ip" => format!(
+                "{}.{}.{}.{}",
+                rng.range(0, 255),
+                rng.range(0, 255),
+                rng.range(0, 255),
+                rng.range(0, 255)
+            ),
+            "ipv6" => (0..8)
+                .map(|_| format!("{:x}", rng.range(0, 0xffff)))
+                .collect::<Vec<_>>()
+                .join(":"),
+            "email" | "idn-email" => format!("{}@example.com", word(rng)),
+            "hostname" | "idn-hostname" => format!("{}.example.com", word(rng)),
+            "uri" | "iri" | "uri-reference" | "iri-reference" => {
+                format!("https://example.com/{}", word(rng))
+            }
+            _ => return None,
+        })
+    }
+
+    fn array(&mut self, obj: &Map<String, Value>, depth: usize) -> Option<Value> {
+        // Draft 2020-12 uses `prefixItems`, earlier drafts use `items` with an array value
+        let (prefix_items, items) = match (obj.get("prefixItems"), obj.get("items")) {
+            (Some(Value::Array(prefix)), items) => (prefix.as_slice(), items),
+            (_, Some(Value::Array(prefix))) => (prefix.as_slice(), obj.get("additionalItems")),
+            (_, items) => (&[][..], items),
+        };
+
+        let count = |keyword: &str| obj.get(keyword).and_then(Value::as_u64);
+        let min = count("minItems").unwrap_or(0);
+        let max = count("maxItems").unwrap_or(u64::MAX);
+        let unique = obj.get("uniqueItems") == Some(&Value::Bool(true));
+
+        let mut array = Vec::new();
+        for item in prefix_items
+            .iter()
+            .take(usize::try_from(max).unwrap_or(usize::MAX))
+        {
+            array.push(self.generate(item, depth)?);
+        }
+
+        let prefix_len = array.len() as u64;
+        let extra_allowed = items != Some(&Value::Bool(false));
+        let target = if !extra_allowed {
+            prefix_len
+        } else if depth >= self.max_depth {
+            min.max(prefix_len)
+        } else {
+            let lower = min.max(prefix_len);
+            let upper = max.min(lower.saturating_add(MAX_EXTRA_ITEMS));
+            self.rng.between(lower, upper)
+        };
+
+        let items_schema = items.unwrap_or(&Value::Bool(true));
+        while (array.len() as u64) < target {
+            let mut item = self.generate(items_schema, depth)?;
+            if unique {
+                let mut attempts = 0;
+                while array.contains(&item) {
+                    attempts += 1;
+                    if attempts >= UNIQUE_ITEM_ATTEMPTS {
+                        // Rather than failing, stop early if the minimum has already been met
+                        return ((array.len() as u64) >= min).then_some(Value::Array(array));
+                    }
+                    item = self.generate(items_schema, depth)?;
+                }
+            }
+            array.push(item);
+        }
+
+        ((array.len() as u64) >= min).then_some(Value::Array(array))
+    }
+
+    fn object(&mut self, obj: &Map<String, Value>, depth: usize) -> Option<Value> {
+        let empty = Map::new();
+        let properties = obj
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        let mut required = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect::<BTreeSet<_>>())
+            .unwrap_or_default();
+
+        let count = |keyword: &str| obj.get(keyword).and_then(Value::as_u64);
+        let min = count("minProperties").unwrap_or(0);
+        let max = count("maxProperties").unwrap_or(u64::MAX);
+
+        // Choose which optional properties to include, and add any properties that they require
+        let mut included = BTreeSet::new();
+        for name in properties.keys() {
+            let is_required = required.contains(name.as_str());
+            if is_required || (depth < self.max_depth && self.rng.chance(1, 2)) {
+                included.insert(name.as_str());
+            }
+        }
+        if let Some(Value::Object(dependent_required)) = obj.get("dependentRequired") {
+            for (name, dependencies) in dependent_required {
+                if included.contains(name.as_str()) {
+                    for dependency in dependencies.as_array().into_iter().flatten() {
+                        if let Some(dependency) = dependency.as_str() {
+                            required.insert(dependency);
+                            if let Some((key, _)) = properties.get_key_value(dependency) {
+                                included.insert(key.as_str());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut object = Map::new();
+        for (name, schema) in properties {
+            if included.contains(name.as_str()) {
+                match self.generate(schema, depth) {
+                    Some(value) => {
+                        object.insert(name.clone(), value);
+                    }
+                    // Optional properties that can't be generated can be omitted
+                    None if !required.contains(name.as_str()) => {}
+                    None => return None,
+                }
+            }
+        }
+        for name in required {
+            if !object.contains_key(name) && !properties.contains_key(name) {
+                object.insert(name.to_owned(), self.any_value());
+            }
+        }
+
+        // Add extra properties allowed by `additionalProperties`/`patternProperties`
+        let additional = obj
+            .get("additionalProperties")
+            .or_else(|| obj.get("unevaluatedProperties"))
+            .filter(|a| *a != &Value::Bool(false));
+        let pattern_keys = obj
+            .get("patternProperties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter(|(pattern, _)| key_for_pattern(pattern, &mut Rng::new(0)).is_some())
+            .collect::<Vec<_>>();
+
+        if additional.is_some() || !pattern_keys.is_empty() {
+            let current = object.len() as u64;
+            let lower = min.max(current);
+            let target = if depth >= self.max_depth {
+                lower
+            } else {
+                let upper = max.min(lower.saturating_add(MAX_EXTRA_ITEMS));
+                self.rng.between(lower, upper)
+            };
+
+            let mut attempts = 0;
+            while (object.len() as u64) < target && attempts < target * 2 + 10 {
+                attempts += 1;
+                let (key, schema) = match self.rng.choose(&pattern_keys) {
+                    Some((pattern, schema)) if additional.is_none() || self.rng.chance(1, 2) => {
+                        match key_for_pattern(pattern, &mut self.rng) {
+                            Some(key) => (key, *schema),
+                            None => continue,
+                        }
+                    }
+                    _ => match additional {
+                        Some(schema) => (self.chars(1, 8), schema),
+                        None => continue,
+                    },
+                };
+                if !object.contains_key(&key) && !properties.contains_key(&key) {
+                    let value = self.generate(schema, depth)?;
+                    object.insert(key, value);
+                }
+            }
+        }
+
+        ((object.len() as u64) >= min).then_some(Value::Object(object))
+    }
+}
+
+impl Iterator for Sampler<'_> {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        self.sample()
+    }
+}
+
+/// Creates a [`proptest`](https://docs.rs/proptest) strategy that generates values conforming to
+/// the given schema.
+///
+/// This is only available when the `proptest1` feature flag is enabled. Values are generated
+/// using a [`Sampler`] seeded by proptest, so shrinking a failing case produces a different
+/// (rather than a "smaller") value. Seeds for which no value could be generated are rejected.
+///
+/// # Example
+/// ```
+/// use proptest1::prelude::*;
+/// use schemars::{sample, schema_for, JsonSchema};
+/// use serde::Deserialize;
+///
+/// #[derive(JsonSchema, Deserialize)]
+/// struct MyStruct {
+///     #[schemars(range(min = 1, max = 10))]
+///     my_int: i32,
+///     my_bool: Option<bool>,
+/// }
+///
+/// proptest!(|(value in sample::strategy(schema_for!(MyStruct)))| {
+///     let my_struct: MyStruct = serde_json::from_value(value).unwrap();
+///     prop_assert!((1..=10).contains(&my_struct.my_int));
+/// });
+/// ```
+#[cfg(feature = "proptest1")]
+pub fn strategy(schema: Schema) -> impl proptest1::strategy::Strategy<Value = Value> {
+    use proptest1::prelude::*;
+
+    any::<u64>().prop_filter_map("schema could not be sampled", move |seed| {
+        Sampler::new(&schema).with_seed(seed).sample()
+    })
+}
+
+// Returns the keywords of `obj` (other than `exclude` and annotations) which also apply to any
+// subschema, or `None` if there are no such keywords.
+fn siblings(obj: &Map<String, Value>, exclude: &[&str]) -> Option<Map<String, Value>> {
+    let siblings = obj
+        .iter()
+        .filter(|(k, _)| !exclude.contains(&k.as_str()) && !is_annotation(k))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect::<Map<_, _>>();
+    (!siblings.is_empty()).then_some(siblings)
+}
+
+fn is_annotation(keyword: &str) -> bool {
+    matches!(
+        keyword,
+        "$schema"
+            | "$id"
+            | "$comment"
+            | "$defs"
+            | "definitions"
+            | "title"
+            | "description"
+            | "default"
+            | "examples"
+            | "deprecated"
+            | "readOnly"
+            | "writeOnly"
+    )
+}
+
+// Merges the keywords of `schema` into `merged`, combining `properties` and `required` and
+// otherwise keeping any existing values.
+fn merge(mut merged: Map<String, Value>, schema: &Value) -> Value {
+    match schema {
+        Value::Object(obj) => {
+            for (key, value) in obj {
+                match (key.as_str(), merged.get_mut(key), value) {
+                    ("properties", Some(Value::Object(existing)), Value::Object(properties)) => {
+                        for (k, v) in properties {
+                            existing.entry(k.clone()).or_insert_with(|| v.clone());
+                        }
+                    }
+                    ("required", Some(Value::Array(existing)), Value::Array(required)) => {
+                        for r in required {
+                            if !existing.contains(r) {
+                                existing.push(r.clone());
+                            }
+                        }
+                    }
+                    (_, None, _) => {
+                        merged.insert(key.clone(), value.clone());
+                    }
+                    _ => {}
+                }
+            }
+            Value::Object(merged)
+        }
+        Value::Bool(true) => Value::Object(merged),
+        _ => Value::Bool(false),
+    }
+}
+
+fn infer_type(obj: &Map<String, Value>) -> Option<&'static str> {
+    let has_any = |keywords: &[&str]| keywords.iter().any(|k| obj.contains_key(*k));
+
+    if has_any(&[
+        "properties",
+        "required",
+        "additionalProperties",
+        "patternProperties",
+    ]) {
+        Some("object")
+    } else if has_any(&["items", "prefixItems", "minItems", "maxItems"]) {
+        Some("array")
+    } else if has_any(&["minLength", "maxLength", "format"]) {
+        Some("string")
+    } else if has_any(&["minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum"]) {
+        Some("number")
+    } else {
+        None
+    }
+}
+
+// Generates a property name matching one of the `patternProperties` patterns generated by
+// schemars for maps with integer keys.
+fn key_for_pattern(pattern: &str, rng: &mut Rng) -> Option<String> {
+    // Keys are kept small so that they fit into any integer type
+    match pattern {
+        r"^\d+$" => Some(rng.range(0, 100).to_string()),
+        r"^-?\d+$" => Some(rng.range(-100, 100).to_string()),
+        _ => None,
+    }
+}
+
+fn integer_bounds(obj: &Map<String, Value>) -> Option<(i128, i128)> {
+    let min = [
+        obj.get("minimum").and_then(|v| ceil_i128(v, false)),
+        obj.get("exclusiveMinimum").and_then(|v| ceil_i128(v, true)),
+    ]
+    .into_iter()
+    .flatten()
+    .max();
+    let max = [
+        obj.get("maximum").and_then(|v| floor_i128(v, false)),
+        obj.get("exclusiveMaximum")
+            .and_then(|v| floor_i128(v, true)),
+    ]
+    .into_iter()
+    .flatten()
+    .min();
+
+    let (min, max) = match (min, max) {
+        (Some(min), Some(max)) => (min, max),
+        (Some(min), None) => (min, min.saturating_add(2 * DEFAULT_INTEGER_RANGE)),
+        (None, Some(max)) => (max.saturating_sub(2 * DEFAULT_INTEGER_RANGE), max),
+        (None, None) => (-DEFAULT_INTEGER_RANGE, DEFAULT_INTEGER_RANGE),
+    };
+
+    (min <= max).then_some((min, max))
+}
+
+// Returns the smallest integer that is greater than (or equal to, if not `exclusive`) the value
+fn ceil_i128(value: &Value, exclusive: bool) -> Option<i128> {
+    let n = number_to_i128(value.as_number()?, true)?;
+    let is_exact = value.as_i64().is_some() || value.as_u64().is_some() || {
+        let f = value.as_f64()?;
+        is_integral(f)
+    };
+    Some(if exclusive && is_exact { n + 1 } else { n })
+}
+
+// Returns the largest integer that is less than (or equal to, if not `exclusive`) the value
+fn floor_i128(value: &Value, exclusive: bool) -> Option<i128> {
+    let n = number_to_i128(value.as_number()?, false)?;
+    let is_exact = value.as_i64().is_some() || value.as_u64().is_some() || {
+        let f = value.as_f64()?;
+        is_integral(f)
+    };
+    Some(if exclusive && is_exact { n - 1 } else { n })
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn number_to_i128(n: &Number, round_up: bool) -> Option<i128> {
+    if let Some(i) = n.as_i64() {
+        return Some(i.into());
+    }
+    if let Some(u) = n.as_u64() {
+        return Some(u.into());
+    }
+    let f = n.as_f64()?;
+    let rounded = if round_up { ceil(f) } else { floor(f) };
+    // Out-of-range floats are saturated by the cast
+    Some(rounded as i128)
+}
+
+fn integer_value(value: i128) -> Option<Value> {
+    if let Ok(i) = i64::try_from(value) {
+        Some(Value::from(i))
+    } else {
+        u64::try_from(value).ok().map(Value::from)
+    }
+}
+
+#[allow(clippy::float_cmp)]
+fn is_integral(value: f64) -> bool {
+    value.is_finite() && floor(value) == value
+}
+
+// `f64::floor` and `f64::ceil` are not available in `no_std` environments
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn floor(value: f64) -> f64 {
+    // Floats this large have no fractional part
+    if !value.is_finite() || value >= 4_503_599_627_370_496.0 || value <= -4_503_599_627_370_496.0 {
+        return value;
+    }
+    let truncated = value as i64 as f64;
+    if truncated > value {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+fn ceil(value: f64) -> f64 {
+    -floor(-value)
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -div_floor(-a, b)
+}
+
+// A small, fast, deterministic pseudo-random number generator (SplitMix64)
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+// Truncating or wrapping random bits doesn't make them any less random
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Returns a number in the range `0..n`, where `n` must be non-zero
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    // Returns a number in the range `min..=max`
+    fn between(&mut self, min: u64, max: u64) -> u64 {
+        if min >= max {
+            return min;
+        }
+        min + self.next_u64() % (max - min).saturating_add(1)
+    }
+
+    // Returns a number in the range `min..=max`
+    fn range(&mut self, min: i128, max: i128) -> i128 {
+        if min >= max {
+            return min;
+        }
+        let span = (max - min).unsigned_abs().saturating_add(1);
+        let random = (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64());
+        min + (random % span) as i128
+    }
+
+    fn choose<'t, T>(&mut self, items: &'t [T]) -> Option<&'t T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.next_u64() as usize % items.len())
+        }
+    }
+}
@@ -71,7 +71,7 @@ impl<'de> Deserialize<'de> for Schema {
         D: serde::Deserializer<'de>,
     {
         let value = Value::deserialize(deserializer)?;
-        Schema::validate(&value)?;
+        Schema::check_value_type(&value)?;
         Ok(Schema(value))
     }
 }
@@ -159,6 +159,10 @@ impl Schema {
         self.0.as_object_mut()
     }
 
+    pub(crate) fn as_value_mut(&mut self) -> &mut Value {
+        &mut self.0
+    }
+
     pub(crate) fn try_to_object(self) -> Result<Map<String, Value>, bool> {
         match self.0 {
             Value::Object(m) => Ok(m),
@@ -354,6 +358,73 @@ impl Schema {
         }
     }
 
+    /// Looks up the subschema that a `$ref` value points to, treating this schema as the root
+    /// schema document.
+    ///
+    /// Only references that are URI fragments (i.e. begin with `#`) can be resolved, and the
+    /// fragment is percent-decoded before being used as a JSON Pointer. This returns `None` if the
+    /// reference can't be resolved, or if it points to a value that is not a valid schema.
+    ///
+    /// This does not follow any `$ref` within the returned subschema - to do that, use
+    /// [`resolve`](Self::resolve).
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::json_schema;
+    ///
+    /// let schema = json_schema!({
+    ///     "$ref": "#/$defs/My%20Struct",
+    ///     "$defs": {
+    ///         "My Struct": { "type": "object" }
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(
+    ///     schema.resolve_ref("#/$defs/My%20Struct"),
+    ///     Some(&json_schema!({ "type": "object" }))
+    /// );
+    /// assert_eq!(schema.resolve_ref("#/$defs/Missing"), None);
+    /// assert_eq!(schema.resolve_ref("https://example.com/schema"), None);
+    /// ```
+    #[must_use]
+    pub fn resolve_ref(&self, reference: &str) -> Option<&Schema> {
+        if !reference.starts_with('#') {
+            return None;
+        }
+
+        self.pointer(reference)?.try_into().ok()
+    }
+
+    /// If `schema` has a `$ref`, follows it (and any `$ref` in the schema that it points to, and
+    /// so on) using [`resolve_ref`](Self::resolve_ref), treating this schema as the root schema
+    /// document. If `schema` does not have a `$ref`, then it is returned unchanged.
+    ///
+    /// Any keywords alongside a `$ref` are ignored. This returns `None` if any `$ref` in the chain
+    /// can't be resolved, or if the chain of `$ref`s forms a cycle.
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::json_schema;
+    ///
+    /// let root = json_schema!({
+    ///     "$defs": {
+    ///         "A": { "$ref": "#/$defs/B" },
+    ///         "B": { "type": "string" },
+    ///         "Cycle": { "$ref": "#/$defs/Cycle" }
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(
+    ///     root.resolve(&json_schema!({ "$ref": "#/$defs/A" })),
+    ///     Some(&json_schema!({ "type": "string" }))
+    /// );
+    /// assert_eq!(root.resolve(&json_schema!({ "$ref": "#/$defs/Cycle" })), None);
+    /// ```
+    #[must_use]
+    pub fn resolve<'a>(&'a self, schema: &'a Schema) -> Option<&'a Schema> {
+        follow_refs(schema, |reference| self.resolve_ref(reference))
+    }
+
     /// If the `Schema`'s underlying JSON value is an object, removes and returns its value for the
     /// given key.
     ///
@@ -376,6 +447,76 @@ impl Schema {
         self.0.as_object_mut().and_then(|o| o.remove(key))
     }
 
+    /// Replaces every `$ref` within this root schema that points to one of its definitions with the
+    /// definition itself, and removes any definitions that are no longer referenced.
+    ///
+    /// Recursive `$ref`s are left in place (along with the definitions that they point to) where
+    /// they occur within the definition that they point to. To configure how cycles are handled,
+    /// use the [`Dereference`](crate::transform::Dereference) transform instead.
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::json_schema;
+    ///
+    /// let mut schema = json_schema!({
+    ///     "type": "array",
+    ///     "items": { "$ref": "#/$defs/Item" },
+    ///     "$defs": {
+    ///         "Item": { "type": "string" }
+    ///     }
+    /// });
+    ///
+    /// schema.dereference();
+    ///
+    /// assert_eq!(
+    ///     schema,
+    ///     json_schema!({
+    ///         "type": "array",
+    ///         "items": { "type": "string" }
+    ///     })
+    /// );
+    /// ```
+    pub fn dereference(&mut self) {
+        use crate::transform::Transform;
+        crate::transform::Dereference::default().transform(self);
+    }
+
+    /// Moves subschemas that occur more than once within this root schema into its definitions,
+    /// replacing each occurrence with a `$ref`. This is the reverse of
+    /// [`dereference`](Self::dereference).
+    ///
+    /// See the [`ExtractDefinitions`](crate::transform::ExtractDefinitions) transform for details
+    /// of which subschemas are extracted and how they are named.
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::json_schema;
+    ///
+    /// let mut schema = json_schema!({
+    ///     "type": "array",
+    ///     "items": { "title": "Color", "enum": ["red", "green"] },
+    ///     "contains": { "title": "Color", "enum": ["red", "green"] }
+    /// });
+    ///
+    /// schema.extract_definitions();
+    ///
+    /// assert_eq!(
+    ///     schema,
+    ///     json_schema!({
+    ///         "type": "array",
+    ///         "items": { "$ref": "#/$defs/Color" },
+    ///         "contains": { "$ref": "#/$defs/Color" },
+    ///         "$defs": {
+    ///             "Color": { "title": "Color", "enum": ["red", "green"] }
+    ///         }
+    ///     })
+    /// );
+    /// ```
+    pub fn extract_definitions(&mut self) {
+        use crate::transform::Transform;
+        crate::transform::ExtractDefinitions::default().transform(self);
+    }
+
     pub(crate) fn has_type(&self, ty: &str) -> bool {
         match self.0.get("type") {
             Some(Value::Array(values)) => values.iter().any(|v| v.as_str() == Some(ty)),
@@ -384,7 +525,7 @@ impl Schema {
         }
     }
 
-    fn validate<E: serde::de::Error>(value: &Value) -> Result<(), E> {
+    fn check_value_type<E: serde::de::Error>(value: &Value) -> Result<(), E> {
         use serde::de::Unexpected;
         let unexpected = match value {
             Value::Bool(_) | Value::Object(_) => return Ok(()),
@@ -414,6 +555,45 @@ impl Schema {
     fn ref_cast_mut(value: &mut Value) -> &mut Self;
 }
 
+// Follows a chain of `$ref`s starting at `schema`, using `resolve_ref` to look up each reference.
+// Returns `None` if a reference can't be resolved, or if the chain forms a cycle.
+pub(crate) fn follow_refs<'a>(
+    mut schema: &'a Schema,
+    resolve_ref: impl Fn(&str) -> Option<&'a Schema>,
+) -> Option<&'a Schema> {
+    let mut visited = alloc::collections::BTreeSet::new();
+
+    while let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if !visited.insert(reference) {
+            return None;
+        }
+        schema = resolve_ref(reference)?;
+    }
+
+    Some(schema)
+}
+
+// Like `follow_refs`, but starting at a JSON value. Returns `value` itself if it's not a valid
+// schema, or if its chain of `$ref`s can't be followed.
+pub(crate) fn follow_value_refs<'a>(
+    value: &'a Value,
+    resolve_ref: impl Fn(&str) -> Option<&'a Schema>,
+) -> &'a Value {
+    <&Schema>::try_from(value)
+        .ok()
+        .and_then(|schema| follow_refs(schema, resolve_ref))
+        .map_or(value, Schema::as_value)
+}
+
+// Returns true if `value` (or any value nested within it) has a `$ref` property.
+pub(crate) fn contains_ref(value: &Value) -> bool {
+    match value {
+        Value::Object(obj) => obj.contains_key("$ref") || obj.values().any(contains_ref),
+        Value::Array(array) => array.iter().any(contains_ref),
+        _ => false,
+    }
+}
+
 impl From<Schema> for Value {
     fn from(v: Schema) -> Value {
         v.0
@@ -424,7 +604,7 @@ impl core::convert::TryFrom<Value> for Schema {
     type Error = serde_json::Error;
 
     fn try_from(value: Value) -> serde_json::Result<Schema> {
-        Schema::validate(&value)?;
+        Schema::check_value_type(&value)?;
         Ok(Schema(value))
     }
 }
@@ -433,7 +613,7 @@ impl<'a> core::convert::TryFrom<&'a Value> for &'a Schema {
     type Error = serde_json::Error;
 
     fn try_from(value: &Value) -> serde_json::Result<&Schema> {
-        Schema::validate(value)?;
+        Schema::check_value_type(value)?;
         Ok(Schema::ref_cast(value))
     }
 }
@@ -442,7 +622,7 @@ impl<'a> core::convert::TryFrom<&'a mut Value> for &'a mut Schema {
     type Error = serde_json::Error;
 
     fn try_from(value: &mut Value) -> serde_json::Result<&mut Schema> {
-        Schema::validate(value)?;
+        Schema::check_value_type(value)?;
         Ok(Schema::ref_cast_mut(value))
     }
 }
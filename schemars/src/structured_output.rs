@@ -0,0 +1,220 @@
+/*!
+Checking whether a [`Schema`] can be used with the "strict" structured output mode of LLM APIs.
+
+LLM APIs that support structured outputs (e.g. for tool-call parameters or response formats) often
+have a "strict" mode that only accepts a subset of JSON Schema. In particular:
+- the root schema must be an object schema
+- every object schema must set `additionalProperties` to `false`, and list all of its properties in
+  `required`
+- `$ref` must not have sibling keywords
+- only a small set of keywords is supported
+
+The [`StrictStructuredOutput`] transform (which is used by
+[`SchemaSettings::strict_structured_output()`](crate::generate::SchemaSettings::strict_structured_output))
+rewrites schemas to fit these restrictions where possible. [`check`] reports any constructs that
+remain in a schema that cannot be expressed in strict mode, such as maps with arbitrary keys or
+tuples.
+
+# Example
+```
+use schemars::generate::SchemaSettings;
+use schemars::structured_output::{self, Issue};
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+
+#[derive(JsonSchema)]
+struct Weather {
+    /// City name
+    city: String,
+    /// Temperature in Celsius
+    temperature: Option<f64>,
+    tags: BTreeMap<String, String>,
+}
+
+let generator = SchemaSettings::strict_structured_output().into_generator();
+let schema = generator.into_root_schema_for::<Weather>();
+
+let findings = structured_output::check(&schema);
+
+assert_eq!(findings.len(), 1);
+assert_eq!(findings[0].path, "/properties/tags");
+assert_eq!(findings[0].issue, Issue::AdditionalPropertiesNotFalse);
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::encoding::push_pointer_segment;
+use crate::transform::{
+    is_object_schema, RecursivePathTransform, SchemaLocation, StrictStructuredOutput, Transform,
+};
+use crate::Schema;
+use core::fmt;
+use serde_json::Value;
+
+/// Checks whether the given schema can be used in the "strict" structured output mode of LLM
+/// APIs, returning any constructs that are not supported.
+///
+/// This allows the keywords allowed by [`StrictStructuredOutput::default()`]. To check against a
+/// different set of keywords, use [`check_with`].
+///
+/// See the [module documentation](crate::structured_output) for more information.
+#[must_use]
+pub fn check(schema: &Schema) -> Vec<Finding> {
+    check_with(schema, &StrictStructuredOutput::default())
+}
+
+/// Checks whether the given schema can be used in the "strict" structured output mode of LLM
+/// APIs, allowing only the keywords in the given transform's
+/// [`allowed_keywords`](StrictStructuredOutput::allowed_keywords).
+///
+/// See the [module documentation](crate::structured_output) for more information.
+#[must_use]
+pub fn check_with(schema: &Schema, settings: &StrictStructuredOutput) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let mut add_finding = |pointer: &str, issue: Issue| {
+        findings.push(Finding {
+            path: pointer.to_owned(),
+            issue,
+        });
+    };
+
+    let mut visit = |schema: &mut Schema, location: SchemaLocation| {
+        let Some(obj) = schema.as_object() else {
+            if location.is_root() {
+                add_finding(location.pointer, Issue::RootNotObject);
+            } else if location.keyword != Some("additionalProperties") {
+                add_finding(
+                    location.pointer,
+                    Issue::BooleanSchema {
+                        value: schema.as_bool() == Some(true),
+                    },
+                );
+            }
+            return;
+        };
+
+        if location.is_root()
+            && (obj.get("type") != Some(&Value::from("object")) || obj.contains_key("anyOf"))
+        {
+            add_finding(location.pointer, Issue::RootNotObject);
+        }
+
+        for keyword in obj.keys() {
+            if !settings.allowed_keywords.contains(keyword.as_str()) {
+                let mut path = location.pointer.to_owned();
+                push_pointer_segment(&mut path, keyword);
+                add_finding(
+                    &path,
+                    Issue::UnsupportedKeyword {
+                        keyword: keyword.clone(),
+                    },
+                );
+            }
+        }
+
+        if obj.contains_key("$ref") && obj.len() > 1 {
+            add_finding(location.pointer, Issue::RefWithSiblings);
+        }
+
+        if !["type", "$ref", "anyOf", "enum", "const"]
+            .iter()
+            .any(|k| obj.contains_key(*k))
+        {
+            add_finding(location.pointer, Issue::MissingType);
+        }
+
+        if is_object_schema(obj) {
+            if obj.get("additionalProperties") != Some(&Value::Bool(false)) {
+                add_finding(location.pointer, Issue::AdditionalPropertiesNotFalse);
+            }
+
+            if let Some(Value::Object(properties)) = obj.get("properties") {
+                let required = obj.get("required").and_then(Value::as_array);
+                for property in properties.keys() {
+                    if !required.is_some_and(|r| r.iter().any(|r| r == property)) {
+                        add_finding(
+                            location.pointer,
+                            Issue::PropertyNotRequired {
+                                property: property.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    };
+
+    RecursivePathTransform(&mut visit).transform(&mut schema.clone());
+
+    findings
+}
+
+/// A construct in a schema that is not supported by the "strict" structured output mode of LLM
+/// APIs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Finding {
+    /// A JSON Pointer to the unsupported part of the schema.
+    ///
+    /// For [`Issue::UnsupportedKeyword`], this points to the keyword itself. For other issues,
+    /// this points to the schema containing the issue, which is an empty string for the root
+    /// schema.
+    pub path: String,
+    /// The unsupported construct.
+    pub issue: Issue,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.issue)?;
+        if !self.path.is_empty() {
+            write!(f, " (at \"{}\")", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// A kind of construct that is not supported by the "strict" structured output mode of LLM APIs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+#[non_exhaustive]
+pub enum Issue {
+    /// The root schema is not an object schema, or it uses `anyOf`.
+    RootNotObject,
+    /// The schema uses a keyword that is not supported.
+    UnsupportedKeyword { keyword: String },
+    /// A boolean schema (`true` or `false`) is used other than for `additionalProperties`.
+    BooleanSchema { value: bool },
+    /// The schema does not have a `type`, `$ref`, `anyOf`, `enum` or `const`, so does not restrict
+    /// the type of values, e.g. the schema for `serde_json::Value`.
+    MissingType,
+    /// An object schema does not set `additionalProperties` to `false`, e.g. the schema for a map.
+    AdditionalPropertiesNotFalse,
+    /// A property of an object schema is not listed in `required`.
+    PropertyNotRequired { property: String },
+    /// A `$ref` has sibling keywords.
+    RefWithSiblings,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::RootNotObject => f.write_str("root schema must be an object schema"),
+            Issue::UnsupportedKeyword { keyword } => {
+                write!(f, "keyword \"{keyword}\" is not supported")
+            }
+            Issue::BooleanSchema { value } => {
+                write!(f, "boolean schema `{value}` is not supported")
+            }
+            Issue::MissingType => f.write_str("schema does not specify a type"),
+            Issue::AdditionalPropertiesNotFalse => {
+                f.write_str("\"additionalProperties\" must be `false`")
+            }
+            Issue::PropertyNotRequired { property } => {
+                write!(f, "property \"{property}\" must be required")
+            }
+            Issue::RefWithSiblings => f.write_str("\"$ref\" must not have sibling keywords"),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::_alloc_prelude::*;
+use crate::Schema;
+
+/// Schemas describing a function's parameters and return type, e.g. for use with RPC or LLM
+/// tool-calling APIs.
+///
+/// This is generated by functions created by the [`#[schemars::tool]`](macro@crate::tool)
+/// attribute macro.
+///
+/// # Example
+/// ```
+/// use schemars::{generate::SchemaSettings, json_schema, JsonSchema};
+///
+/// #[derive(JsonSchema)]
+/// struct Forecast {
+///     temperatures: Vec<f64>,
+/// }
+///
+/// /// Gets the weather forecast for a city.
+/// #[schemars::tool]
+/// fn get_forecast(
+///     /// The name of the city
+///     city: String,
+///     #[schemars(range(min = 1, max = 14))] days: Option<u8>,
+/// ) -> Forecast {
+///     # let _ = (city, days);
+///     # unimplemented!()
+///     // ...
+/// }
+///
+/// let tool = get_forecast_tool_schema(&SchemaSettings::default());
+///
+/// assert_eq!(tool.name, "get_forecast");
+/// assert_eq!(
+///     tool.description.as_deref(),
+///     Some("Gets the weather forecast for a city.")
+/// );
+/// assert_eq!(
+///     tool.parameters,
+///     json_schema!({
+///         "$schema": "https://json-schema.org/draft/2020-12/schema",
+///         "title": "get_forecast",
+///         "description": "Gets the weather forecast for a city.",
+///         "type": "object",
+///         "properties": {
+///             "city": {
+///                 "description": "The name of the city",
+///                 "type": "string"
+///             },
+///             "days": {
+///                 "type": ["integer", "null"],
+///                 "format": "uint8",
+///                 "minimum": 1,
+///                 "maximum": 14
+///             }
+///         },
+///         "required": ["city"]
+///     })
+/// );
+/// assert_eq!(tool.returns.get("title"), Some(&"Forecast".into()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ToolSchema {
+    /// The name of the function.
+    pub name: String,
+    /// The description of the function, taken from its doc comment.
+    ///
+    /// This is the same as the `description` of the [`parameters`](Self::parameters) schema.
+    pub description: Option<String>,
+    /// A root schema for an object whose properties are the function's parameters.
+    ///
+    /// This is generated using the [`Deserialize`](crate::generate::Contract::Deserialize)
+    /// contract, since it describes values that are passed in to the function.
+    pub parameters: Schema,
+    /// A root schema for the function's return type.
+    ///
+    /// This is generated using the [`Serialize`](crate::generate::Contract::Serialize) contract,
+    /// since it describes values that are produced by the function.
+    pub returns: Schema,
+}
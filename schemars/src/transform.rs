@@ -8,6 +8,14 @@ To make a transform recursive (i.e. apply it to subschemas), you have two option
 1. call the [`transform_subschemas`] function within the transform function
 2. wrap the `Transform` in a [`RecursiveTransform`]
 
+# Path-aware Transforms
+
+If a transform needs to know where each subschema is located (e.g. to only modify schemas under a
+particular property, or to report the JSON Pointer of a schema), implement [`PathTransform`]
+instead. This receives a [`SchemaLocation`] alongside each schema, and can be made recursive either
+by calling [`transform_subschemas_with_path`] or by wrapping it in a [`RecursivePathTransform`],
+which also implements `Transform`.
+
 # Examples
 
 To add a custom property to all object schemas:
@@ -107,9 +115,11 @@ assert_eq!(
 
 */
 use crate::_alloc_prelude::*;
+use crate::encoding::push_pointer_segment;
 use crate::{consts::meta_schemas, Schema};
 use alloc::borrow::Cow;
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::cmp::Reverse;
 use serde_json::{json, Map, Value};
 
 /// Trait used to modify a constructed schema and optionally its subschemas.
@@ -142,6 +152,21 @@ where
 
 /// Applies the given [`Transform`] to all direct subschemas of the [`Schema`].
 pub fn transform_subschemas<T: Transform + ?Sized>(t: &mut T, schema: &mut Schema) {
+    for_each_subschema(schema, |_, _, subschema| t.transform(subschema));
+}
+
+/// The position of a subschema within its parent, relative to the keyword it was found under.
+#[derive(Clone, Copy)]
+enum ChildKey<'a> {
+    /// The keyword's value is the subschema itself, e.g. `not` or `additionalProperties`.
+    None,
+    /// The subschema is an element of an array, e.g. under `anyOf` or `prefixItems`.
+    Index(usize),
+    /// The subschema is a value of an object, e.g. under `properties` or `$defs`.
+    Name(&'a str),
+}
+
+fn for_each_subschema(schema: &mut Schema, mut f: impl FnMut(&str, ChildKey, &mut Schema)) {
     for (key, value) in schema.as_object_mut().into_iter().flatten() {
         // This is intentionally written to work with multiple JSON Schema versions, so that
         // users can add their own transforms on the end of e.g. `SchemaSettings::draft07()` and
@@ -158,14 +183,14 @@ pub fn transform_subschemas<T: Transform + ?Sized>(t: &mut T, schema: &mut Schem
             | "propertyNames"
             | "additionalItems" => {
                 if let Ok(subschema) = value.try_into() {
-                    t.transform(subschema);
+                    f(key, ChildKey::None, subschema);
                 }
             }
             "allOf" | "anyOf" | "oneOf" | "prefixItems" => {
                 if let Some(array) = value.as_array_mut() {
-                    for value in array {
+                    for (index, value) in array.iter_mut().enumerate() {
                         if let Ok(subschema) = value.try_into() {
-                            t.transform(subschema);
+                            f(key, ChildKey::Index(index), subschema);
                         }
                     }
                 }
@@ -174,20 +199,20 @@ pub fn transform_subschemas<T: Transform + ?Sized>(t: &mut T, schema: &mut Schem
             // comment)
             "items" => {
                 if let Some(array) = value.as_array_mut() {
-                    for value in array {
+                    for (index, value) in array.iter_mut().enumerate() {
                         if let Ok(subschema) = value.try_into() {
-                            t.transform(subschema);
+                            f(key, ChildKey::Index(index), subschema);
                         }
                     }
                 } else if let Ok(subschema) = value.try_into() {
-                    t.transform(subschema);
+                    f(key, ChildKey::None, subschema);
                 }
             }
             "properties" | "patternProperties" | "$defs" | "definitions" => {
                 if let Some(obj) = value.as_object_mut() {
-                    for value in obj.values_mut() {
+                    for (name, value) in obj {
                         if let Ok(subschema) = value.try_into() {
-                            t.transform(subschema);
+                            f(key, ChildKey::Name(name), subschema);
                         }
                     }
                 }
@@ -197,6 +222,148 @@ pub fn transform_subschemas<T: Transform + ?Sized>(t: &mut T, schema: &mut Schem
     }
 }
 
+/// The location of a schema being visited by a [`PathTransform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SchemaLocation<'a> {
+    /// The JSON Pointer of the schema, relative to the schema that the transform was originally
+    /// applied to, e.g. `"/properties/metadata/items"`.
+    ///
+    /// This is an empty string for the top-level schema.
+    pub pointer: &'a str,
+    /// The keyword in the parent schema that this schema was found under, e.g. `"properties"`,
+    /// `"items"` or `"anyOf"`.
+    ///
+    /// This is `None` for the top-level schema.
+    pub keyword: Option<&'a str>,
+}
+
+impl SchemaLocation<'_> {
+    /// The location of the top-level schema.
+    pub const ROOT: SchemaLocation<'static> = SchemaLocation {
+        pointer: "",
+        keyword: None,
+    };
+
+    /// Returns `true` if this is the location of the top-level schema.
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.pointer.is_empty()
+    }
+}
+
+/// Trait used to modify a constructed schema and optionally its subschemas, with knowledge of
+/// where each schema is located.
+///
+/// This is similar to [`Transform`], but the transform is also given the [`SchemaLocation`] of
+/// each schema, so it can e.g. only apply to schemas under a particular property, or report the
+/// JSON Pointer of schemas it finds.
+///
+/// A `PathTransform` can be used wherever a `Transform` is expected by wrapping it in a
+/// [`RecursivePathTransform`].
+///
+/// This trait is automatically implemented for functions of the form
+/// `fn(&mut Schema, SchemaLocation) -> ()`.
+///
+/// # Example
+/// ```
+/// # use schemars::{Schema, json_schema};
+/// use schemars::transform::{PathTransform, SchemaLocation, transform_subschemas_with_path};
+///
+/// struct DescribeMetadata;
+///
+/// impl PathTransform for DescribeMetadata {
+///     fn transform_at(&mut self, schema: &mut Schema, location: SchemaLocation) {
+///         if location.pointer.starts_with("/properties/metadata/") {
+///             schema.insert("description".to_owned(), location.pointer.into());
+///         }
+///
+///         transform_subschemas_with_path(self, schema, location.pointer);
+///     }
+/// }
+///
+/// let mut schema = json_schema!({
+///     "type": "object",
+///     "properties": {
+///         "id": { "type": "string" },
+///         "metadata": {
+///             "properties": {
+///                 "tags": { "type": "array" }
+///             }
+///         }
+///     }
+/// });
+///
+/// DescribeMetadata.transform_at(&mut schema, SchemaLocation::ROOT);
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "object",
+///         "properties": {
+///             "id": { "type": "string" },
+///             "metadata": {
+///                 "properties": {
+///                     "tags": {
+///                         "type": "array",
+///                         "description": "/properties/metadata/properties/tags"
+///                     }
+///                 }
+///             }
+///         }
+///     })
+/// );
+/// ```
+pub trait PathTransform {
+    /// Applies the transform to the given [`Schema`], which is at the given [`SchemaLocation`].
+    ///
+    /// When overriding this method, you may want to call the [`transform_subschemas_with_path`]
+    /// function to also transform any subschemas.
+    fn transform_at(&mut self, schema: &mut Schema, location: SchemaLocation);
+}
+
+impl<F> PathTransform for F
+where
+    F: FnMut(&mut Schema, SchemaLocation),
+{
+    fn transform_at(&mut self, schema: &mut Schema, location: SchemaLocation) {
+        self(schema, location);
+    }
+}
+
+/// Applies the given [`PathTransform`] to all direct subschemas of the [`Schema`].
+///
+/// `pointer` is the JSON Pointer of `schema` itself, which is used to build the
+/// [`SchemaLocation`] of each subschema. This is typically the `pointer` of the location passed
+/// to [`PathTransform::transform_at`].
+pub fn transform_subschemas_with_path<T: PathTransform + ?Sized>(
+    t: &mut T,
+    schema: &mut Schema,
+    pointer: &str,
+) {
+    use core::fmt::Write as _;
+
+    let mut child_pointer = String::new();
+    for_each_subschema(schema, |keyword, child_key, subschema| {
+        child_pointer.clear();
+        child_pointer.push_str(pointer);
+        push_pointer_segment(&mut child_pointer, keyword);
+        match child_key {
+            ChildKey::None => {}
+            ChildKey::Index(index) => {
+                _ = write!(child_pointer, "/{index}");
+            }
+            ChildKey::Name(name) => push_pointer_segment(&mut child_pointer, name),
+        }
+
+        let location = SchemaLocation {
+            pointer: &child_pointer,
+            keyword: Some(keyword),
+        };
+        t.transform_at(subschema, location);
+    });
+}
+
 // Similar to `transform_subschemas`, but only transforms subschemas that apply to the top-level
 // object, e.g. "oneOf" but not "properties".
 pub(crate) fn transform_immediate_subschemas<T: Transform + ?Sized>(
@@ -271,6 +438,70 @@ where
     }
 }
 
+/// A helper struct that can wrap a non-recursive [`PathTransform`] into a recursive [`Transform`].
+///
+/// Its implementation of `Transform` will first apply the inner transform to the "parent" schema
+/// at [`SchemaLocation::ROOT`], and then its subschemas (and their subschemas, and so on), each
+/// with their own location.
+///
+/// # Example
+/// ```
+/// # use schemars::{Schema, json_schema};
+/// use schemars::transform::{Transform, RecursivePathTransform, SchemaLocation};
+///
+/// let mut transform = RecursivePathTransform(|schema: &mut Schema, location: SchemaLocation| {
+///     if location.keyword == Some("items") {
+///         schema.insert("x-pointer".to_owned(), location.pointer.into());
+///     }
+/// });
+///
+/// let mut schema = json_schema!({
+///     "type": "array",
+///     "items": {
+///         "type": "array",
+///         "items": {}
+///     }
+/// });
+///
+/// transform.transform(&mut schema);
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "array",
+///         "items": {
+///             "type": "array",
+///             "items": {
+///                 "x-pointer": "/items/items"
+///             },
+///             "x-pointer": "/items"
+///         }
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[allow(clippy::exhaustive_structs)]
+pub struct RecursivePathTransform<T>(pub T);
+
+impl<T> PathTransform for RecursivePathTransform<T>
+where
+    T: PathTransform,
+{
+    fn transform_at(&mut self, schema: &mut Schema, location: SchemaLocation) {
+        self.0.transform_at(schema, location);
+        transform_subschemas_with_path(self, schema, location.pointer);
+    }
+}
+
+impl<T> Transform for RecursivePathTransform<T>
+where
+    T: PathTransform,
+{
+    fn transform(&mut self, schema: &mut Schema) {
+        self.transform_at(schema, SchemaLocation::ROOT);
+    }
+}
+
 /// Replaces boolean JSON Schemas with equivalent object schemas.
 ///
 /// This also applies to subschemas.
@@ -405,6 +636,122 @@ impl Transform for ReplacePrefixItems {
     }
 }
 
+/// Replaces numeric `exclusiveMinimum`/`exclusiveMaximum` schema properties with the boolean form,
+/// which modifies the meaning of the `minimum`/`maximum` properties.
+///
+/// This also applies to subschemas.
+///
+/// Where a schema has both `minimum` and a numeric `exclusiveMinimum` (or `maximum` and
+/// `exclusiveMaximum`), only the more restrictive of the two bounds is kept.
+///
+/// This is useful for versions of JSON Schema (e.g. Draft 4) that only support boolean
+/// `exclusiveMinimum`/`exclusiveMaximum` properties.
+///
+/// # Example
+/// ```
+/// use schemars::json_schema;
+/// use schemars::transform::{ReplaceExclusiveBounds, Transform};
+///
+/// let mut schema = json_schema!({
+///     "type": "integer",
+///     "minimum": 0,
+///     "exclusiveMinimum": 5,
+///     "maximum": 100,
+///     "exclusiveMaximum": 200
+/// });
+///
+/// ReplaceExclusiveBounds::default().transform(&mut schema);
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "integer",
+///         "minimum": 5,
+///         "exclusiveMinimum": true,
+///         "maximum": 100
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ReplaceExclusiveBounds;
+
+impl Transform for ReplaceExclusiveBounds {
+    fn transform(&mut self, schema: &mut Schema) {
+        transform_subschemas(self, schema);
+
+        if let Some(obj) = schema.as_object_mut() {
+            replace_exclusive_bound(obj, "minimum", "exclusiveMinimum", |ex, inc| ex >= inc);
+            replace_exclusive_bound(obj, "maximum", "exclusiveMaximum", |ex, inc| ex <= inc);
+        }
+    }
+}
+
+fn replace_exclusive_bound(
+    obj: &mut Map<String, Value>,
+    inclusive_key: &str,
+    exclusive_key: &str,
+    exclusive_is_stricter: fn(f64, f64) -> bool,
+) {
+    let Some(exclusive) = obj.get(exclusive_key).filter(|v| v.is_number()).cloned() else {
+        return;
+    };
+
+    let use_exclusive = match obj.get(inclusive_key).and_then(Value::as_f64) {
+        Some(inclusive) => exclusive
+            .as_f64()
+            .is_some_and(|ex| exclusive_is_stricter(ex, inclusive)),
+        None => true,
+    };
+
+    if use_exclusive {
+        obj.insert(inclusive_key.to_owned(), exclusive);
+        obj.insert(exclusive_key.to_owned(), true.into());
+    } else {
+        obj.remove(exclusive_key);
+    }
+}
+
+/// Renames the `$defs` schema property to `definitions`, and updates any `$ref` properties that
+/// point to a subschema within `$defs`.
+///
+/// This also applies to subschemas.
+///
+/// This is useful for versions of JSON Schema (e.g. Draft 4 and Draft 6) that do not support the
+/// `$defs` property.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ReplaceDefs;
+
+impl Transform for ReplaceDefs {
+    fn transform(&mut self, schema: &mut Schema) {
+        transform_subschemas(self, schema);
+
+        let Some(obj) = schema.as_object_mut() else {
+            return;
+        };
+
+        if let Some(Value::Object(defs)) = obj.remove("$defs") {
+            if let Some(Value::Object(definitions)) = obj.get_mut("definitions") {
+                for (name, def) in defs {
+                    definitions.entry(name).or_insert(def);
+                }
+            } else {
+                obj.insert("definitions".to_owned(), defs.into());
+            }
+        }
+
+        if let Some(Value::String(reference)) = obj.get_mut("$ref") {
+            for prefix in ["#/$defs/", "#/%24defs/"] {
+                if let Some(rest) = reference.strip_prefix(prefix) {
+                    *reference = format!("#/definitions/{rest}");
+                    break;
+                }
+            }
+        }
+    }
+}
+
 /// Adds a `"nullable": true` property to schemas that allow `null` types.
 ///
 /// This also applies to subschemas.
@@ -639,24 +986,26 @@ static DEFINED_FORMATS: &[&str] = &[
     // `duration` and `uuid` are defined only in draft 2019-09+
     "duration",
     "uuid",
-    // The rest are also defined in draft-07:
-    "date-time",
+    // These are also defined in draft-07:
     "date",
     "time",
-    "email",
     "idn-email",
-    "hostname",
     "idn-hostname",
-    "ipv4",
-    "ipv6",
-    "uri",
-    "uri-reference",
     "iri",
     "iri-reference",
-    "uri-template",
-    "json-pointer",
     "relative-json-pointer",
     "regex",
+    // These are also defined in draft-06:
+    "uri-reference",
+    "uri-template",
+    "json-pointer",
+    // The rest are also defined in draft-04:
+    "date-time",
+    "email",
+    "hostname",
+    "ipv4",
+    "ipv6",
+    "uri",
 ];
 
 struct RestrictFormatsImpl<'a> {
@@ -679,8 +1028,12 @@ impl Transform for RestrictFormatsImpl<'_> {
                 .and_then(Value::as_str)
                 .unwrap_or_default()
             {
+                meta_schemas::DRAFT04 => Some(&DEFINED_FORMATS[13..]),
+                meta_schemas::DRAFT06 => Some(&DEFINED_FORMATS[10..]),
                 meta_schemas::DRAFT07 => Some(&DEFINED_FORMATS[2..]),
-                meta_schemas::DRAFT2019_09 | meta_schemas::DRAFT2020_12 => Some(DEFINED_FORMATS),
+                meta_schemas::DRAFT2019_09
+                | meta_schemas::DRAFT2020_12
+                | meta_schemas::OPENAPI3_1 => Some(DEFINED_FORMATS),
                 _ => {
                     // we can't handle an unrecognised meta-schema
                     return;
@@ -704,54 +1057,2532 @@ impl Transform for RestrictFormatsImpl<'_> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use pretty_assertions::assert_eq;
+// The locations of definitions within the root schemas generated by schemars, in order of
+// preference.
+const DEFINITIONS_PATHS: [&str; 3] = ["/$defs", "/definitions", "/components/schemas"];
+
+// Keywords whose values are taken from a schema containing a `$ref` rather than from the
+// referenced schema when the `$ref` is inlined.
+const ANNOTATION_KEYWORDS: [&str; 9] = [
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "$comment",
+    "nullable",
+];
 
-    #[test]
-    fn restrict_formats() {
-        let mut schema = json_schema!({
-            "$schema": meta_schemas::DRAFT2020_12,
-            "anyOf": [
-                { "format": "uuid" },
-                { "$schema": meta_schemas::DRAFT07, "format": "uuid" },
-                { "$schema": "http://unknown", "format": "uuid" },
-                { "format": "date" },
-                { "$schema": meta_schemas::DRAFT07, "format": "date" },
-                { "$schema": "http://unknown", "format": "date" },
-                { "format": "custom1" },
-                { "$schema": meta_schemas::DRAFT07, "format": "custom1" },
-                { "$schema": "http://unknown", "format": "custom1" },
-                { "format": "custom2" },
-                { "$schema": meta_schemas::DRAFT07, "format": "custom2" },
-                { "$schema": "http://unknown", "format": "custom2" },
-            ]
-        });
+/// Replaces every `$ref` that points to a subschema within the root schema with the subschema
+/// that it points to, and removes any definitions that are no longer referenced.
+///
+/// This should be applied to a root schema, e.g. one returned by
+/// [`schema_for!`](crate::schema_for). `$ref`s that do not point within the root schema are left
+/// unchanged.
+///
+/// Recursive schemas cannot be fully inlined, so each cycle is cut once a `$ref` has been inlined
+/// [`max_recursion`](Self::max_recursion) times within the subschema that it points to. By
+/// default, the `$ref` is left in place at these points, along with the definition that it
+/// points to.
+///
+/// This is also available as [`Schema::dereference`].
+///
+/// # Example
+/// ```
+/// use schemars::json_schema;
+/// use schemars::transform::{Dereference, Transform};
+///
+/// let mut schema = json_schema!({
+///     "type": "object",
+///     "properties": {
+///         "a": { "$ref": "#/$defs/Node" }
+///     },
+///     "$defs": {
+///         "Node": {
+///             "type": "object",
+///             "properties": {
+///                 "next": { "$ref": "#/$defs/Node" }
+///             }
+///         }
+///     }
+/// });
+///
+/// let mut transform = Dereference::default();
+/// transform.keep_cycle_refs = false;
+/// transform.transform(&mut schema);
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "object",
+///         "properties": {
+///             "a": {
+///                 "type": "object",
+///                 "properties": {
+///                     "next": true
+///                 }
+///             }
+///         }
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Dereference {
+    /// How many times a `$ref` may be inlined within the subschema that it points to before the
+    /// cycle is cut.
+    ///
+    /// By default, this is `0`, so recursive `$ref`s are never inlined within themselves.
+    pub max_recursion: usize,
+    /// Whether to leave a `$ref` in place where a cycle is cut. If this is `false`, the `$ref` is
+    /// removed instead, so the recursive subschema will allow any value.
+    ///
+    /// By default, this is `true`.
+    pub keep_cycle_refs: bool,
+}
 
-        let mut transform = RestrictFormats::default();
-        transform.allowed_formats.insert("custom1".into());
-        transform.transform(&mut schema);
+impl Default for Dereference {
+    fn default() -> Self {
+        Self {
+            max_recursion: 0,
+            keep_cycle_refs: true,
+        }
+    }
+}
 
-        assert_eq!(
-            schema,
-            json_schema!({
-                "$schema": meta_schemas::DRAFT2020_12,
-                "anyOf": [
-                    { "format": "uuid" },
-                    { "$schema": meta_schemas::DRAFT07 },
-                    { "$schema": "http://unknown", "format": "uuid" },
-                    { "format": "date" },
-                    { "$schema": meta_schemas::DRAFT07, "format": "date" },
-                    { "$schema": "http://unknown", "format": "date" },
-                    { "format": "custom1" },
-                    { "$schema": meta_schemas::DRAFT07, "format": "custom1" },
-                    { "$schema": "http://unknown", "format": "custom1" },
-                    { },
-                    { "$schema": meta_schemas::DRAFT07 },
+impl Transform for Dereference {
+    fn transform(&mut self, schema: &mut Schema) {
+        let root = schema.clone();
+        let definitions = take_definitions(schema);
+
+        let mut inliner = Inliner {
+            root: &root,
+            max_recursion: self.max_recursion,
+            keep_cycle_refs: self.keep_cycle_refs,
+            stack: Vec::new(),
+        };
+        inliner.transform(schema);
+
+        let referenced = referenced_definitions(schema, &definitions);
+        for (path, mut defs) in definitions {
+            defs.retain(|name, _| referenced.contains(&(path, name.clone())));
+            put_definitions(schema, path, defs);
+        }
+    }
+}
+
+struct Inliner<'a> {
+    root: &'a Schema,
+    max_recursion: usize,
+    keep_cycle_refs: bool,
+    // The (decoded) pointers of the `$ref`s currently being inlined
+    stack: Vec<String>,
+}
+
+impl Transform for Inliner<'_> {
+    fn transform(&mut self, schema: &mut Schema) {
+        // Subschemas must be handled before the `$ref`, so that the inlined schema is not visited
+        // again once it has been removed from the stack.
+        transform_subschemas(self, schema);
+
+        let Some(pointer) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix('#'))
+            .and_then(crate::encoding::percent_decode)
+            .map(Cow::into_owned)
+        else {
+            return;
+        };
+        let Some(target) = self.root.as_value().pointer(&pointer) else {
+            return;
+        };
+        let Ok(mut target) = Schema::try_from(target.clone()) else {
+            return;
+        };
+
+        let recursion = self.stack.iter().filter(|p| **p == pointer).count();
+        if recursion > self.max_recursion {
+            if !self.keep_cycle_refs {
+                schema.remove("$ref");
+                if schema.as_object().is_some_and(Map::is_empty) {
+                    *schema = true.into();
+                }
+            }
+            return;
+        }
+
+        if pointer.is_empty() {
+            // The root schema's definitions (and other top-level keywords) don't belong inline
+            take_definitions(&mut target);
+            target.remove("$schema");
+            target.remove("$id");
+        }
+
+        self.stack.push(pointer);
+        self.transform(&mut target);
+        self.stack.pop();
+
+        schema.remove("$ref");
+        merge_inlined(schema, target);
+    }
+}
+
+// Combines the remaining keywords of a schema that contained a `$ref` with the schema that it
+// pointed to.
+fn merge_inlined(schema: &mut Schema, target: Schema) {
+    if schema.as_object().is_some_and(Map::is_empty) {
+        *schema = target;
+        return;
+    }
+
+    let Ok(obj) = schema.try_as_object_mut() else {
+        return;
+    };
+    match target.try_to_object() {
+        Ok(mut target) => {
+            for keyword in ANNOTATION_KEYWORDS {
+                if obj.contains_key(keyword) {
+                    target.remove(keyword);
+                }
+            }
+
+            if target.keys().any(|k| obj.contains_key(k)) {
+                let all_of = obj
+                    .entry("allOf")
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if let Some(all_of) = all_of.as_array_mut() {
+                    all_of.push(target.into());
+                }
+            } else {
+                obj.extend(target);
+            }
+        }
+        Err(true) => {}
+        Err(false) => *schema = false.into(),
+    }
+}
+
+/// Moves subschemas that occur multiple times within the root schema into the definitions, and
+/// replaces each occurrence with a `$ref`. This is the reverse of [`Dereference`].
+///
+/// This should be applied to a root schema, e.g. one returned by
+/// [`schema_for!`](crate::schema_for). New definitions are added to the root schema's existing
+/// definitions (`$defs`, `definitions` or `components/schemas`), or to `$defs` if there are none
+/// (or `definitions` for JSON Schema versions before 2019-09).
+///
+/// Only subschemas that describe objects or enumerations (i.e. that contain `properties`,
+/// `enum`, `oneOf`, `anyOf` or `allOf`) are extracted, unless
+/// [`extract_formats`](ExtractDefinitions::extract_formats) is enabled. Each definition is named
+/// after its `title` if it has one, otherwise after its `format`, otherwise after the property that
+/// it was first found in, and a number is appended to the name if it is already in use. If an identical schema is already defined, it is
+/// reused rather than being defined again, even if it occurs fewer than `min_occurrences` times.
+///
+/// This is also available as [`Schema::extract_definitions`].
+///
+/// # Example
+/// ```
+/// use schemars::json_schema;
+/// use schemars::transform::{ExtractDefinitions, Transform};
+///
+/// let mut schema = json_schema!({
+///     "type": "object",
+///     "properties": {
+///         "billing_address": {
+///             "type": "object",
+///             "properties": { "street": { "type": "string" } }
+///         },
+///         "shipping_address": {
+///             "type": "object",
+///             "properties": { "street": { "type": "string" } }
+///         }
+///     }
+/// });
+///
+/// ExtractDefinitions::default().transform(&mut schema);
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "object",
+///         "properties": {
+///             "billing_address": { "$ref": "#/$defs/BillingAddress" },
+///             "shipping_address": { "$ref": "#/$defs/BillingAddress" }
+///         },
+///         "$defs": {
+///             "BillingAddress": {
+///                 "type": "object",
+///                 "properties": { "street": { "type": "string" } }
+///             }
+///         }
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ExtractDefinitions {
+    /// The minimum number of times that an identical subschema must occur for it to be extracted.
+    ///
+    /// By default, this is `2`.
+    pub min_occurrences: usize,
+    /// Whether to also extract subschemas that contain a `format`, such as
+    /// `{"type": "string", "format": "uuid"}`.
+    ///
+    /// By default, this is `false`.
+    pub extract_formats: bool,
+}
+
+impl Default for ExtractDefinitions {
+    fn default() -> Self {
+        Self {
+            min_occurrences: 2,
+            extract_formats: false,
+        }
+    }
+}
+
+impl Transform for ExtractDefinitions {
+    fn transform(&mut self, schema: &mut Schema) {
+        let mut definitions = take_definitions(schema);
+        let path = match definitions.first() {
+            Some((path, _)) => *path,
+            None => match schema.get("$schema").and_then(Value::as_str) {
+                Some(meta_schemas::DRAFT04 | meta_schemas::DRAFT06 | meta_schemas::DRAFT07) => {
+                    "/definitions"
+                }
+                _ => "/$defs",
+            },
+        };
+        if !definitions.iter().any(|(p, _)| *p == path) {
+            definitions.push((path, Map::new()));
+        }
+
+        // Identical copies of existing definitions are always replaced, however often they occur.
+        let references = definition_keys(&definitions)
+            .into_iter()
+            .map(|(key, (def_path, name))| (key, definition_ref(def_path, &name)))
+            .collect();
+        replace_subschemas(schema, &mut definitions, references, self.extract_formats);
+
+        loop {
+            let mut counter = CountSubschemas::new(self.extract_formats);
+            counter.count_subschemas(schema);
+            for (_, defs) in &mut definitions {
+                for def in defs.values_mut() {
+                    if let Ok(def) = def.try_into() {
+                        counter.count_subschemas(def);
+                    }
+                }
+            }
+
+            let existing = definition_keys(&definitions);
+
+            // Extract the largest subschemas first, so that they are not split up by extracting
+            // any smaller subschemas that they contain.
+            let mut candidates: Vec<_> = counter
+                .candidates
+                .into_iter()
+                .filter(|(_, c)| c.count >= self.min_occurrences.max(1))
+                .collect();
+            candidates.sort_by_key(|(key, c)| (Reverse(key.len()), Reverse(c.index)));
+
+            // Subschemas within an extracted subschema will have fewer occurrences once it has been
+            // replaced, so they're left to be recounted in the next pass.
+            let mut nested = BTreeSet::new();
+            let mut references = BTreeMap::new();
+            for (key, candidate) in candidates {
+                if nested.contains(&key) {
+                    continue;
+                }
+
+                let mut value = candidate.value;
+                if let Ok(value) = (&mut value).try_into() {
+                    let mut nested_counter = CountSubschemas::new(self.extract_formats);
+                    nested_counter.count_subschemas(value);
+                    nested.extend(nested_counter.candidates.into_keys());
+                }
+
+                let (def_path, name) = existing.get(&key).cloned().unwrap_or_else(|| {
+                    let defs = definitions
+                        .iter_mut()
+                        .find_map(|(p, defs)| (*p == path).then_some(defs))
+                        .expect("definitions for path were inserted above");
+                    let base_name = candidate.name;
+                    let mut name = base_name.clone();
+                    for i in 2.. {
+                        if !defs.contains_key(&name) {
+                            break;
+                        }
+                        name = format!("{base_name}{i}");
+                    }
+                    defs.insert(name.clone(), value);
+                    (path, name)
+                });
+
+                references.insert(key, definition_ref(def_path, &name));
+            }
+
+            if references.is_empty() {
+                break;
+            }
+
+            replace_subschemas(schema, &mut definitions, references, self.extract_formats);
+        }
+
+        for (path, defs) in definitions {
+            put_definitions(schema, path, defs);
+        }
+    }
+}
+
+struct Candidate {
+    value: Value,
+    count: usize,
+    name: String,
+    // The order in which the subschema was first found
+    index: usize,
+}
+
+struct CountSubschemas {
+    extract_formats: bool,
+    // Keyed by the canonical form of each subschema
+    candidates: BTreeMap<String, Candidate>,
+    // Names for subschemas that are about to be visited, keyed by their address
+    property_names: Vec<(*const Value, String)>,
+}
+
+impl Transform for CountSubschemas {
+    fn transform(&mut self, schema: &mut Schema) {
+        if is_extractable(schema.as_value(), self.extract_formats) {
+            let address: *const Value = schema.as_value();
+            let index = self.candidates.len();
+            self.candidates
+                .entry(canonical_key(schema.as_value()))
+                .and_modify(|candidate| candidate.count += 1)
+                .or_insert_with(|| {
+                    let name = schema
+                        .get("title")
+                        .and_then(Value::as_str)
+                        .map(ToOwned::to_owned)
+                        .or_else(|| {
+                            schema
+                                .get("format")
+                                .and_then(Value::as_str)
+                                .map(pascal_case)
+                        })
+                        .or_else(|| {
+                            self.property_names
+                                .iter()
+                                .find(|(a, _)| *a == address)
+                                .map(|(_, name)| pascal_case(name))
+                        })
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or_else(|| "Schema".to_owned());
+                    Candidate {
+                        value: schema.as_value().clone(),
+                        count: 1,
+                        name,
+                        index,
+                    }
+                });
+        }
+
+        self.count_subschemas(schema);
+    }
+}
+
+impl CountSubschemas {
+    fn new(extract_formats: bool) -> Self {
+        Self {
+            extract_formats,
+            candidates: BTreeMap::new(),
+            property_names: Vec::new(),
+        }
+    }
+
+    fn count_subschemas(&mut self, schema: &mut Schema) {
+        if let Some(Value::Object(properties)) = schema.get("properties") {
+            self.property_names.extend(
+                properties
+                    .iter()
+                    .map(|(name, value)| (value as *const Value, name.clone())),
+            );
+        }
+
+        transform_subschemas(self, schema);
+    }
+}
+
+// Returns the name and path of each definition, keyed by its canonical form. If several
+// definitions are identical, the first one is used.
+fn definition_keys(
+    definitions: &[(&'static str, Map<String, Value>)],
+) -> BTreeMap<String, (&'static str, String)> {
+    let mut keys = BTreeMap::new();
+    for (path, defs) in definitions {
+        for (name, def) in defs {
+            keys.entry(canonical_key(def))
+                .or_insert_with(|| (*path, name.clone()));
+        }
+    }
+    keys
+}
+
+fn definition_ref(path: &str, name: &str) -> String {
+    format!("#{path}/{}", crate::encoding::encode_ref_name(name))
+}
+
+// Replaces subschemas of the root schema and its definitions with a `$ref`, without replacing the
+// definitions themselves.
+fn replace_subschemas(
+    schema: &mut Schema,
+    definitions: &mut [(&'static str, Map<String, Value>)],
+    references: BTreeMap<String, String>,
+    extract_formats: bool,
+) {
+    if references.is_empty() {
+        return;
+    }
+
+    let mut replacer = ReplaceSubschemas {
+        references,
+        extract_formats,
+    };
+    transform_subschemas(&mut replacer, schema);
+    for (_, defs) in definitions {
+        for def in defs.values_mut() {
+            if let Ok(def) = def.try_into() {
+                transform_subschemas(&mut replacer, def);
+            }
+        }
+    }
+}
+
+// Replaces subschemas with a `$ref`, keyed by their canonical form
+struct ReplaceSubschemas {
+    references: BTreeMap<String, String>,
+    extract_formats: bool,
+}
+
+impl Transform for ReplaceSubschemas {
+    fn transform(&mut self, schema: &mut Schema) {
+        let reference = is_extractable(schema.as_value(), self.extract_formats)
+            .then(|| self.references.get(&canonical_key(schema.as_value())))
+            .flatten();
+        if let Some(reference) = reference {
+            *schema = Schema::new_ref(reference.clone());
+        } else {
+            transform_subschemas(self, schema);
+        }
+    }
+}
+
+fn is_extractable(value: &Value, extract_formats: bool) -> bool {
+    ["properties", "enum", "oneOf", "anyOf", "allOf"]
+        .into_iter()
+        .any(|k| value.get(k).is_some())
+        || (extract_formats && value.get("format").is_some())
+}
+
+// Serializes a value with its object keys sorted, so that equal values always have the same key
+// regardless of whether the `preserve_order` feature is enabled.
+fn canonical_key(value: &Value) -> String {
+    fn write(value: &Value, out: &mut String) {
+        match value {
+            Value::Array(array) => {
+                out.push('[');
+                for (i, item) in array.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write(item, out);
+                }
+                out.push(']');
+            }
+            Value::Object(obj) => {
+                let mut entries: Vec<_> = obj.iter().collect();
+                entries.sort_unstable_by_key(|(k, _)| *k);
+                out.push('{');
+                for (i, (k, v)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&Value::from(k.as_str()).to_string());
+                    out.push(':');
+                    write(v, out);
+                }
+                out.push('}');
+            }
+            other => out.push_str(&other.to_string()),
+        }
+    }
+
+    let mut out = String::new();
+    write(value, &mut out);
+    out
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars)
+        })
+        .collect()
+}
+
+// Removes and returns the root schema's definitions, along with the paths they were removed from.
+fn take_definitions(schema: &mut Schema) -> Vec<(&'static str, Map<String, Value>)> {
+    DEFINITIONS_PATHS
+        .into_iter()
+        .filter_map(|path| {
+            let (parent, key) = path.rsplit_once('/')?;
+            let parent = schema.pointer_mut(parent)?.as_object_mut()?;
+            match parent.remove(key)? {
+                Value::Object(defs) => Some((path, defs)),
+                other => {
+                    parent.insert(key.to_owned(), other);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+// Inserts definitions into the root schema at the given path, or removes the path's (now-empty)
+// parent object if there are no definitions.
+fn put_definitions(schema: &mut Schema, path: &str, defs: Map<String, Value>) {
+    let Some((parent_path, key)) = path.rsplit_once('/') else {
+        return;
+    };
+
+    if defs.is_empty() {
+        if let Some((_, parent_key)) = parent_path.rsplit_once('/') {
+            if schema
+                .get(parent_key)
+                .and_then(Value::as_object)
+                .is_some_and(Map::is_empty)
+            {
+                schema.remove(parent_key);
+            }
+        }
+        return;
+    }
+
+    if let Some(parent_key) = parent_path.strip_prefix('/') {
+        schema
+            .ensure_object()
+            .entry(parent_key)
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    if let Some(Value::Object(parent)) = schema.pointer_mut(parent_path) {
+        parent.insert(key.to_owned(), defs.into());
+    }
+}
+
+// Returns the paths and names of the definitions that are referenced from the schema, either
+// directly or via other referenced definitions.
+fn referenced_definitions(
+    schema: &Schema,
+    definitions: &[(&'static str, Map<String, Value>)],
+) -> BTreeSet<(&'static str, String)> {
+    fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(obj) => {
+                if let Some(Value::String(reference)) = obj.get("$ref") {
+                    refs.push(reference);
+                }
+                obj.values().for_each(|v| collect_refs(v, refs));
+            }
+            Value::Array(array) => array.iter().for_each(|v| collect_refs(v, refs)),
+            _ => {}
+        }
+    }
+
+    let mut pending = Vec::new();
+    collect_refs(schema.as_value(), &mut pending);
+
+    let mut referenced = BTreeSet::new();
+    while let Some(reference) = pending.pop() {
+        let Some(pointer) = reference
+            .strip_prefix('#')
+            .and_then(crate::encoding::percent_decode)
+        else {
+            continue;
+        };
+
+        for (path, defs) in definitions {
+            let Some(name) = pointer
+                .strip_prefix(path)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .and_then(|rest| rest.split('/').next())
+            else {
+                continue;
+            };
+            let name = name.replace("~1", "/").replace("~0", "~");
+            if let Some(def) = defs.get(&name) {
+                if referenced.insert((*path, name)) {
+                    collect_refs(def, &mut pending);
+                }
+            }
+        }
+    }
+
+    referenced
+}
+
+/// Rewrites schemas into a simpler but equivalent form, removing redundant structure that is often
+/// present in generated schemas.
+///
+/// This also applies to subschemas.
+///
+/// Each rewrite rule can be enabled or disabled individually, and none of them change which values
+/// are valid against the schema. The output is deterministic and applying the transform again has
+/// no further effect, so two schemas can be compared meaningfully after simplifying them both.
+///
+/// # Example
+/// ```
+/// use schemars::json_schema;
+/// use schemars::transform::{Simplify, Transform};
+///
+/// let mut schema = json_schema!({
+///     "type": "object",
+///     "properties": {
+///         "inner": {
+///             "description": "An inner value.",
+///             "allOf": [{ "type": "object", "properties": { "x": true } }]
+///         },
+///         "level": {
+///             "anyOf": [{ "const": "low" }, { "const": "high" }]
+///         },
+///         "id": {
+///             "anyOf": [{ "type": "string", "minLength": 1 }, { "type": "string", "format": "uuid" }]
+///         }
+///     },
+///     "required": ["level", "id", "inner", "id"]
+/// });
+///
+/// Simplify::default().transform(&mut schema);
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "object",
+///         "properties": {
+///             "inner": {
+///                 "description": "An inner value.",
+///                 "type": "object",
+///                 "properties": { "x": true }
+///             },
+///             "level": {
+///                 "enum": ["low", "high"]
+///             },
+///             "id": {
+///                 "type": "string",
+///                 "anyOf": [{ "minLength": 1 }, { "format": "uuid" }]
+///             }
+///         },
+///         "required": ["id", "inner", "level"]
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Simplify {
+    /// Whether to replace an `allOf`, `anyOf` or `oneOf` that contains a single subschema with
+    /// that subschema, merging it into the parent schema when their keywords do not overlap.
+    ///
+    /// By default, this is `true`.
+    pub unwrap_single_subschemas: bool,
+    /// Whether to replace an `anyOf` or `oneOf` where every subschema only contains `const` or
+    /// `enum` with a single `enum`.
+    ///
+    /// By default, this is `true`.
+    pub merge_enum_alternatives: bool,
+    /// Whether to replace an `anyOf` or `oneOf` where every subschema only contains `type` with
+    /// a single `type` array.
+    ///
+    /// By default, this is `true`.
+    pub merge_type_alternatives: bool,
+    /// Whether to move a `type` that is repeated in every subschema of an `anyOf` or `oneOf` into
+    /// the parent schema.
+    ///
+    /// By default, this is `true`.
+    pub hoist_common_type: bool,
+    /// Whether to sort and remove duplicate values from `required` and `type` arrays, remove
+    /// duplicate values from `enum` arrays, and replace single-valued `type` arrays with a string.
+    ///
+    /// By default, this is `true`.
+    pub normalize_arrays: bool,
+    /// Whether to move subschemas that are repeated throughout the schema into definitions, using
+    /// [`ExtractDefinitions`] with [`extract_formats`](ExtractDefinitions::extract_formats)
+    /// enabled, so that repeated schemas like `{"type": "string", "format": "uuid"}` are also
+    /// extracted. This is applied to the root schema after the other rules.
+    ///
+    /// By default, this is `false`.
+    pub extract_repeated_subschemas: bool,
+}
+
+impl Default for Simplify {
+    fn default() -> Self {
+        Self {
+            unwrap_single_subschemas: true,
+            merge_enum_alternatives: true,
+            merge_type_alternatives: true,
+            hoist_common_type: true,
+            normalize_arrays: true,
+            extract_repeated_subschemas: false,
+        }
+    }
+}
+
+impl Transform for Simplify {
+    fn transform(&mut self, schema: &mut Schema) {
+        let mut implementation = SimplifyImpl(self);
+        implementation.transform(schema);
+
+        if self.extract_repeated_subschemas {
+            ExtractDefinitions {
+                extract_formats: true,
+                ..ExtractDefinitions::default()
+            }
+            .transform(schema);
+        }
+    }
+}
+
+struct SimplifyImpl<'a>(&'a Simplify);
+
+impl Transform for SimplifyImpl<'_> {
+    fn transform(&mut self, schema: &mut Schema) {
+        transform_subschemas(self, schema);
+
+        // Each rewrite may allow another to be applied, so keep going until nothing changes
+        loop {
+            let changed = (self.0.unwrap_single_subschemas && unwrap_single_subschema(schema))
+                | (self.0.merge_enum_alternatives && merge_enum_alternatives(schema))
+                | (self.0.merge_type_alternatives && merge_type_alternatives(schema))
+                | (self.0.hoist_common_type && hoist_common_type(schema))
+                | (self.0.normalize_arrays && normalize_arrays(schema));
+            if !changed {
+                break;
+            }
+        }
+    }
+}
+
+const ALTERNATIVE_KEYWORDS: [&str; 3] = ["allOf", "anyOf", "oneOf"];
+
+fn unwrap_single_subschema(schema: &mut Schema) -> bool {
+    let Some(obj) = schema.as_object_mut() else {
+        return false;
+    };
+
+    for keyword in ALTERNATIVE_KEYWORDS {
+        let Some(Value::Array(subschemas)) = obj.get(keyword) else {
+            continue;
+        };
+        let [subschema] = subschemas.as_slice() else {
+            continue;
+        };
+
+        if obj.len() == 1 {
+            if let Ok(subschema) = Schema::try_from(subschema.clone()) {
+                *schema = subschema;
+                return true;
+            }
+        }
+
+        match subschema {
+            Value::Bool(true) => {}
+            Value::Object(sub_obj) if can_merge(obj, sub_obj) => {}
+            _ => continue,
+        }
+
+        if let Some(Value::Array(mut subschemas)) = obj.remove(keyword) {
+            if let Some(Value::Object(sub_obj)) = subschemas.pop() {
+                obj.extend(sub_obj);
+            }
+        }
+        return true;
+    }
+
+    false
+}
+
+// Keywords whose meaning depends on other keywords in the same schema object
+const ADJACENT_KEYWORD_GROUPS: [&[&str]; 3] = [
+    &[
+        "properties",
+        "patternProperties",
+        "additionalProperties",
+        "unevaluatedProperties",
+    ],
+    &[
+        "items",
+        "prefixItems",
+        "additionalItems",
+        "unevaluatedItems",
+        "contains",
+        "minContains",
+        "maxContains",
+    ],
+    &["if", "then", "else"],
+];
+
+// Whether the keywords of `sub_obj` can be moved into `obj` without changing their meaning
+fn can_merge(obj: &Map<String, Value>, sub_obj: &Map<String, Value>) -> bool {
+    let in_group =
+        |o: &Map<String, Value>, group: &[&str]| group.iter().any(|k| o.contains_key(*k));
+
+    // In older versions of JSON Schema, keywords alongside `$ref` are ignored
+    !obj.contains_key("$ref")
+        && !sub_obj.keys().any(|k| {
+            obj.contains_key(k)
+                || matches!(
+                    k.as_str(),
+                    "$ref" | "$defs" | "definitions" | "$id" | "$schema" | "$anchor"
+                )
+        })
+        && !ADJACENT_KEYWORD_GROUPS
+            .iter()
+            .any(|group| in_group(obj, group) && in_group(sub_obj, group))
+}
+
+fn merge_enum_alternatives(schema: &mut Schema) -> bool {
+    let Some(obj) = schema.as_object_mut() else {
+        return false;
+    };
+    if obj.contains_key("enum") || obj.contains_key("const") {
+        return false;
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        let Some(Value::Array(subschemas)) = obj.get(keyword) else {
+            continue;
+        };
+
+        let mut values = Vec::new();
+        let mut duplicated = false;
+        let all_enums = subschemas.iter().all(|s| {
+            let Some(sub_obj) = s.as_object() else {
+                return false;
+            };
+            let sub_values = match (sub_obj.get("const"), sub_obj.get("enum"), sub_obj.len()) {
+                (Some(value), None, 1) => core::slice::from_ref(value),
+                (None, Some(Value::Array(values)), 1) => values.as_slice(),
+                _ => return false,
+            };
+            for value in sub_values {
+                if values.contains(value) {
+                    duplicated = true;
+                } else {
+                    values.push(value.clone());
+                }
+            }
+            true
+        });
+
+        // A value that matches more than one subschema of a `oneOf` is invalid
+        if !all_enums || subschemas.is_empty() || (duplicated && keyword == "oneOf") {
+            continue;
+        }
+
+        obj.remove(keyword);
+        obj.insert("enum".into(), values.into());
+        return true;
+    }
+
+    false
+}
+
+fn merge_type_alternatives(schema: &mut Schema) -> bool {
+    let Some(obj) = schema.as_object_mut() else {
+        return false;
+    };
+    if obj.contains_key("type") {
+        return false;
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        let Some(Value::Array(subschemas)) = obj.get(keyword) else {
+            continue;
+        };
+
+        let mut types = Vec::new();
+        let all_types = subschemas.iter().all(|s| {
+            let Some(sub_obj) = s.as_object() else {
+                return false;
+            };
+            match (sub_obj.get("type"), sub_obj.len()) {
+                (Some(Value::String(ty)), 1) => types.push(ty.as_str()),
+                (Some(Value::Array(sub_types)), 1) => {
+                    types.extend(sub_types.iter().filter_map(Value::as_str));
+                }
+                _ => return false,
+            }
+            true
+        });
+
+        let unique_types = types.iter().copied().collect::<BTreeSet<_>>();
+        // Integers are also numbers, so would match more than one subschema of a `oneOf`
+        let overlapping = unique_types.len() != types.len()
+            || (unique_types.contains("integer") && unique_types.contains("number"));
+        if !all_types || types.is_empty() || (overlapping && keyword == "oneOf") {
+            continue;
+        }
+
+        let types = unique_types
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>();
+        obj.remove(keyword);
+        obj.insert("type".into(), types.into());
+        return true;
+    }
+
+    false
+}
+
+fn hoist_common_type(schema: &mut Schema) -> bool {
+    let Some(obj) = schema.as_object_mut() else {
+        return false;
+    };
+    if obj.contains_key("type") {
+        return false;
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        let Some(Value::Array(subschemas)) = obj.get_mut(keyword) else {
+            continue;
+        };
+
+        let Some(common_type) = subschemas.first().and_then(|s| s.get("type")).cloned() else {
+            continue;
+        };
+        // Leave subschemas that only contain `type` to `merge_type_alternatives`
+        if subschemas.len() < 2
+            || !subschemas.iter().all(|s| {
+                s.as_object()
+                    .is_some_and(|o| o.len() > 1 && o.get("type") == Some(&common_type))
+            })
+        {
+            continue;
+        }
+
+        for subschema in subschemas.iter_mut() {
+            if let Some(sub_obj) = subschema.as_object_mut() {
+                sub_obj.remove("type");
+            }
+        }
+        obj.insert("type".into(), common_type);
+        return true;
+    }
+
+    false
+}
+
+fn normalize_arrays(schema: &mut Schema) -> bool {
+    let Some(obj) = schema.as_object_mut() else {
+        return false;
+    };
+    let mut changed = false;
+
+    for keyword in ["required", "type"] {
+        if let Some(Value::Array(values)) = obj.get_mut(keyword) {
+            let mut normalized = values.clone();
+            normalized.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+            normalized.dedup();
+            if *values != normalized {
+                *values = normalized;
+                changed = true;
+            }
+        }
+    }
+
+    if let Some(Value::Array(types)) = obj.get_mut("type") {
+        if let [ty] = types.as_mut_slice() {
+            let ty = ty.take();
+            obj.insert("type".into(), ty);
+            changed = true;
+        }
+    }
+
+    if let Some(Value::Array(values)) = obj.get_mut("enum") {
+        let len = values.len();
+        let mut unique = Vec::with_capacity(len);
+        for value in values.drain(..) {
+            if !unique.contains(&value) {
+                unique.push(value);
+            }
+        }
+        changed |= unique.len() != len;
+        *values = unique;
+    }
+
+    changed
+}
+
+/// Rewrites a schema into the subset of JSON Schema accepted by the "strict" structured output
+/// mode of LLM APIs, e.g. for tool-call parameters or response formats.
+///
+/// This also applies to subschemas.
+///
+/// Specifically, this transform:
+/// - lists every property of object schemas in `required`, making previously-optional properties
+///   nullable instead (e.g. by adding `"null"` to their `type`, or wrapping them in an `anyOf`)
+/// - sets `additionalProperties` to `false` on object schemas, unless it is already a schema
+/// - replaces `oneOf` with `anyOf`
+/// - moves a `$ref` that has sibling keywords into a single-item `anyOf`
+/// - removes keywords that are not in [`allowed_keywords`](Self::allowed_keywords), unless they
+///   contain subschemas (e.g. `allOf` or `prefixItems`), since removing those could significantly
+///   change the meaning of the schema
+///
+/// Some constructs cannot be expressed in strict mode, e.g. maps with arbitrary keys, tuples, or a
+/// root schema that is not an object. These are left unchanged, and can be found using
+/// [`structured_output::check`](crate::structured_output::check).
+///
+/// Note that making an optional property nullable means that an LLM will send `null` rather than
+/// omitting the property. This deserializes correctly for `Option` fields, but not for other
+/// fields that have a `#[serde(default)]` attribute.
+///
+/// # Example
+/// ```
+/// # use schemars::{Schema, json_schema};
+/// use schemars::transform::{Transform, StrictStructuredOutput};
+///
+/// let mut schema = json_schema!({
+///     "type": "object",
+///     "properties": {
+///         "name": { "type": "string", "minLength": 1 },
+///         "email": { "type": "string", "format": "email" }
+///     },
+///     "required": ["name"]
+/// });
+///
+/// StrictStructuredOutput::default().transform(&mut schema);
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "object",
+///         "properties": {
+///             "name": { "type": "string" },
+///             "email": { "type": ["string", "null"] }
+///         },
+///         "required": ["name", "email"],
+///         "additionalProperties": false
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct StrictStructuredOutput {
+    /// Keywords that will be kept in schemas. Other keywords will be removed, unless they
+    /// contain subschemas.
+    ///
+    /// By default, this contains `type`, `properties`, `required`, `additionalProperties`,
+    /// `items`, `anyOf`, `enum`, `const`, `$ref`, `$defs`, `definitions`, `title` and
+    /// `description`. Some providers also support keywords such as `pattern` or `format`, which
+    /// can be added to this set.
+    pub allowed_keywords: BTreeSet<Cow<'static, str>>,
+}
+
+impl Default for StrictStructuredOutput {
+    fn default() -> Self {
+        Self {
+            allowed_keywords: STRICT_STRUCTURED_OUTPUT_KEYWORDS
+                .iter()
+                .map(|k| Cow::Borrowed(*k))
+                .collect(),
+        }
+    }
+}
+
+const STRICT_STRUCTURED_OUTPUT_KEYWORDS: [&str; 13] = [
+    "type",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+    "anyOf",
+    "enum",
+    "const",
+    "$ref",
+    "$defs",
+    "definitions",
+    "title",
+    "description",
+];
+
+// Keywords containing subschemas, which are not removed by `StrictStructuredOutput` because doing
+// so could significantly change the meaning of the schema.
+pub(crate) const SUBSCHEMA_KEYWORDS: [&str; 11] = [
+    "not",
+    "if",
+    "then",
+    "else",
+    "contains",
+    "propertyNames",
+    "additionalItems",
+    "allOf",
+    "oneOf",
+    "prefixItems",
+    "patternProperties",
+];
+
+impl Transform for StrictStructuredOutput {
+    fn transform(&mut self, schema: &mut Schema) {
+        if let Some(obj) = schema.as_object_mut() {
+            if !obj.contains_key("anyOf") {
+                if let Some(one_of) = obj.remove("oneOf") {
+                    obj.insert("anyOf".to_owned(), one_of);
+                }
+            }
+
+            if is_object_schema(obj) {
+                require_all_properties(obj);
+
+                if matches!(
+                    obj.get("additionalProperties"),
+                    None | Some(Value::Bool(true))
+                ) {
+                    obj.insert("additionalProperties".to_owned(), false.into());
+                }
+            }
+
+            obj.retain(|key, _| {
+                self.allowed_keywords.contains(key.as_str())
+                    || SUBSCHEMA_KEYWORDS.contains(&key.as_str())
+            });
+
+            if obj.len() > 1 && !obj.contains_key("anyOf") {
+                if let Some(reference) = obj.remove("$ref") {
+                    obj.insert("anyOf".to_owned(), json!([{ "$ref": reference }]));
+                }
+            }
+        }
+
+        transform_subschemas(self, schema);
+    }
+}
+
+pub(crate) fn is_object_schema(obj: &Map<String, Value>) -> bool {
+    obj.contains_key("properties")
+        || match obj.get("type") {
+            Some(Value::String(ty)) => ty == "object",
+            Some(Value::Array(types)) => types.iter().any(|ty| ty == "object"),
+            _ => false,
+        }
+}
+
+fn require_all_properties(obj: &mut Map<String, Value>) {
+    let mut required = match obj.remove("required") {
+        Some(Value::Array(required)) => required,
+        _ => Vec::new(),
+    };
+
+    if let Some(Value::Object(properties)) = obj.get_mut("properties") {
+        for (name, property) in properties.iter_mut() {
+            if !required.iter().any(|r| r == name) {
+                make_nullable(property);
+                required.push(Value::String(name.clone()));
+            }
+        }
+    }
+
+    if !required.is_empty() {
+        obj.insert("required".to_owned(), Value::Array(required));
+    }
+}
+
+fn make_nullable(schema: &mut Value) {
+    if accepts_null(schema) {
+        return;
+    }
+
+    let Value::Object(obj) = schema else {
+        // `false` schema
+        *schema = json!({ "type": "null" });
+        return;
+    };
+
+    let has_ref = obj.contains_key("$ref");
+    let has_type = obj.contains_key("type");
+    let has_subschemas = obj.contains_key("anyOf") || obj.contains_key("oneOf");
+    let has_values = obj.contains_key("enum") || obj.contains_key("const");
+
+    if !has_ref && !has_subschemas && (has_type || has_values) {
+        match obj.get_mut("type") {
+            Some(Value::String(ty)) => {
+                let ty = core::mem::take(ty);
+                obj.insert("type".to_owned(), json!([ty, "null"]));
+            }
+            Some(Value::Array(types)) => types.push("null".into()),
+            _ => {}
+        }
+        if let Some(Value::Array(values)) = obj.get_mut("enum") {
+            values.push(Value::Null);
+        }
+        if let Some(value) = obj.remove("const") {
+            obj.insert("enum".to_owned(), json!([value, null]));
+        }
+        return;
+    }
+
+    if !has_ref && !has_type && !has_values {
+        // A `null` subschema can be added to `oneOf` as well as `anyOf`, since none of the
+        // existing subschemas accept null.
+        let subschemas = match obj.get_mut("anyOf") {
+            Some(any_of) => Some(any_of),
+            None => obj.get_mut("oneOf"),
+        };
+        if let Some(Value::Array(subschemas)) = subschemas {
+            subschemas.push(json!({ "type": "null" }));
+            return;
+        }
+    }
+
+    let mut wrapper = Map::new();
+    for annotation in ["title", "description"] {
+        if let Some(value) = obj.remove(annotation) {
+            wrapper.insert(annotation.to_owned(), value);
+        }
+    }
+    wrapper.insert(
+        "anyOf".to_owned(),
+        json!([schema.take(), { "type": "null" }]),
+    );
+    *schema = Value::Object(wrapper);
+}
+
+fn accepts_null(schema: &Value) -> bool {
+    let Value::Object(obj) = schema else {
+        return schema == &Value::Bool(true);
+    };
+
+    // The referenced schema may or may not accept null, so conservatively assume it doesn't.
+    if obj.contains_key("$ref") {
+        return false;
+    }
+
+    let type_allows_null = match obj.get("type") {
+        Some(Value::String(ty)) => ty == "null",
+        Some(Value::Array(types)) => types.iter().any(|ty| ty == "null"),
+        Some(_) => false,
+        None => true,
+    };
+    let values_allow_null = match (obj.get("enum"), obj.get("const")) {
+        (Some(Value::Array(values)), _) => values.contains(&Value::Null),
+        (_, Some(value)) => value.is_null(),
+        _ => true,
+    };
+    let subschemas_allow_null = ["anyOf", "oneOf"]
+        .iter()
+        .all(|keyword| match obj.get(*keyword) {
+            Some(Value::Array(subschemas)) => subschemas.iter().any(accepts_null),
+            _ => true,
+        });
+
+    type_allows_null && values_allow_null && subschemas_allow_null
+}
+
+/// Restructures a schema into a Kubernetes [structural
+/// schema](https://kubernetes.io/docs/tasks/extend-kubernetes/custom-resources/custom-resource-definitions/#specifying-a-structural-schema),
+/// as required for the `openAPIV3Schema` of a `CustomResourceDefinition`.
+///
+/// This also applies to subschemas.
+///
+/// This transform:
+/// - replaces `const` with a single-valued `enum`
+/// - unwraps an `anyOf` containing a `{"type": "null"}` subschema (as generated for `Option<T>`),
+///   adding `"null"` to the `type` of the remaining subschema
+/// - replaces `true` schemas, schemas that allow any value and `"additionalProperties": true`
+///   with `"x-kubernetes-preserve-unknown-fields": true`
+/// - removes `"additionalProperties": false`, since Kubernetes prunes unknown fields by default
+/// - replaces an `anyOf`/`oneOf` of an integer and a string schema with
+///   `"x-kubernetes-int-or-string": true`
+/// - merges `anyOf`/`oneOf` subschemas that are all string enums into a single `enum`
+/// - moves the properties of `anyOf`/`oneOf` subschemas that are all object schemas (e.g. enum
+///   variants) into the parent schema, leaving only `required` in each subschema
+/// - replaces `"uniqueItems": true` with `"x-kubernetes-list-type": "set"` for arrays of scalars
+/// - removes keywords that Kubernetes does not support, such as `$schema`, `readOnly` or
+///   `deprecated`
+///
+/// This does not inline `$ref`s or replace `null` types with `nullable`, which can be done by the
+/// [`Dereference`] and [`AddNullable`] transforms. These are combined with this transform by
+/// [`SchemaSettings::kubernetes_structural()`](crate::generate::SchemaSettings::kubernetes_structural).
+/// Constructs that cannot be made structural (such as tuples, or enums that mix unit and
+/// non-unit variants) are left as they are, and can be found using
+/// [`kubernetes::check`](crate::kubernetes::check).
+///
+/// # Example
+/// ```
+/// use schemars::json_schema;
+/// use schemars::transform::{KubernetesStructural, Transform};
+///
+/// let mut schema = json_schema!({
+///     "oneOf": [
+///         {
+///             "type": "object",
+///             "properties": {
+///                 "kind": { "type": "string", "const": "Deployment" },
+///                 "replicas": { "type": "integer" }
+///             },
+///             "required": ["kind", "replicas"]
+///         },
+///         {
+///             "type": "object",
+///             "properties": {
+///                 "kind": { "type": "string", "const": "Job" },
+///                 "port": {
+///                     "anyOf": [{ "type": "integer" }, { "type": "string" }]
+///                 }
+///             },
+///             "required": ["kind"]
+///         }
+///     ]
+/// });
+///
+/// KubernetesStructural::default().transform(&mut schema);
+///
+/// assert_eq!(
+///     schema,
+///     json_schema!({
+///         "type": "object",
+///         "properties": {
+///             "kind": { "type": "string", "enum": ["Deployment", "Job"] },
+///             "replicas": { "type": "integer" },
+///             "port": { "x-kubernetes-int-or-string": true }
+///         },
+///         "oneOf": [
+///             { "required": ["kind", "replicas"] },
+///             { "required": ["kind"] }
+///         ]
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct KubernetesStructural;
+
+// Keywords of Kubernetes' `JSONSchemaProps` that are allowed in structural schemas, other than
+// `x-kubernetes-*` extensions.
+pub(crate) const KUBERNETES_KEYWORDS: [&str; 29] = [
+    "type",
+    "format",
+    "title",
+    "description",
+    "default",
+    "example",
+    "enum",
+    "nullable",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "multipleOf",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "maxProperties",
+    "minProperties",
+    "required",
+    "items",
+    "properties",
+    "additionalProperties",
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+    "externalDocs",
+];
+
+pub(crate) const PRESERVE_UNKNOWN_FIELDS: &str = "x-kubernetes-preserve-unknown-fields";
+pub(crate) const INT_OR_STRING: &str = "x-kubernetes-int-or-string";
+
+impl Transform for KubernetesStructural {
+    fn transform(&mut self, schema: &mut Schema) {
+        if schema.as_bool() == Some(true) {
+            *schema = json_schema!({ "x-kubernetes-preserve-unknown-fields": true });
+            return;
+        }
+        let Some(obj) = schema.as_object_mut() else {
+            return;
+        };
+
+        let nullable = unwrap_nullable(obj);
+
+        if let Some(value) = obj.remove("const") {
+            obj.insert("enum".to_owned(), Value::Array(vec![value]));
+        }
+
+        match obj.get("additionalProperties") {
+            Some(Value::Bool(true)) => {
+                obj.remove("additionalProperties");
+                obj.insert(PRESERVE_UNKNOWN_FIELDS.to_owned(), true.into());
+            }
+            Some(Value::Object(ap)) if allows_any_value(ap) => {
+                obj.remove("additionalProperties");
+                obj.insert(PRESERVE_UNKNOWN_FIELDS.to_owned(), true.into());
+            }
+            Some(Value::Bool(false)) => {
+                obj.remove("additionalProperties");
+            }
+            _ => {}
+        }
+
+        if obj.remove("uniqueItems") == Some(Value::Bool(true)) {
+            let scalar_items = obj
+                .get("items")
+                .and_then(|items| items.get("type"))
+                .and_then(Value::as_str)
+                .is_some_and(|ty| ["string", "integer", "number", "boolean"].contains(&ty));
+            if scalar_items {
+                obj.entry("x-kubernetes-list-type").or_insert("set".into());
+            }
+        }
+
+        transform_subschemas(self, schema);
+
+        let obj = schema.as_object_mut().unwrap();
+
+        let nullable = replace_int_or_string(obj) || nullable;
+        for keyword in ["anyOf", "oneOf"] {
+            if merge_string_enums(obj, keyword) || hoist_properties(obj, keyword) {
+                break;
+            }
+        }
+
+        if obj.contains_key("properties") && obj.remove("additionalProperties").is_some() {
+            obj.insert(PRESERVE_UNKNOWN_FIELDS.to_owned(), true.into());
+        }
+
+        if allows_any_value(obj) {
+            obj.insert(PRESERVE_UNKNOWN_FIELDS.to_owned(), true.into());
+        }
+
+        if nullable {
+            match obj.get_mut("type") {
+                Some(Value::String(ty)) => {
+                    let ty = core::mem::take(ty);
+                    obj.insert("type".to_owned(), json!([ty, "null"]));
+                }
+                Some(Value::Array(types)) => {
+                    if !types.iter().any(|t| t == "null") {
+                        types.push("null".into());
+                    }
+                }
+                _ => {
+                    obj.insert("nullable".to_owned(), true.into());
+                }
+            }
+        }
+
+        obj.retain(|key, _| {
+            KUBERNETES_KEYWORDS.contains(&key.as_str())
+                || key.starts_with("x-kubernetes-")
+                || SUBSCHEMA_KEYWORDS.contains(&key.as_str())
+                // Removing these could break other `$ref`s, so leave them to be reported
+                || ["$ref", "$defs", "definitions"].contains(&key.as_str())
+        });
+    }
+}
+
+// Replaces `{"anyOf": [schema, {"type": "null"}]}` with `schema`, returning whether a null
+// subschema was removed.
+fn unwrap_nullable(obj: &mut Map<String, Value>) -> bool {
+    let is_null_schema = |v: &Value| {
+        v.as_object()
+            .is_some_and(|o| o.len() == 1 && o.get("type") == Some(&Value::from("null")))
+    };
+
+    let Some(Value::Array(any_of)) = obj.get("anyOf") else {
+        return false;
+    };
+    if any_of.len() != 2 || !any_of.iter().any(is_null_schema) {
+        return false;
+    }
+
+    let Some(Value::Array(mut any_of)) = obj.remove("anyOf") else {
+        unreachable!()
+    };
+    any_of.retain(|v| !is_null_schema(v));
+    match any_of.pop() {
+        Some(Value::Object(inner)) => {
+            for (key, value) in inner {
+                obj.entry(key).or_insert(value);
+            }
+        }
+        Some(Value::Bool(true)) | None => {}
+        Some(other) => {
+            obj.insert("anyOf".to_owned(), Value::Array(vec![other]));
+        }
+    }
+    true
+}
+
+fn allows_any_value(obj: &Map<String, Value>) -> bool {
+    !obj.contains_key(PRESERVE_UNKNOWN_FIELDS)
+        && obj
+            .keys()
+            .all(|k| ANNOTATION_KEYWORDS.contains(&k.as_str()) || k == "example")
+}
+
+// Replaces a `type` array or `anyOf`/`oneOf` that allows only integers and strings with
+// `x-kubernetes-int-or-string`, returning whether `null` was also allowed.
+fn replace_int_or_string(obj: &mut Map<String, Value>) -> bool {
+    fn has_type(value: &Value, ty: &str) -> bool {
+        value.get("type").is_some_and(|t| t == ty)
+    }
+
+    if let Some(Value::Array(types)) = obj.get("type") {
+        let non_null = types.iter().filter(|t| *t != "null");
+        if non_null.clone().count() == 2
+            && non_null.clone().any(|t| t == "integer")
+            && non_null.clone().any(|t| t == "string")
+        {
+            let allows_null = types.len() > 2;
+            obj.remove("type");
+            obj.insert(INT_OR_STRING.to_owned(), true.into());
+            return allows_null;
+        }
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(subschemas)) = obj.get(keyword) {
+            if subschemas.len() == 2
+                && subschemas.iter().any(|s| has_type(s, "integer"))
+                && subschemas.iter().any(|s| has_type(s, "string"))
+            {
+                obj.remove(keyword);
+                obj.insert(INT_OR_STRING.to_owned(), true.into());
+                break;
+            }
+        }
+    }
+
+    false
+}
+
+// Merges `anyOf`/`oneOf` subschemas that only allow specific strings into a single `enum`.
+fn merge_string_enums(obj: &mut Map<String, Value>, keyword: &str) -> bool {
+    if obj.contains_key("type") || obj.contains_key("enum") {
+        return false;
+    }
+    let Some(Value::Array(subschemas)) = obj.get(keyword) else {
+        return false;
+    };
+
+    let mut values = Vec::new();
+    for subschema in subschemas {
+        let Some(sub) = subschema.as_object() else {
+            return false;
+        };
+        let Some(Value::Array(sub_values)) = sub.get("enum") else {
+            return false;
+        };
+        let only_strings = sub_values.iter().all(Value::is_string)
+            && sub.get("type").map_or(true, |t| t == "string")
+            && sub
+                .keys()
+                .all(|k| k == "enum" || k == "type" || ANNOTATION_KEYWORDS.contains(&k.as_str()));
+        if !only_strings {
+            return false;
+        }
+        for value in sub_values {
+            if !values.contains(value) {
+                values.push(value.clone());
+            }
+        }
+    }
+
+    obj.remove(keyword);
+    obj.insert("type".to_owned(), "string".into());
+    obj.insert("enum".to_owned(), Value::Array(values));
+    true
+}
+
+// Moves the properties of `anyOf`/`oneOf` subschemas that are all object schemas into the parent
+// schema, leaving only their `required` keywords behind.
+fn hoist_properties(obj: &mut Map<String, Value>, keyword: &str) -> bool {
+    if obj.get("type").is_some_and(|t| t != "object") {
+        return false;
+    }
+    let Some(Value::Array(subschemas)) = obj.get(keyword) else {
+        return false;
+    };
+    let mut properties = match obj.get("properties") {
+        Some(Value::Object(properties)) => properties.clone(),
+        None => Map::new(),
+        Some(_) => return false,
+    };
+
+    let mut remaining = Vec::new();
+    for subschema in subschemas {
+        let Some(sub) = subschema.as_object() else {
+            return false;
+        };
+        if sub.get("type").map_or(true, |t| t != "object")
+            || ["allOf", "anyOf", "oneOf", "not"]
+                .iter()
+                .any(|k| sub.contains_key(*k))
+        {
+            return false;
+        }
+
+        if let Some(Value::Object(sub_properties)) = sub.get("properties") {
+            for (name, property) in sub_properties {
+                match properties.get_mut(name) {
+                    Some(existing) => {
+                        if !merge_property(existing, property) {
+                            return false;
+                        }
+                    }
+                    None => {
+                        properties.insert(name.clone(), property.clone());
+                    }
+                }
+            }
+        }
+
+        remaining.push(
+            sub.get("required")
+                .filter(|r| r.as_array().is_some_and(|r| !r.is_empty()))
+                .map(|required| json!({ "required": required })),
+        );
+    }
+
+    obj.insert("type".to_owned(), "object".into());
+    if !properties.is_empty() {
+        obj.insert("properties".to_owned(), Value::Object(properties));
+    }
+    // If any subschema has no required properties, it would match any object, so the others no
+    // longer need to be checked.
+    match remaining.into_iter().collect::<Option<Vec<_>>>() {
+        Some(remaining) => obj.insert(keyword.to_owned(), Value::Array(remaining)),
+        None => obj.remove(keyword),
+    };
+    true
+}
+
+// Merges two schemas for the same property, which is possible if they are identical or only
+// differ in their `enum` values (e.g. for the tag property of an internally tagged enum).
+fn merge_property(existing: &mut Value, other: &Value) -> bool {
+    if existing == other {
+        return true;
+    }
+
+    let (Some(existing), Some(other)) = (existing.as_object_mut(), other.as_object()) else {
+        return false;
+    };
+    let (Some(Value::Array(_)), Some(Value::Array(other_values))) =
+        (existing.get("enum"), other.get("enum"))
+    else {
+        return false;
+    };
+    let same_keywords = existing.len() == other.len()
+        && existing
+            .iter()
+            .all(|(k, v)| k == "enum" || other.get(k) == Some(v));
+    if !same_keywords {
+        return false;
+    }
+
+    let Some(Value::Array(values)) = existing.get_mut("enum") else {
+        unreachable!()
+    };
+    for value in other_values {
+        if !values.contains(value) {
+            values.push(value.clone());
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn restrict_formats() {
+        let mut schema = json_schema!({
+            "$schema": meta_schemas::DRAFT2020_12,
+            "anyOf": [
+                { "format": "uuid" },
+                { "$schema": meta_schemas::DRAFT07, "format": "uuid" },
+                { "$schema": "http://unknown", "format": "uuid" },
+                { "format": "date" },
+                { "$schema": meta_schemas::DRAFT07, "format": "date" },
+                { "$schema": "http://unknown", "format": "date" },
+                { "format": "custom1" },
+                { "$schema": meta_schemas::DRAFT07, "format": "custom1" },
+                { "$schema": "http://unknown", "format": "custom1" },
+                { "format": "custom2" },
+                { "$schema": meta_schemas::DRAFT07, "format": "custom2" },
+                { "$schema": "http://unknown", "format": "custom2" },
+            ]
+        });
+
+        let mut transform = RestrictFormats::default();
+        transform.allowed_formats.insert("custom1".into());
+        transform.transform(&mut schema);
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "$schema": meta_schemas::DRAFT2020_12,
+                "anyOf": [
+                    { "format": "uuid" },
+                    { "$schema": meta_schemas::DRAFT07 },
+                    { "$schema": "http://unknown", "format": "uuid" },
+                    { "format": "date" },
+                    { "$schema": meta_schemas::DRAFT07, "format": "date" },
+                    { "$schema": "http://unknown", "format": "date" },
+                    { "format": "custom1" },
+                    { "$schema": meta_schemas::DRAFT07, "format": "custom1" },
+                    { "$schema": "http://unknown", "format": "custom1" },
+                    { },
+                    { "$schema": meta_schemas::DRAFT07 },
                     { "$schema": "http://unknown", "format": "custom2" },
                 ]
             })
         );
     }
+
+    #[test]
+    fn replace_exclusive_bounds() {
+        let mut schema = json_schema!({
+            "anyOf": [
+                { "exclusiveMinimum": 1, "exclusiveMaximum": 10 },
+                { "minimum": 0, "exclusiveMinimum": 1, "maximum": 10, "exclusiveMaximum": 10 },
+                { "minimum": 2, "exclusiveMinimum": 1, "maximum": 5, "exclusiveMaximum": 10 },
+                { "minimum": 0, "exclusiveMinimum": true },
+            ]
+        });
+
+        ReplaceExclusiveBounds.transform(&mut schema);
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "anyOf": [
+                    { "minimum": 1, "exclusiveMinimum": true, "maximum": 10, "exclusiveMaximum": true },
+                    { "minimum": 1, "exclusiveMinimum": true, "maximum": 10, "exclusiveMaximum": true },
+                    { "minimum": 2, "maximum": 5 },
+                    { "minimum": 0, "exclusiveMinimum": true },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn replace_defs() {
+        let mut schema = json_schema!({
+            "properties": {
+                "a": { "$ref": "#/$defs/A" },
+                "b": { "$ref": "#/%24defs/B" },
+                "c": { "$ref": "#/definitions/C" },
+            },
+            "$defs": {
+                "A": { "$ref": "#/$defs/B" },
+                "B": true,
+            },
+            "definitions": {
+                "C": true,
+            }
+        });
+
+        ReplaceDefs.transform(&mut schema);
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "properties": {
+                    "a": { "$ref": "#/definitions/A" },
+                    "b": { "$ref": "#/definitions/B" },
+                    "c": { "$ref": "#/definitions/C" },
+                },
+                "definitions": {
+                    "A": { "$ref": "#/definitions/B" },
+                    "B": true,
+                    "C": true,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn dereference() {
+        let mut schema = json_schema!({
+            "type": "object",
+            "properties": {
+                "a": { "$ref": "#/$defs/A" },
+                "b": { "$ref": "#/$defs/B", "description": "B via ref" },
+                "c": { "$ref": "#/$defs/C", "type": "object" },
+                "external": { "$ref": "https://example.com/schema" },
+            },
+            "$defs": {
+                "A": { "$ref": "#/$defs/B" },
+                "B": { "type": "string", "description": "B" },
+                "C": { "type": "object", "properties": { "x": { "$ref": "#/$defs/A" } } },
+                "Unused": true,
+            }
+        });
+
+        schema.dereference();
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "a": { "type": "string", "description": "B" },
+                    "b": { "type": "string", "description": "B via ref" },
+                    "c": {
+                        "type": "object",
+                        "allOf": [{
+                            "type": "object",
+                            "properties": {
+                                "x": { "type": "string", "description": "B" }
+                            }
+                        }]
+                    },
+                    "external": { "$ref": "https://example.com/schema" },
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn dereference_recursive() {
+        let original = json_schema!({
+            "$schema": meta_schemas::DRAFT07,
+            "anyOf": [
+                { "$ref": "#/definitions/List" },
+                { "$ref": "#" },
+            ],
+            "definitions": {
+                "List": {
+                    "type": "array",
+                    "items": { "$ref": "#/definitions/List" }
+                }
+            }
+        });
+
+        let mut schema = original.clone();
+        schema.dereference();
+        assert_eq!(
+            schema,
+            json_schema!({
+                "$schema": meta_schemas::DRAFT07,
+                "anyOf": [
+                    {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/List" }
+                    },
+                    {
+                        "anyOf": [
+                            {
+                                "type": "array",
+                                "items": { "$ref": "#/definitions/List" }
+                            },
+                            { "$ref": "#" },
+                        ]
+                    },
+                ],
+                "definitions": {
+                    "List": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/List" }
+                    }
+                }
+            })
+        );
+
+        let mut schema = original;
+        Dereference {
+            max_recursion: 1,
+            keep_cycle_refs: false,
+        }
+        .transform(&mut schema);
+        let list = json!({
+            "type": "array",
+            "items": {
+                "type": "array",
+                "items": true
+            }
+        });
+        assert_eq!(
+            schema,
+            json_schema!({
+                "$schema": meta_schemas::DRAFT07,
+                "anyOf": [
+                    list,
+                    {
+                        "anyOf": [
+                            list,
+                            {
+                                "anyOf": [list, true]
+                            },
+                        ]
+                    },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn dereference_openapi() {
+        let mut schema = json_schema!({
+            "openapi": "3.0.0",
+            "components": {
+                "schemas": {
+                    "A": { "type": "string" },
+                    "B": { "items": { "$ref": "#/components/schemas/A" } }
+                }
+            },
+            "items": { "$ref": "#/components/schemas/B" }
+        });
+
+        schema.dereference();
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "openapi": "3.0.0",
+                "items": {
+                    "items": { "type": "string" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn extract_definitions() {
+        let point = json!({
+            "type": "object",
+            "properties": {
+                "x": { "type": "number" },
+                "y": { "type": "number" }
+            }
+        });
+        let line = json!({
+            "type": "object",
+            "properties": {
+                "start": point,
+                "end": point
+            }
+        });
+        let mut schema = json_schema!({
+            "type": "object",
+            "properties": {
+                "first": line,
+                "second": line,
+                "third": { "title": "Tagged", "enum": ["a", "b"] },
+                "fourth": { "title": "Tagged", "enum": ["a", "b"] },
+                "fifth": { "enum": ["a", "b"] },
+                "sixth": { "enum": ["a", "b"] },
+                "origin": point,
+                "strings": { "type": "string" },
+                "more_strings": { "type": "string" },
+            },
+            "$defs": {
+                "Tagged": { "type": "string" },
+                "Existing": { "enum": ["a", "b"] }
+            }
+        });
+
+        schema.extract_definitions();
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "first": { "$ref": "#/$defs/First" },
+                    "second": { "$ref": "#/$defs/First" },
+                    "third": { "$ref": "#/$defs/Tagged2" },
+                    "fourth": { "$ref": "#/$defs/Tagged2" },
+                    "fifth": { "$ref": "#/$defs/Existing" },
+                    "sixth": { "$ref": "#/$defs/Existing" },
+                    "origin": { "$ref": "#/$defs/Origin" },
+                    "strings": { "type": "string" },
+                    "more_strings": { "type": "string" },
+                },
+                "$defs": {
+                    "Tagged": { "type": "string" },
+                    "Existing": { "enum": ["a", "b"] },
+                    "First": {
+                        "type": "object",
+                        "properties": {
+                            "start": { "$ref": "#/$defs/Origin" },
+                            "end": { "$ref": "#/$defs/Origin" }
+                        }
+                    },
+                    "Tagged2": { "title": "Tagged", "enum": ["a", "b"] },
+                    "Origin": point,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn extract_definitions_reuses_existing_definitions() {
+        let mut schema = json_schema!({
+            "type": "object",
+            "properties": {
+                "level": { "enum": ["low", "high"] },
+                "id": { "type": "string", "format": "uuid" }
+            },
+            "$defs": {
+                "Level": { "enum": ["low", "high"] },
+                "Id": { "type": "string", "format": "uuid" }
+            }
+        });
+
+        schema.extract_definitions();
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "level": { "$ref": "#/$defs/Level" },
+                    "id": { "type": "string", "format": "uuid" }
+                },
+                "$defs": {
+                    "Level": { "enum": ["low", "high"] },
+                    "Id": { "type": "string", "format": "uuid" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn extract_definitions_reverses_recursive_dereference() {
+        let original = json_schema!({
+            "type": "object",
+            "properties": {
+                "root": { "$ref": "#/$defs/Node" }
+            },
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "properties": {
+                        "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } }
+                    }
+                }
+            }
+        });
+
+        let mut schema = original.clone();
+        schema.dereference();
+        assert_eq!(
+            schema.pointer("/properties/root/type"),
+            Some(&json!("object"))
+        );
+        assert!(schema.pointer("/$defs/Node").is_some());
+
+        schema.extract_definitions();
+        assert_eq!(schema, original);
+    }
+
+    #[test]
+    fn extract_definitions_ignores_key_order() {
+        let mut schema = json_schema!({
+            "type": "object",
+            "properties": {
+                "a": { "type": "object", "properties": { "x": true, "y": true } },
+                "b": { "properties": { "y": true, "x": true }, "type": "object" }
+            }
+        });
+
+        schema.extract_definitions();
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "a": { "$ref": "#/$defs/A" },
+                    "b": { "$ref": "#/$defs/A" }
+                },
+                "$defs": {
+                    "A": { "type": "object", "properties": { "x": true, "y": true } }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn extract_definitions_reverses_dereference() {
+        let original = json_schema!({
+            "$schema": meta_schemas::DRAFT07,
+            "type": "object",
+            "properties": {
+                "a": { "$ref": "#/definitions/Inner" },
+                "b": { "$ref": "#/definitions/Inner" },
+            },
+            "definitions": {
+                "Inner": {
+                    "type": "object",
+                    "properties": { "value": { "type": "integer" } }
+                }
+            }
+        });
+
+        let mut schema = original.clone();
+        schema.dereference();
+        assert!(schema.get("definitions").is_none());
+
+        schema.extract_definitions();
+        assert_eq!(
+            schema,
+            json_schema!({
+                "$schema": meta_schemas::DRAFT07,
+                "type": "object",
+                "properties": {
+                    "a": { "$ref": "#/definitions/A" },
+                    "b": { "$ref": "#/definitions/A" },
+                },
+                "definitions": {
+                    "A": {
+                        "type": "object",
+                        "properties": { "value": { "type": "integer" } }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn simplify() {
+        let mut schema = json_schema!({
+            "anyOf": [
+                { "allOf": [{ "type": "integer" }] },
+                { "oneOf": [{ "const": 1 }, { "enum": [2, 3] }] },
+                { "oneOf": [{ "const": 1 }, { "enum": [1, 2] }] },
+                { "anyOf": [{ "const": 1 }, { "enum": [1, 2] }] },
+                { "anyOf": [{ "type": "string" }, { "type": ["null", "string"] }] },
+                { "oneOf": [{ "type": "integer" }, { "type": "number" }] },
+                { "anyOf": [{ "type": "object", "minProperties": 1 }, { "type": "object" , "title": "x" }] },
+                { "description": "x", "allOf": [{ "$ref": "#/$defs/A" }] },
+                { "additionalProperties": false, "allOf": [{ "properties": { "a": true } }] },
+                { "minimum": 1, "allOf": [{ "minimum": 2 }] },
+                { "type": ["string"], "required": ["b", "a", "b"], "enum": ["x", "x", "y"] },
+            ]
+        });
+
+        let mut transform = Simplify::default();
+        transform.transform(&mut schema);
+
+        let expected = json_schema!({
+            "anyOf": [
+                { "type": "integer" },
+                { "enum": [1, 2, 3] },
+                { "oneOf": [{ "const": 1 }, { "enum": [1, 2] }] },
+                { "enum": [1, 2] },
+                { "type": ["null", "string"] },
+                { "oneOf": [{ "type": "integer" }, { "type": "number" }] },
+                { "type": "object", "anyOf": [{ "minProperties": 1 }, { "title": "x" }] },
+                { "description": "x", "allOf": [{ "$ref": "#/$defs/A" }] },
+                { "additionalProperties": false, "allOf": [{ "properties": { "a": true } }] },
+                { "minimum": 1, "allOf": [{ "minimum": 2 }] },
+                { "type": "string", "required": ["a", "b"], "enum": ["x", "y"] },
+            ]
+        });
+        assert_eq!(schema, expected);
+
+        transform.transform(&mut schema);
+        assert_eq!(schema, expected);
+    }
+
+    #[test]
+    fn simplify_extracts_repeated_formats() {
+        let mut schema = json_schema!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "format": "uuid" },
+                "parent_id": { "type": "string", "format": "uuid" },
+                "created": { "type": "string", "format": "date-time" },
+                "name": { "type": "string" },
+                "label": { "type": "string" }
+            }
+        });
+
+        Simplify::default().transform(&mut schema);
+        assert_eq!(schema.get("$defs"), None);
+
+        let mut transform = Simplify {
+            extract_repeated_subschemas: true,
+            ..Simplify::default()
+        };
+        transform.transform(&mut schema);
+        assert_eq!(
+            schema,
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "id": { "$ref": "#/$defs/Uuid" },
+                    "parent_id": { "$ref": "#/$defs/Uuid" },
+                    "created": { "type": "string", "format": "date-time" },
+                    "name": { "type": "string" },
+                    "label": { "type": "string" }
+                },
+                "$defs": {
+                    "Uuid": { "type": "string", "format": "uuid" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn simplify_rules_can_be_disabled() {
+        let original = json_schema!({
+            "allOf": [{
+                "anyOf": [{ "const": "a" }, { "const": "b" }]
+            }],
+            "required": ["b", "a"]
+        });
+
+        let mut schema = original.clone();
+        let mut transform = Simplify {
+            unwrap_single_subschemas: false,
+            merge_enum_alternatives: false,
+            normalize_arrays: false,
+            ..Simplify::default()
+        };
+        transform.transform(&mut schema);
+        assert_eq!(schema, original);
+
+        transform.merge_enum_alternatives = true;
+        transform.transform(&mut schema);
+        assert_eq!(
+            schema,
+            json_schema!({
+                "allOf": [{ "enum": ["a", "b"] }],
+                "required": ["b", "a"]
+            })
+        );
+    }
+
+    #[test]
+    fn path_transform() {
+        let mut schema = json_schema!({
+            "$defs": {
+                "a/b~c": { "type": "string" }
+            },
+            "properties": {
+                "list": {
+                    "items": [true, { "anyOf": [{ "$ref": "#/$defs/a~1b~0c" }] }]
+                },
+                "map": {
+                    "additionalProperties": { "items": false }
+                }
+            },
+            "not": {},
+            "examples": [{}]
+        });
+
+        let mut visited = Vec::new();
+        RecursivePathTransform(|schema: &mut Schema, location: SchemaLocation| {
+            visited.push((
+                location.pointer.to_owned(),
+                location.keyword.map(str::to_owned),
+            ));
+            if location.keyword == Some("anyOf") {
+                schema.insert("title".to_owned(), location.pointer.into());
+            }
+        })
+        .transform(&mut schema);
+
+        visited.sort();
+        let expected = [
+            ("", None),
+            ("/$defs/a~1b~0c", Some("$defs")),
+            ("/not", Some("not")),
+            ("/properties/list", Some("properties")),
+            ("/properties/list/items/0", Some("items")),
+            ("/properties/list/items/1", Some("items")),
+            ("/properties/list/items/1/anyOf/0", Some("anyOf")),
+            ("/properties/map", Some("properties")),
+            (
+                "/properties/map/additionalProperties",
+                Some("additionalProperties"),
+            ),
+            ("/properties/map/additionalProperties/items", Some("items")),
+        ]
+        .map(|(pointer, keyword)| (pointer.to_owned(), keyword.map(str::to_owned)));
+        assert_eq!(visited, expected);
+
+        assert_eq!(
+            schema.pointer("/properties/list/items/1/anyOf/0/title"),
+            Some(&json!("/properties/list/items/1/anyOf/0"))
+        );
+    }
+
+    #[test]
+    fn strict_structured_output() {
+        let mut schema = json_schema!({
+            "type": "object",
+            "properties": {
+                "a": { "type": "string", "format": "uuid", "default": "" },
+                "b": { "enum": [1, 2] },
+                "c": { "const": "x" },
+                "d": { "$ref": "#/$defs/D", "description": "A D" },
+                "e": { "oneOf": [{ "type": "integer" }, { "type": "boolean" }] },
+                "f": { "type": ["string", "null"] },
+                "g": false,
+                "h": { "type": "object", "additionalProperties": { "type": "integer" } },
+                "i": { "$ref": "#/$defs/D", "description": "A required D" }
+            },
+            "required": ["i"],
+            "unevaluatedProperties": false,
+            "prefixItems": [{ "type": "string", "examples": [] }],
+            "$defs": {
+                "D": {
+                    "properties": {
+                        "x": { "type": "number" }
+                    }
+                }
+            }
+        });
+
+        StrictStructuredOutput::default().transform(&mut schema);
+
+        let mut required: Vec<_> = schema.as_object().unwrap()["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r.as_str().unwrap())
+            .collect();
+        required.sort_unstable();
+        assert_eq!(required, ["a", "b", "c", "d", "e", "f", "g", "h", "i"]);
+        schema.remove("required");
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "a": { "type": ["string", "null"] },
+                    "b": { "enum": [1, 2, null] },
+                    "c": { "enum": ["x", null] },
+                    "d": {
+                        "description": "A D",
+                        "anyOf": [{ "$ref": "#/$defs/D" }, { "type": "null" }]
+                    },
+                    "e": { "anyOf": [{ "type": "integer" }, { "type": "boolean" }, { "type": "null" }] },
+                    "f": { "type": ["string", "null"] },
+                    "g": { "type": "null" },
+                    "h": {
+                        "type": ["object", "null"],
+                        "additionalProperties": { "type": "integer" }
+                    },
+                    "i": {
+                        "description": "A required D",
+                        "anyOf": [{ "$ref": "#/$defs/D" }]
+                    }
+                },
+                "additionalProperties": false,
+                "prefixItems": [{ "type": "string" }],
+                "$defs": {
+                    "D": {
+                        "properties": {
+                            "x": { "type": ["number", "null"] }
+                        },
+                        "required": ["x"],
+                        "additionalProperties": false
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn kubernetes_structural() {
+        let mut schema = json_schema!({
+            "type": "object",
+            "properties": {
+                "a": {
+                    "description": "An optional enum",
+                    "anyOf": [
+                        {
+                            "oneOf": [
+                                { "type": "string", "const": "x", "description": "X" },
+                                { "type": "string", "enum": ["y", "z"] }
+                            ]
+                        },
+                        { "type": "null" }
+                    ]
+                },
+                "b": { "type": ["integer", "string", "null"] },
+                "c": {
+                    "type": "object",
+                    "properties": { "x": { "type": "number" } },
+                    "additionalProperties": { "type": "string" }
+                },
+                "d": { "type": "object", "additionalProperties": true },
+                "e": { "description": "Anything", "readOnly": true },
+                "f": {
+                    "type": "array",
+                    "items": { "type": "object" },
+                    "uniqueItems": true
+                },
+                "g": {
+                    "oneOf": [
+                        { "type": "object", "properties": { "x": { "type": "number" } } },
+                        { "type": "object", "properties": { "x": { "type": "string" } } }
+                    ]
+                }
+            },
+            "additionalProperties": false,
+            "deprecated": true
+        });
+
+        KubernetesStructural.transform(&mut schema);
+
+        assert_eq!(
+            schema,
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "a": {
+                        "description": "An optional enum",
+                        "type": ["string", "null"],
+                        "enum": ["x", "y", "z"]
+                    },
+                    "b": { "x-kubernetes-int-or-string": true, "nullable": true },
+                    "c": {
+                        "type": "object",
+                        "properties": { "x": { "type": "number" } },
+                        "x-kubernetes-preserve-unknown-fields": true
+                    },
+                    "d": { "type": "object", "x-kubernetes-preserve-unknown-fields": true },
+                    "e": { "description": "Anything", "x-kubernetes-preserve-unknown-fields": true },
+                    "f": {
+                        "type": "array",
+                        "items": { "type": "object" }
+                    },
+                    "g": {
+                        "oneOf": [
+                            { "type": "object", "properties": { "x": { "type": "number" } } },
+                            { "type": "object", "properties": { "x": { "type": "string" } } }
+                        ]
+                    }
+                }
+            })
+        );
+    }
 }
@@ -0,0 +1,619 @@
+/*!
+Generation of TypeScript declarations (`.d.ts`) from a [`Schema`].
+
+This module is only available when the `typescript` feature flag is enabled.
+
+The root schema is emitted as an exported type named after the root type, followed by an
+exported type for each definition that it (transitively) references. Schemas describing objects
+with a fixed set of properties are emitted as `interface`s, and all other schemas are emitted as
+`type` aliases:
+- internally and adjacently tagged enums become discriminated unions, because each variant's tag
+  property has a `const` value, which becomes a string literal type.
+- unit-only enums (and other schemas with `enum`/`const`) become unions of literal types.
+- properties that are not `required` are marked as optional with `?`.
+- `additionalProperties` (and `patternProperties`) become `Record<string, T>`.
+- `title` and `description` become `/** */` doc comments, and `deprecated` becomes a
+  `@deprecated` tag.
+
+The declarations describe values according to the [`Contract`](crate::generate::Contract) that
+the schema was generated for, so TypeScript types for both the serialized and deserialized forms
+of a rust type can be emitted by using a generator created with
+[`SchemaSettings::for_serialize`] or [`SchemaSettings::for_deserialize`].
+
+Keywords that cannot be represented in TypeScript's type system (such as `minimum` or `pattern`)
+are ignored.
+
+# Example
+```
+use schemars::{typescript, JsonSchema, SchemaGenerator};
+
+/// A user of the application.
+#[derive(JsonSchema)]
+struct User {
+    id: u64,
+    name: String,
+    nickname: Option<String>,
+    role: Role,
+}
+
+#[derive(JsonSchema)]
+enum Role {
+    Admin,
+    Member,
+}
+
+let mut generator = SchemaGenerator::default();
+let declarations = typescript::declarations_for::<User>(&mut generator);
+
+assert_eq!(
+    declarations,
+    r#"/** A user of the application. */
+export interface User {
+  id: number;
+  name: string;
+  nickname?: string | null;
+  role: Role;
+}
+
+export type Role = "Admin" | "Member";
+"#
+);
+```
+
+[`SchemaSettings::for_serialize`]: crate::generate::SchemaSettings::for_serialize
+[`SchemaSettings::for_deserialize`]: crate::generate::SchemaSettings::for_deserialize
+*/
+
+use crate::_alloc_prelude::*;
+use crate::{JsonSchema, Schema, SchemaGenerator};
+use alloc::collections::{BTreeSet, VecDeque};
+use serde_json::{Map, Value};
+
+/// Generates TypeScript declarations for the type `T` and all types that its schema references,
+/// using the given generator.
+///
+/// The root type is named after [`T::schema_name()`](JsonSchema::schema_name).
+#[must_use]
+pub fn declarations_for<T: ?Sized + JsonSchema>(generator: &mut SchemaGenerator) -> String {
+    let root = generator.root_schema_for::<T>();
+    declarations(&T::schema_name(), &root)
+}
+
+/// Generates TypeScript declarations for a root schema, giving it the type name `root_name`.
+///
+/// Declarations are also generated for all definitions that the schema references. Any `$ref`s
+/// within the schema are resolved against `root`, so it should contain its definitions (e.g.
+/// under `$defs`), as is the case for schemas returned by
+/// [`SchemaGenerator::root_schema_for`].
+#[must_use]
+pub fn declarations(root_name: &str, root: &Schema) -> String {
+    let mut emitter = Emitter {
+        root,
+        root_name: type_name(root_name),
+        queued: BTreeSet::new(),
+        pending: VecDeque::new(),
+        output: String::new(),
+    };
+
+    let name = emitter.root_name.clone();
+    emitter.emit_declaration(&name, root.as_value(), Some(root_name));
+
+    while let Some(reference) = emitter.pending.pop_front() {
+        if let Some(schema) = emitter.root.resolve_ref(&reference) {
+            let name = emitter.ref_type_name(&reference);
+            emitter.emit_declaration(&name, schema.as_value(), None);
+        }
+    }
+
+    emitter.output
+}
+
+struct Emitter<'a> {
+    root: &'a Schema,
+    root_name: String,
+    // `$ref`s that have been seen, and those that have not yet been emitted (in the order they
+    // were first seen)
+    queued: BTreeSet<String>,
+    pending: VecDeque<String>,
+    output: String,
+}
+
+// A TypeScript type expression
+#[derive(Debug, Clone, PartialEq)]
+enum Ty {
+    Named(String),
+    Object(Vec<Property>),
+    Array(Box<Ty>),
+    Tuple(Vec<Ty>, Option<Box<Ty>>),
+    Record(Box<Ty>),
+    Union(Vec<Ty>),
+    Intersection(Vec<Ty>),
+    // A type with a doc comment, which is only emitted for members of multi-line unions
+    Documented(String, Box<Ty>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Property {
+    name: String,
+    optional: bool,
+    readonly: bool,
+    doc: Option<String>,
+    ty: Ty,
+}
+
+impl Ty {
+    fn named(name: &str) -> Self {
+        Ty::Named(name.to_owned())
+    }
+
+    fn union(members: Vec<Ty>) -> Self {
+        let mut flattened = Vec::new();
+        for member in members {
+            match member {
+                Ty::Union(inner) => flattened.extend(inner),
+                other => flattened.push(other),
+            }
+        }
+
+        let mut deduped = Vec::new();
+        for member in flattened {
+            if !deduped.contains(&member) {
+                deduped.push(member);
+            }
+        }
+
+        if deduped.contains(&Ty::named("unknown")) {
+            return Ty::named("unknown");
+        }
+        deduped.retain(|m| m != &Ty::named("never"));
+
+        match deduped.len() {
+            0 => Ty::named("never"),
+            1 => deduped.remove(0),
+            _ => Ty::Union(deduped),
+        }
+    }
+
+    fn intersection(members: Vec<Ty>) -> Self {
+        let mut flattened = Vec::new();
+        for member in members {
+            match member {
+                Ty::Intersection(inner) => flattened.extend(inner),
+                Ty::Named(name) if name == "unknown" => {}
+                other => flattened.push(other),
+            }
+        }
+
+        match flattened.len() {
+            0 => Ty::named("unknown"),
+            1 => flattened.remove(0),
+            _ => Ty::Intersection(flattened),
+        }
+    }
+
+    fn is_multiline(&self) -> bool {
+        match self {
+            Ty::Named(_) => false,
+            Ty::Object(properties) => !properties.is_empty(),
+            Ty::Array(inner) | Ty::Record(inner) | Ty::Documented(_, inner) => inner.is_multiline(),
+            Ty::Tuple(items, rest) => {
+                items.iter().any(Ty::is_multiline)
+                    || rest.as_ref().is_some_and(|r| r.is_multiline())
+            }
+            Ty::Union(members) | Ty::Intersection(members) => members.iter().any(Ty::is_multiline),
+        }
+    }
+
+    // Formats the type, where `indent` is the indentation of the line that the type starts on.
+    fn format(&self, indent: &str) -> String {
+        match self {
+            Ty::Named(name) => name.clone(),
+            Ty::Object(properties) if properties.is_empty() => "{}".to_owned(),
+            Ty::Object(properties) => {
+                let inner_indent = format!("{indent}  ");
+                let mut out = "{\n".to_owned();
+                for property in properties {
+                    if let Some(doc) = &property.doc {
+                        write_doc_comment(&mut out, doc, &inner_indent);
+                    }
+                    out.push_str(&inner_indent);
+                    if property.readonly {
+                        out.push_str("readonly ");
+                    }
+                    out.push_str(&property_name(&property.name));
+                    if property.optional {
+                        out.push('?');
+                    }
+                    out.push_str(": ");
+                    out.push_str(&property.ty.format(&inner_indent));
+                    out.push_str(";\n");
+                }
+                out.push_str(indent);
+                out.push('}');
+                out
+            }
+            Ty::Array(inner) => format!("{}[]", inner.format_operand(indent, true)),
+            Ty::Tuple(items, rest) => {
+                let mut parts = items.iter().map(|i| i.format(indent)).collect::<Vec<_>>();
+                if let Some(rest) = rest {
+                    parts.push(format!("...{}[]", rest.format_operand(indent, true)));
+                }
+                format!("[{}]", parts.join(", "))
+            }
+            Ty::Record(inner) => format!("Record<string, {}>", inner.format(indent)),
+            Ty::Union(members) => members
+                .iter()
+                .map(|m| m.format(indent))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Ty::Intersection(members) => members
+                .iter()
+                .map(|m| m.format_operand(indent, false))
+                .collect::<Vec<_>>()
+                .join(" & "),
+            Ty::Documented(_, inner) => inner.format(indent),
+        }
+    }
+
+    // Formats the type as an operand of `&` (or `[]` when `is_array` is true), adding parentheses
+    // if necessary.
+    fn format_operand(&self, indent: &str, is_array: bool) -> String {
+        let needs_parens = match self {
+            Ty::Documented(_, inner) => return inner.format_operand(indent, is_array),
+            Ty::Union(_) => true,
+            Ty::Intersection(_) => is_array,
+            _ => false,
+        };
+
+        if needs_parens {
+            format!("({})", self.format(indent))
+        } else {
+            self.format(indent)
+        }
+    }
+}
+
+impl Emitter<'_> {
+    fn emit_declaration(&mut self, name: &str, schema: &Value, root_name: Option<&str>) {
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+
+        if let Some(doc) = schema.as_object().and_then(|obj| doc_text(obj, root_name)) {
+            write_doc_comment(&mut self.output, &doc, "");
+        }
+
+        let ty = self.schema_type(schema);
+
+        match ty {
+            Ty::Object(_) if schema.as_object().is_some_and(is_interface) => {
+                self.output.push_str("export interface ");
+                self.output.push_str(name);
+                self.output.push(' ');
+                self.output.push_str(&ty.format(""));
+                self.output.push('\n');
+            }
+            Ty::Union(members) if members.iter().any(Ty::is_multiline) => {
+                self.output.push_str("export type ");
+                self.output.push_str(name);
+                self.output.push_str(" =");
+                for member in members {
+                    self.output.push('\n');
+                    if let Ty::Documented(doc, _) = &member {
+                        write_doc_comment(&mut self.output, doc, "  ");
+                    }
+                    self.output.push_str("  | ");
+                    self.output.push_str(&member.format_operand("    ", false));
+                }
+                self.output.push_str(";\n");
+            }
+            ty => {
+                self.output.push_str("export type ");
+                self.output.push_str(name);
+                self.output.push_str(" = ");
+                self.output.push_str(&ty.format(""));
+                self.output.push_str(";\n");
+            }
+        }
+    }
+
+    fn schema_type(&mut self, schema: &Value) -> Ty {
+        let obj = match schema {
+            Value::Object(obj) => obj,
+            Value::Bool(false) => return Ty::named("never"),
+            _ => return Ty::named("unknown"),
+        };
+
+        let mut parts = Vec::new();
+
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            parts.push(Ty::Named(self.reference(reference)));
+        }
+
+        if let Some(value) = obj.get("const") {
+            parts.push(literal(value));
+        } else if let Some(Value::Array(values)) = obj.get("enum") {
+            parts.push(Ty::union(values.iter().map(literal).collect()));
+        } else if let Some(ty) = self.instance_type(obj) {
+            parts.push(ty);
+        }
+
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(Value::Array(subschemas)) = obj.get(keyword) {
+                let members = subschemas
+                    .iter()
+                    .map(|s| {
+                        let ty = self.schema_type(s);
+                        match s.as_object().and_then(|obj| doc_text(obj, None)) {
+                            Some(doc) => Ty::Documented(doc, Box::new(ty)),
+                            None => ty,
+                        }
+                    })
+                    .collect();
+                parts.push(Ty::union(members));
+            }
+        }
+
+        if let Some(Value::Array(subschemas)) = obj.get("allOf") {
+            for subschema in subschemas {
+                parts.push(self.schema_type(subschema));
+            }
+        }
+
+        Ty::intersection(parts)
+    }
+
+    // Returns the type for the schema's `type` keyword (and the keywords that depend on it), or
+    // `None` if the schema does not restrict the instance type.
+    fn instance_type(&mut self, obj: &Map<String, Value>) -> Option<Ty> {
+        let types: Vec<&str> = match obj.get("type") {
+            Some(Value::String(ty)) => vec![ty],
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+            _ if ["properties", "additionalProperties", "patternProperties"]
+                .iter()
+                .any(|k| obj.contains_key(*k)) =>
+            {
+                vec!["object"]
+            }
+            _ if obj.contains_key("items") || obj.contains_key("prefixItems") => vec!["array"],
+            _ => return None,
+        };
+
+        let members = types
+            .into_iter()
+            .map(|ty| match ty {
+                "null" => Ty::named("null"),
+                "boolean" => Ty::named("boolean"),
+                "integer" | "number" => Ty::named("number"),
+                "string" => Ty::named("string"),
+                "array" => self.array_type(obj),
+                "object" => self.object_type(obj),
+                _ => Ty::named("unknown"),
+            })
+            .collect();
+
+        Some(Ty::union(members))
+    }
+
+    fn array_type(&mut self, obj: &Map<String, Value>) -> Ty {
+        // Draft 2020-12 uses `prefixItems`, earlier drafts use `items` with an array value
+        let (prefix_items, rest) = match (obj.get("prefixItems"), obj.get("items")) {
+            (Some(Value::Array(prefix)), rest) => (Some(prefix), rest),
+            (_, Some(Value::Array(prefix))) => (Some(prefix), obj.get("additionalItems")),
+            (_, items) => (None, items),
+        };
+
+        let is_closed_tuple = prefix_items.is_some_and(|prefix| {
+            obj.get("maxItems")
+                .and_then(Value::as_u64)
+                .is_some_and(|max| max <= prefix.len() as u64)
+        });
+
+        let rest = match rest {
+            Some(Value::Bool(false)) => None,
+            _ if is_closed_tuple => None,
+            Some(schema) => Some(self.schema_type(schema)),
+            None => Some(Ty::named("unknown")),
+        };
+
+        match prefix_items {
+            Some(prefix) => Ty::Tuple(
+                prefix.iter().map(|s| self.schema_type(s)).collect(),
+                rest.map(Box::new),
+            ),
+            None => Ty::Array(Box::new(rest.unwrap_or_else(|| Ty::named("never")))),
+        }
+    }
+
+    fn object_type(&mut self, obj: &Map<String, Value>) -> Ty {
+        let required = obj
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect::<BTreeSet<_>>())
+            .unwrap_or_default();
+
+        let properties = obj
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, schema)| Property {
+                        name: name.clone(),
+                        optional: !required.contains(name.as_str()),
+                        readonly: schema.get("readOnly") == Some(&Value::Bool(true)),
+                        doc: schema.as_object().and_then(|s| doc_text(s, None)),
+                        ty: self.schema_type(schema),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let mut additional = Vec::new();
+        let additional_properties = obj
+            .get("additionalProperties")
+            .or_else(|| obj.get("unevaluatedProperties"));
+        match additional_properties {
+            Some(Value::Bool(false)) => {}
+            Some(schema) => additional.push(self.schema_type(schema)),
+            None if properties.is_empty() => additional.push(Ty::named("unknown")),
+            None => {}
+        }
+        if let Some(Value::Object(patterns)) = obj.get("patternProperties") {
+            for schema in patterns.values() {
+                additional.push(self.schema_type(schema));
+            }
+        }
+
+        let record = (!additional.is_empty()).then(|| Ty::Record(Box::new(Ty::union(additional))));
+
+        match (properties.is_empty(), record) {
+            (true, Some(record)) => record,
+            (false, Some(record)) => Ty::Intersection(vec![Ty::Object(properties), record]),
+            (_, None) => Ty::Object(properties),
+        }
+    }
+
+    fn reference(&mut self, reference: &str) -> String {
+        if reference == "#" {
+            return self.root_name.clone();
+        }
+
+        if self.queued.insert(reference.to_owned()) {
+            self.pending.push_back(reference.to_owned());
+        }
+
+        self.ref_type_name(reference)
+    }
+
+    fn ref_type_name(&self, reference: &str) -> String {
+        let decoded = reference
+            .strip_prefix('#')
+            .and_then(crate::encoding::percent_decode);
+        let pointer = decoded.as_deref().unwrap_or(reference);
+
+        match pointer.rsplit('/').next() {
+            Some(segment) if !pointer.is_empty() => {
+                type_name(&segment.replace("~1", "/").replace("~0", "~"))
+            }
+            _ => self.root_name.clone(),
+        }
+    }
+}
+
+// Whether a schema should be emitted as an `interface`, i.e. it describes an object with a fixed
+// set of properties and nothing else.
+fn is_interface(obj: &Map<String, Value>) -> bool {
+    let has_properties = obj
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_some_and(|p| !p.is_empty());
+    let is_object = matches!(obj.get("type"), Some(Value::String(t)) if t == "object")
+        || !obj.contains_key("type");
+    let has_other_keywords = [
+        "$ref",
+        "const",
+        "enum",
+        "oneOf",
+        "anyOf",
+        "allOf",
+        "patternProperties",
+    ]
+    .iter()
+    .any(|k| obj.contains_key(*k));
+    let has_additional = obj
+        .get("additionalProperties")
+        .or_else(|| obj.get("unevaluatedProperties"))
+        .is_some_and(|a| a != &Value::Bool(false));
+
+    has_properties && is_object && !has_other_keywords && !has_additional
+}
+
+fn literal(value: &Value) -> Ty {
+    Ty::Named(value.to_string())
+}
+
+// Returns the text of a doc comment for the schema, omitting the title if it is just the name of
+// the type being declared.
+fn doc_text(obj: &Map<String, Value>, type_name: Option<&str>) -> Option<String> {
+    let mut paragraphs = Vec::new();
+
+    if let Some(title) = obj.get("title").and_then(Value::as_str) {
+        if !title.is_empty() && Some(title) != type_name {
+            paragraphs.push(title.to_owned());
+        }
+    }
+    if let Some(description) = obj.get("description").and_then(Value::as_str) {
+        if !description.is_empty() {
+            paragraphs.push(description.to_owned());
+        }
+    }
+    if obj.get("deprecated") == Some(&Value::Bool(true)) {
+        paragraphs.push("@deprecated".to_owned());
+    }
+
+    (!paragraphs.is_empty()).then(|| paragraphs.join("\n\n"))
+}
+
+fn write_doc_comment(out: &mut String, doc: &str, indent: &str) {
+    let doc = doc.replace("*/", "*\\/");
+
+    if !doc.contains('\n') {
+        out.push_str(indent);
+        out.push_str("/** ");
+        out.push_str(&doc);
+        out.push_str(" */\n");
+        return;
+    }
+
+    out.push_str(indent);
+    out.push_str("/**\n");
+    for line in doc.lines() {
+        out.push_str(indent);
+        if line.is_empty() {
+            out.push_str(" *\n");
+        } else {
+            out.push_str(" * ");
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out.push_str(indent);
+    out.push_str(" */\n");
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_owned()
+    } else {
+        Value::from(name).to_string()
+    }
+}
+
+// Converts a schema name into a valid TypeScript identifier
+fn type_name(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$') {
+        result.insert(0, '_');
+    }
+
+    result
+}
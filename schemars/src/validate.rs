@@ -0,0 +1,956 @@
+/*!
+Validation of JSON values against a [`Schema`].
+
+This module is only available when the `validate` feature flag is enabled.
+
+The validator supports the JSON Schema keywords that schemars itself generates (along with a few
+closely-related keywords), so that values can be checked against schemas generated by schemars
+without depending on a separate JSON Schema implementation. It is not a fully-compliant JSON
+Schema validator - in particular:
+- `$ref` values are only resolved when they are URI fragments (e.g. `#/$defs/MyStruct`), which
+  are resolved against the root schema that `validate` was called on.
+- `pattern` and `patternProperties` are evaluated using Rust's regular expression syntax rather
+  than ECMA-262.
+- only the `format` values that schemars generates are checked, and any other formats are
+  assumed to be valid.
+
+# Example
+```
+use schemars::{schema_for, JsonSchema};
+use serde_json::json;
+
+#[derive(JsonSchema)]
+struct MyStruct {
+    my_int: u8,
+    my_string: String,
+}
+
+let schema = schema_for!(MyStruct);
+
+assert!(schema.validate(&json!({ "my_int": 1, "my_string": "hello" })).is_ok());
+
+let errors = schema.validate(&json!({ "my_int": 256 })).unwrap_err();
+assert_eq!(errors.len(), 2);
+assert_eq!(errors[0].instance_path, "");
+assert_eq!(errors[0].schema_path, "/required");
+assert_eq!(errors[1].instance_path, "/my_int");
+assert_eq!(errors[1].schema_path, "/properties/my_int/maximum");
+```
+*/
+
+use crate::_alloc_prelude::*;
+use crate::encoding::push_pointer_segment;
+use crate::Schema;
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::cmp::Ordering;
+use core::fmt;
+use regex_automata::meta::Regex;
+use serde_json::{Map, Number, Value};
+
+/// An error describing why a JSON value failed validation against a [`Schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ValidationError {
+    /// A JSON Pointer to the part of the validated value that is invalid.
+    ///
+    /// This is an empty string when the root value is invalid.
+    pub instance_path: String,
+    /// A JSON Pointer to the schema keyword that the value failed to satisfy.
+    ///
+    /// When validation follows a `$ref`, the path continues through the `$ref` keyword, e.g.
+    /// `/properties/inner/$ref/required`.
+    pub schema_path: String,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (instance path: \"{}\", schema path: \"{}\")",
+            self.message, self.instance_path, self.schema_path
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+impl Schema {
+    /// Validates the given JSON value against this schema.
+    ///
+    /// Any `$ref`s within the schema are resolved against `self`, so this should typically be
+    /// called on a root schema (e.g. one returned by [`schema_for!`](crate::schema_for)) rather
+    /// than on a subschema.
+    ///
+    /// If the value is invalid, this returns all validation errors that were found. See the
+    /// [module documentation](crate::validate) for limitations of the validator.
+    ///
+    /// # Example
+    /// ```
+    /// use schemars::json_schema;
+    /// use serde_json::json;
+    ///
+    /// let schema = json_schema!({
+    ///     "type": "array",
+    ///     "items": { "type": "string" }
+    /// });
+    ///
+    /// assert!(schema.validate(&json!(["a", "b"])).is_ok());
+    ///
+    /// let errors = schema.validate(&json!(["a", 1])).unwrap_err();
+    /// assert_eq!(errors[0].instance_path, "/1");
+    /// assert_eq!(errors[0].schema_path, "/items/type");
+    /// ```
+    pub fn validate(&self, instance: &Value) -> Result<(), Vec<ValidationError>> {
+        let mut validator = Validator {
+            root: self,
+            instance_path: String::new(),
+            schema_path: String::new(),
+            errors: Vec::new(),
+            ref_stack: Vec::new(),
+            regexes: BTreeMap::new(),
+        };
+
+        validator.validate(self.as_value(), instance);
+
+        if validator.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validator.errors)
+        }
+    }
+}
+
+struct Validator<'s> {
+    root: &'s Schema,
+    instance_path: String,
+    schema_path: String,
+    errors: Vec<ValidationError>,
+    // Each `$ref` currently being followed, with the address of the value it is being applied to.
+    // This is used to detect references cycles that would never terminate.
+    ref_stack: Vec<(&'s str, *const Value)>,
+    regexes: BTreeMap<&'s str, Option<Regex>>,
+}
+
+// Property names of an object instance that were successfully evaluated by a schema, as required
+// for `unevaluatedProperties`.
+type Evaluated<'i> = BTreeSet<&'i str>;
+
+impl<'s> Validator<'s> {
+    fn validate<'i>(&mut self, schema: &'s Value, instance: &'i Value) -> Evaluated<'i> {
+        let obj = match schema {
+            Value::Object(obj) => obj,
+            Value::Bool(false) => {
+                self.error("no values are allowed by the `false` schema");
+                return Evaluated::new();
+            }
+            _ => return Evaluated::new(),
+        };
+
+        let mut evaluated = Evaluated::new();
+
+        if let Some(Value::String(reference)) = obj.get("$ref") {
+            evaluated.extend(self.validate_ref(reference, instance));
+        }
+
+        self.validate_type(obj, instance);
+        self.validate_enum_const(obj, instance);
+
+        match instance {
+            Value::Number(n) => self.validate_number(obj, n),
+            Value::String(s) => self.validate_string(obj, s),
+            Value::Array(a) => self.validate_array(obj, a),
+            Value::Object(o) => evaluated.extend(self.validate_object(obj, o)),
+            Value::Null | Value::Bool(_) => {}
+        }
+
+        evaluated.extend(self.validate_applicators(obj, instance));
+
+        if let (Some(unevaluated), Value::Object(o)) = (obj.get("unevaluatedProperties"), instance)
+        {
+            for (key, value) in o {
+                if !evaluated.contains(key.as_str()) {
+                    self.in_schema_path("unevaluatedProperties", |v| {
+                        v.in_instance_path(key, |v| v.validate(unevaluated, value));
+                    });
+                }
+            }
+            evaluated.extend(o.keys().map(String::as_str));
+        }
+
+        evaluated
+    }
+
+    fn validate_ref<'i>(&mut self, reference: &'s str, instance: &'i Value) -> Evaluated<'i> {
+        let stack_entry = (reference, instance as *const Value);
+        if self.ref_stack.contains(&stack_entry) {
+            // Following this reference again would never terminate, and could not produce any
+            // errors that won't already be reported
+            return Evaluated::new();
+        }
+
+        self.in_schema_path("$ref", |v| {
+            let Some(target) = v.root.resolve_ref(reference) else {
+                v.error(format!("could not resolve reference `{reference}`"));
+                return Evaluated::new();
+            };
+
+            v.ref_stack.push(stack_entry);
+            let evaluated = v.validate(target.as_value(), instance);
+            v.ref_stack.pop();
+            evaluated
+        })
+    }
+
+    fn validate_type(&mut self, obj: &'s Map<String, Value>, instance: &Value) {
+        let matches = match obj.get("type") {
+            Some(Value::String(ty)) => has_type(ty, instance),
+            Some(Value::Array(types)) => types
+                .iter()
+                .any(|ty| ty.as_str().is_some_and(|ty| has_type(ty, instance))),
+            _ => return,
+        };
+
+        if !matches {
+            self.in_schema_path("type", |v| {
+                v.error(format!("{instance} is not of type {}", obj["type"]));
+            });
+        }
+    }
+
+    fn validate_enum_const(&mut self, obj: &'s Map<String, Value>, instance: &Value) {
+        if let Some(Value::Array(values)) = obj.get("enum") {
+            if !values.iter().any(|value| json_eq(value, instance)) {
+                self.in_schema_path("enum", |v| {
+                    v.error(format!("{instance} is not one of {}", obj["enum"]));
+                });
+            }
+        }
+
+        if let Some(value) = obj.get("const") {
+            if !json_eq(value, instance) {
+                self.in_schema_path("const", |v| {
+                    v.error(format!("{value} was expected"));
+                });
+            }
+        }
+    }
+
+    fn validate_number(&mut self, obj: &'s Map<String, Value>, n: &Number) {
+        let bound = |keyword: &str| match obj.get(keyword) {
+            Some(Value::Number(bound)) => Some(bound),
+            _ => None,
+        };
+
+        // `exclusiveMinimum`/`exclusiveMaximum` may be booleans in draft 4, in which case they
+        // modify the behaviour of `minimum`/`maximum`
+        if let Some(minimum) = bound("minimum") {
+            let exclusive = obj.get("exclusiveMinimum") == Some(&Value::Bool(true));
+            let invalid = match compare_numbers(n, minimum) {
+                Some(Ordering::Less) => true,
+                Some(Ordering::Equal) => exclusive,
+                _ => false,
+            };
+            if invalid {
+                self.in_schema_path("minimum", |v| {
+                    let or_equal = if exclusive { "" } else { " or equal to" };
+                    v.error(format!("{n} is not greater than{or_equal} {minimum}"));
+                });
+            }
+        }
+
+        if let Some(maximum) = bound("maximum") {
+            let exclusive = obj.get("exclusiveMaximum") == Some(&Value::Bool(true));
+            let invalid = match compare_numbers(n, maximum) {
+                Some(Ordering::Greater) => true,
+                Some(Ordering::Equal) => exclusive,
+                _ => false,
+            };
+            if invalid {
+                self.in_schema_path("maximum", |v| {
+                    let or_equal = if exclusive { "" } else { " or equal to" };
+                    v.error(format!("{n} is not less than{or_equal} {maximum}"));
+                });
+            }
+        }
+
+        if let Some(minimum) = bound("exclusiveMinimum") {
+            if compare_numbers(n, minimum) != Some(Ordering::Greater) {
+                self.in_schema_path("exclusiveMinimum", |v| {
+                    v.error(format!("{n} is not greater than {minimum}"));
+                });
+            }
+        }
+
+        if let Some(maximum) = bound("exclusiveMaximum") {
+            if compare_numbers(n, maximum) != Some(Ordering::Less) {
+                self.in_schema_path("exclusiveMaximum", |v| {
+                    v.error(format!("{n} is not less than {maximum}"));
+                });
+            }
+        }
+
+        if let Some(divisor) = bound("multipleOf") {
+            if !is_multiple_of(n, divisor) {
+                self.in_schema_path("multipleOf", |v| {
+                    v.error(format!("{n} is not a multiple of {divisor}"));
+                });
+            }
+        }
+    }
+
+    fn validate_string(&mut self, obj: &'s Map<String, Value>, s: &str) {
+        let len = s.chars().count() as u64;
+
+        if let Some(min) = obj.get("minLength").and_then(Value::as_u64) {
+            if len < min {
+                self.in_schema_path("minLength", |v| {
+                    v.error(format!("{s:?} is shorter than {min} characters"));
+                });
+            }
+        }
+
+        if let Some(max) = obj.get("maxLength").and_then(Value::as_u64) {
+            if len > max {
+                self.in_schema_path("maxLength", |v| {
+                    v.error(format!("{s:?} is longer than {max} characters"));
+                });
+            }
+        }
+
+        if let Some(Value::String(pattern)) = obj.get("pattern") {
+            match self.regex_is_match(pattern, s) {
+                Some(true) => {}
+                Some(false) => self.in_schema_path("pattern", |v| {
+                    v.error(format!("{s:?} does not match {pattern:?}"));
+                }),
+                None => self.in_schema_path("pattern", |v| {
+                    v.error(format!("{pattern:?} is not a valid regular expression"));
+                }),
+            }
+        }
+
+        if let Some(Value::String(format)) = obj.get("format") {
+            let valid = if format == "regex" {
+                Regex::new(s).is_ok()
+            } else {
+                formats::is_valid(format, s)
+            };
+
+            if !valid {
+                self.in_schema_path("format", |v| {
+                    v.error(format!("{s:?} is not a valid {format:?}"));
+                });
+            }
+        }
+    }
+
+    fn validate_array(&mut self, obj: &'s Map<String, Value>, array: &[Value]) {
+        let len = array.len() as u64;
+
+        if let Some(min) = obj.get("minItems").and_then(Value::as_u64) {
+            if len < min {
+                self.in_schema_path("minItems", |v| {
+                    v.error(format!("array has fewer than {min} items"));
+                });
+            }
+        }
+
+        if let Some(max) = obj.get("maxItems").and_then(Value::as_u64) {
+            if len > max {
+                self.in_schema_path("maxItems", |v| {
+                    v.error(format!("array has more than {max} items"));
+                });
+            }
+        }
+
+        if obj.get("uniqueItems") == Some(&Value::Bool(true)) {
+            let has_duplicate = array
+                .iter()
+                .enumerate()
+                .any(|(i, a)| array[..i].iter().any(|b| json_eq(a, b)));
+            if has_duplicate {
+                self.in_schema_path("uniqueItems", |v| {
+                    v.error("array has non-unique items");
+                });
+            }
+        }
+
+        // `items` may be an array of schemas in draft 2019-09 and earlier, in which case it
+        // behaves like `prefixItems`, and `additionalItems` behaves like `items`.
+        let (prefix_keyword, rest_keyword) = match obj.get("items") {
+            Some(Value::Array(_)) => ("items", "additionalItems"),
+            _ => ("prefixItems", "items"),
+        };
+
+        let mut prefix_len = 0;
+        if let Some(Value::Array(prefix_items)) = obj.get(prefix_keyword) {
+            prefix_len = prefix_items.len();
+            self.in_schema_path(prefix_keyword, |v| {
+                for (index, (schema, item)) in prefix_items.iter().zip(array).enumerate() {
+                    v.in_schema_path(&index.to_string(), |v| {
+                        v.in_instance_path(&index.to_string(), |v| v.validate(schema, item));
+                    });
+                }
+            });
+        }
+
+        if let Some(schema) = obj.get(rest_keyword) {
+            self.in_schema_path(rest_keyword, |v| {
+                for (index, item) in array.iter().enumerate().skip(prefix_len) {
+                    v.in_instance_path(&index.to_string(), |v| v.validate(schema, item));
+                }
+            });
+        }
+
+        if let Some(contains) = obj.get("contains") {
+            let count = array
+                .iter()
+                .filter(|item| self.is_valid(contains, item))
+                .count() as u64;
+            let min = obj.get("minContains").and_then(Value::as_u64).unwrap_or(1);
+            let max = obj.get("maxContains").and_then(Value::as_u64);
+
+            if count < min || max.is_some_and(|max| count > max) {
+                self.in_schema_path("contains", |v| {
+                    v.error(format!(
+                        "array contains {count} items matching the `contains` schema"
+                    ));
+                });
+            }
+        }
+    }
+
+    fn validate_object<'i>(
+        &mut self,
+        obj: &'s Map<String, Value>,
+        object: &'i Map<String, Value>,
+    ) -> Evaluated<'i> {
+        let mut evaluated = Evaluated::new();
+        let len = object.len() as u64;
+
+        if let Some(min) = obj.get("minProperties").and_then(Value::as_u64) {
+            if len < min {
+                self.in_schema_path("minProperties", |v| {
+                    v.error(format!("object has fewer than {min} properties"));
+                });
+            }
+        }
+
+        if let Some(max) = obj.get("maxProperties").and_then(Value::as_u64) {
+            if len > max {
+                self.in_schema_path("maxProperties", |v| {
+                    v.error(format!("object has more than {max} properties"));
+                });
+            }
+        }
+
+        if let Some(Value::Array(required)) = obj.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    self.in_schema_path("required", |v| {
+                        v.error(format!("{name:?} is a required property"));
+                    });
+                }
+            }
+        }
+
+        if let Some(Value::Object(dependent_required)) = obj.get("dependentRequired") {
+            for (key, required) in dependent_required {
+                if !object.contains_key(key) {
+                    continue;
+                }
+                for name in required.as_array().into_iter().flatten() {
+                    let name = name.as_str().unwrap_or_default();
+                    if !object.contains_key(name) {
+                        self.in_schema_path("dependentRequired", |v| {
+                            v.error(format!("{name:?} is required when {key:?} is present"));
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(property_names) = obj.get("propertyNames") {
+            for key in object.keys() {
+                let key_value = Value::String(key.clone());
+                self.in_schema_path("propertyNames", |v| v.validate(property_names, &key_value));
+            }
+        }
+
+        if let Some(Value::Object(properties)) = obj.get("properties") {
+            for (key, value) in object {
+                if let Some(schema) = properties.get(key) {
+                    self.in_schema_path("properties", |v| {
+                        v.in_schema_path(key, |v| {
+                            v.in_instance_path(key, |v| v.validate(schema, value));
+                        });
+                    });
+                    evaluated.insert(key.as_str());
+                }
+            }
+        }
+
+        if let Some(Value::Object(pattern_properties)) = obj.get("patternProperties") {
+            for (pattern, schema) in pattern_properties {
+                for (key, value) in object {
+                    if self.regex_is_match(pattern, key) == Some(true) {
+                        self.in_schema_path("patternProperties", |v| {
+                            v.in_schema_path(pattern, |v| {
+                                v.in_instance_path(key, |v| v.validate(schema, value));
+                            });
+                        });
+                        evaluated.insert(key.as_str());
+                    }
+                }
+            }
+        }
+
+        if let Some(schema) = obj.get("additionalProperties") {
+            for (key, value) in object {
+                if !evaluated.contains(key.as_str()) {
+                    self.in_schema_path("additionalProperties", |v| {
+                        v.in_instance_path(key, |v| v.validate(schema, value));
+                    });
+                }
+            }
+            evaluated.extend(object.keys().map(String::as_str));
+        }
+
+        evaluated
+    }
+
+    fn validate_applicators<'i>(
+        &mut self,
+        obj: &'s Map<String, Value>,
+        instance: &'i Value,
+    ) -> Evaluated<'i> {
+        let mut evaluated = Evaluated::new();
+
+        if let Some(Value::Array(all_of)) = obj.get("allOf") {
+            self.in_schema_path("allOf", |v| {
+                for (index, schema) in all_of.iter().enumerate() {
+                    v.in_schema_path(&index.to_string(), |v| {
+                        evaluated.extend(v.validate(schema, instance));
+                    });
+                }
+            });
+        }
+
+        if let Some(Value::Array(any_of)) = obj.get("anyOf") {
+            let mut valid_count = 0;
+            for schema in any_of {
+                if let Some(e) = self.evaluate_if_valid(schema, instance) {
+                    valid_count += 1;
+                    evaluated.extend(e);
+                }
+            }
+
+            if valid_count == 0 {
+                self.in_schema_path("anyOf", |v| {
+                    v.error(format!("{instance} is not valid under any of the schemas"));
+                });
+            }
+        }
+
+        if let Some(Value::Array(one_of)) = obj.get("oneOf") {
+            let mut valid_count = 0;
+            for schema in one_of {
+                if let Some(e) = self.evaluate_if_valid(schema, instance) {
+                    valid_count += 1;
+                    evaluated.extend(e);
+                }
+            }
+
+            if valid_count != 1 {
+                self.in_schema_path("oneOf", |v| {
+                    v.error(if valid_count == 0 {
+                        format!("{instance} is not valid under any of the schemas")
+                    } else {
+                        format!("{instance} is valid under more than one of the schemas")
+                    });
+                });
+            }
+        }
+
+        if let Some(not) = obj.get("not") {
+            if self.is_valid(not, instance) {
+                self.in_schema_path("not", |v| {
+                    v.error(format!("{instance} should not be valid under {not}"));
+                });
+            }
+        }
+
+        if let Some(if_schema) = obj.get("if") {
+            if let Some(e) = self.evaluate_if_valid(if_schema, instance) {
+                evaluated.extend(e);
+                if let Some(then) = obj.get("then") {
+                    self.in_schema_path("then", |v| evaluated.extend(v.validate(then, instance)));
+                }
+            } else if let Some(else_schema) = obj.get("else") {
+                self.in_schema_path("else", |v| {
+                    evaluated.extend(v.validate(else_schema, instance));
+                });
+            }
+        }
+
+        evaluated
+    }
+
+    /// Validates the instance without recording any errors, returning the evaluated properties
+    /// only if the instance is valid.
+    fn evaluate_if_valid<'i>(
+        &mut self,
+        schema: &'s Value,
+        instance: &'i Value,
+    ) -> Option<Evaluated<'i>> {
+        let error_count = self.errors.len();
+        let evaluated = self.validate(schema, instance);
+        let is_valid = self.errors.len() == error_count;
+        self.errors.truncate(error_count);
+        is_valid.then_some(evaluated)
+    }
+
+    fn is_valid(&mut self, schema: &'s Value, instance: &Value) -> bool {
+        self.evaluate_if_valid(schema, instance).is_some()
+    }
+
+    fn regex_is_match(&mut self, pattern: &'s str, haystack: &str) -> Option<bool> {
+        self.regexes
+            .entry(pattern)
+            .or_insert_with(|| Regex::new(pattern).ok())
+            .as_ref()
+            .map(|regex| regex.is_match(haystack))
+    }
+
+    fn in_schema_path<R>(&mut self, segment: &str, f: impl FnOnce(&mut Self) -> R) -> R {
+        let len = self.schema_path.len();
+        push_pointer_segment(&mut self.schema_path, segment);
+        let result = f(self);
+        self.schema_path.truncate(len);
+        result
+    }
+
+    fn in_instance_path<R>(&mut self, segment: &str, f: impl FnOnce(&mut Self) -> R) -> R {
+        let len = self.instance_path.len();
+        push_pointer_segment(&mut self.instance_path, segment);
+        let result = f(self);
+        self.instance_path.truncate(len);
+        result
+    }
+
+    fn error(&mut self, message: impl Into<String>) {
+        self.errors.push(ValidationError {
+            instance_path: self.instance_path.clone(),
+            schema_path: self.schema_path.clone(),
+            message: message.into(),
+        });
+    }
+}
+
+fn has_type(ty: &str, instance: &Value) -> bool {
+    match ty {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        "integer" => instance.as_number().is_some_and(is_integer),
+        _ => false,
+    }
+}
+
+fn is_integer(n: &Number) -> bool {
+    n.is_i64() || n.is_u64() || n.as_f64().is_some_and(is_integral)
+}
+
+fn is_integral(f: f64) -> bool {
+    // Every finite f64 with a magnitude of at least 2^52 is an integer
+    const LIMIT: f64 = 4_503_599_627_370_496.0;
+
+    if !f.is_finite() {
+        false
+    } else if !(-LIMIT..=LIMIT).contains(&f) {
+        true
+    } else {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::float_cmp
+        )]
+        let is_integral = f as i64 as f64 == f;
+        is_integral
+    }
+}
+
+fn is_multiple_of(n: &Number, divisor: &Number) -> bool {
+    if let (Some(n), Some(divisor)) = (n.as_i64(), divisor.as_i64()) {
+        return divisor != 0 && n % divisor == 0;
+    }
+    if let (Some(n), Some(divisor)) = (n.as_u64(), divisor.as_u64()) {
+        return divisor != 0 && n % divisor == 0;
+    }
+
+    match (n.as_f64(), divisor.as_f64()) {
+        (Some(n), Some(divisor)) if divisor != 0.0 => is_integral(n / divisor),
+        _ => false,
+    }
+}
+
+fn compare_numbers(a: &Number, b: &Number) -> Option<Ordering> {
+    if let (Some(a), Some(b)) = (a.as_i64(), b.as_i64()) {
+        return Some(a.cmp(&b));
+    }
+    if let (Some(a), Some(b)) = (a.as_u64(), b.as_u64()) {
+        return Some(a.cmp(&b));
+    }
+    // One is a negative integer and the other is an integer too large for i64
+    if (a.is_u64() && b.is_i64()) || (a.is_i64() && b.is_u64()) {
+        return Some(if a.is_u64() {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        });
+    }
+
+    a.as_f64()?.partial_cmp(&b.as_f64()?)
+}
+
+/// Compares JSON values for equality, treating numbers with the same mathematical value as equal
+/// (e.g. `1` and `1.0`).
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b) == Some(Ordering::Equal),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| json_eq(a, b)))
+        }
+        _ => a == b,
+    }
+}
+
+mod formats {
+    pub(super) fn is_valid(format: &str, s: &str) -> bool {
+        match format {
+            "date-time" => is_date_time(s),
+            "date" => is_date(s),
+            "time" => is_time(s),
+            "duration" => is_duration(s),
+            "uuid" => is_uuid(s),
+            "ipv4" => is_ipv4(s),
+            "ipv6" => is_ipv6(s),
+            "email" => is_email(s),
+            "uri" => is_uri(s),
+            _ => true,
+        }
+    }
+
+    fn digits(s: &str, len: usize) -> Option<u32> {
+        if s.len() == len && s.bytes().all(|b| b.is_ascii_digit()) {
+            s.parse().ok()
+        } else {
+            None
+        }
+    }
+
+    fn is_date_time(s: &str) -> bool {
+        match s.find(['T', 't']) {
+            Some(index) => is_date(&s[..index]) && is_time(&s[index + 1..]),
+            None => false,
+        }
+    }
+
+    fn is_date(s: &str) -> bool {
+        let mut parts = s.split('-');
+        let (Some(year), Some(month), Some(day), None) = (
+            parts.next().and_then(|p| digits(p, 4)),
+            parts.next().and_then(|p| digits(p, 2)),
+            parts.next().and_then(|p| digits(p, 2)),
+            parts.next(),
+        ) else {
+            return false;
+        };
+
+        let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if is_leap_year => 29,
+            2 => 28,
+            _ => return false,
+        };
+
+        (1..=days_in_month).contains(&day)
+    }
+
+    fn is_time(s: &str) -> bool {
+        let (time, offset) = if let Some(time) = s.strip_suffix(['Z', 'z']) {
+            (time, None)
+        } else if let Some(index) = s.rfind(['+', '-']) {
+            (&s[..index], Some(&s[index + 1..]))
+        } else {
+            return false;
+        };
+
+        if let Some(offset) = offset {
+            let Some((hour, minute)) = offset.split_once(':') else {
+                return false;
+            };
+            if !digits(hour, 2).is_some_and(|h| h < 24)
+                || !digits(minute, 2).is_some_and(|m| m < 60)
+            {
+                return false;
+            }
+        }
+
+        let (time, fraction) = time.split_once('.').unwrap_or((time, "0"));
+        if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+
+        let mut parts = time.split(':');
+        matches!(
+            (
+                parts.next().and_then(|p| digits(p, 2)),
+                parts.next().and_then(|p| digits(p, 2)),
+                parts.next().and_then(|p| digits(p, 2)),
+                parts.next(),
+            ),
+            // allow leap seconds
+            (Some(0..=23), Some(0..=59), Some(0..=60), None)
+        )
+    }
+
+    fn is_duration(s: &str) -> bool {
+        // Each component is some digits followed by a unit, and units must be in order
+        fn components(mut s: &str, units: &str) -> bool {
+            let mut remaining_units = units;
+            while !s.is_empty() {
+                let digit_count = s.bytes().take_while(u8::is_ascii_digit).count();
+                let Some(unit) = s[digit_count..].chars().next() else {
+                    return false;
+                };
+                let Some(unit_index) = remaining_units.find(unit) else {
+                    return false;
+                };
+                if digit_count == 0 {
+                    return false;
+                }
+
+                remaining_units = &remaining_units[unit_index + 1..];
+                s = &s[digit_count + 1..];
+            }
+            true
+        }
+
+        let Some(s) = s.strip_prefix('P') else {
+            return false;
+        };
+
+        let (date, time) = match s.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (s, None),
+        };
+
+        if time == Some("") || (date.is_empty() && time.is_none()) {
+            return false;
+        }
+
+        if let Some(weeks) = date.strip_suffix('W') {
+            return time.is_none()
+                && !weeks.is_empty()
+                && weeks.bytes().all(|b| b.is_ascii_digit());
+        }
+
+        components(date, "YMD") && time.map_or(true, |time| components(time, "HMS"))
+    }
+
+    fn is_uuid(s: &str) -> bool {
+        let groups: [usize; 5] = [8, 4, 4, 4, 12];
+        let mut parts = s.split('-');
+        groups.iter().all(|len| {
+            parts
+                .next()
+                .is_some_and(|p| p.len() == *len && p.bytes().all(|b| b.is_ascii_hexdigit()))
+        }) && parts.next().is_none()
+    }
+
+    fn is_ipv4(s: &str) -> bool {
+        let mut parts = s.split('.');
+        (0..4).all(|_| {
+            parts.next().is_some_and(|p| {
+                !p.is_empty()
+                    && p.len() <= 3
+                    && p.bytes().all(|b| b.is_ascii_digit())
+                    && (p == "0" || !p.starts_with('0'))
+                    && p.parse::<u8>().is_ok()
+            })
+        }) && parts.next().is_none()
+    }
+
+    fn is_ipv6(s: &str) -> bool {
+        fn groups(s: &str) -> Option<usize> {
+            if s.is_empty() {
+                return Some(0);
+            }
+
+            let mut count = 0;
+            let mut parts = s.split(':').peekable();
+            while let Some(part) = parts.next() {
+                if parts.peek().is_none() && part.contains('.') {
+                    // embedded IPv4 address counts as 2 groups
+                    return is_ipv4(part).then_some(count + 2);
+                }
+                if part.is_empty() || part.len() > 4 || !part.bytes().all(|b| b.is_ascii_hexdigit())
+                {
+                    return None;
+                }
+                count += 1;
+            }
+            Some(count)
+        }
+
+        match s.split_once("::") {
+            Some((before, after)) => {
+                if after.contains("::") {
+                    return false;
+                }
+                matches!((groups(before), groups(after)), (Some(b), Some(a)) if b + a < 8)
+            }
+            None => groups(s) == Some(8),
+        }
+    }
+
+    fn is_email(s: &str) -> bool {
+        match s.rsplit_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.is_empty()
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+                    && !s.chars().any(char::is_whitespace)
+            }
+            None => false,
+        }
+    }
+
+    fn is_uri(s: &str) -> bool {
+        match s.split_once(':') {
+            Some((scheme, _)) => {
+                scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+                    && !s.chars().any(|c| c.is_whitespace() || c.is_control())
+            }
+            None => false,
+        }
+    }
+}
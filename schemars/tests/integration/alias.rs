@@ -0,0 +1,100 @@
+use crate::prelude::*;
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+struct Struct {
+    #[serde(alias = "full_name")]
+    name: String,
+    #[serde(default, alias = "years", alias = "age_in_years")]
+    age: u32,
+    #[serde(rename = "email_address", alias = "email")]
+    email_address: Option<String>,
+}
+
+#[test]
+fn struct_alias() {
+    test!(Struct)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_de_roundtrip([
+            json!({ "name": "a" }),
+            json!({ "full_name": "a", "years": 1 }),
+            json!({ "name": "a", "age_in_years": 1, "email": "a@example.com" }),
+        ])
+        .assert_rejects_de([
+            json!({}),
+            json!({ "name": "a", "full_name": "a" }),
+            json!({ "name": "a", "age": 1, "years": 1 }),
+            json!({ "name": "a", "years": 1, "age_in_years": 1 }),
+            json!({ "name": "a", "email": "a", "email_address": "a" }),
+        ])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+enum External {
+    #[default]
+    #[serde(alias = "U")]
+    Unit,
+    #[serde(alias = "S")]
+    Struct { value: i32 },
+    /// A documented unit variant
+    #[serde(alias = "D")]
+    DocumentedUnit,
+}
+
+#[test]
+fn enum_external_alias() {
+    test!(External)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_de_roundtrip([
+            json!("U"),
+            json!("D"),
+            json!({ "S": { "value": 1 } }),
+            json!({ "Struct": { "value": 1 } }),
+        ])
+        .assert_rejects_de([json!({ "Struct": { "value": 1 }, "S": { "value": 1 } })])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+#[serde(tag = "type")]
+enum Internal {
+    #[default]
+    #[serde(alias = "unit")]
+    Unit,
+    #[serde(alias = "struct", alias = "s")]
+    Struct { value: i32 },
+}
+
+#[test]
+fn enum_internal_alias() {
+    test!(Internal)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_de_roundtrip([
+            json!({ "type": "unit" }),
+            json!({ "type": "s", "value": 1 }),
+        ])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    #[default]
+    #[serde(alias = "unit")]
+    Unit,
+    #[serde(alias = "int")]
+    Int(i32),
+}
+
+#[test]
+fn enum_adjacent_alias() {
+    test!(Adjacent)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_de_roundtrip([json!({ "t": "unit" }), json!({ "t": "int", "c": 1 })])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
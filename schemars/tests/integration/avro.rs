@@ -0,0 +1,213 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::avro::{self, AvroError};
+use schemars::SchemaGenerator;
+use std::collections::BTreeMap;
+
+// Fields are declared in alphabetical order, so that record fields have the same order with or
+// without the `preserve_order` feature.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct Order {
+    /// Number of items
+    count: i32,
+    customer: Option<Customer>,
+    id: i64,
+    metadata: BTreeMap<String, u8>,
+    price: f32,
+    shape: Shape,
+    status: Status,
+    tree: Tree,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct Customer {
+    name: String,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(tag = "type")]
+enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+enum Status {
+    /// Not yet shipped
+    Pending,
+    /// Delivered to the customer
+    Delivered,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct Tree {
+    children: Vec<Tree>,
+    value: Option<u64>,
+}
+
+#[test]
+fn avro_schema() {
+    let schema = avro::schema_for::<Order>(&mut SchemaGenerator::default()).unwrap();
+
+    assert_eq!(
+        schema,
+        json!({
+            "type": "record",
+            "name": "Order",
+            "fields": [
+                { "name": "count", "doc": "Number of items", "type": "int" },
+                {
+                    "name": "customer",
+                    "type": [
+                        "null",
+                        {
+                            "type": "record",
+                            "name": "Customer",
+                            "fields": [{ "name": "name", "type": "string" }]
+                        }
+                    ],
+                    "default": null
+                },
+                { "name": "id", "type": "long" },
+                { "name": "metadata", "type": { "type": "map", "values": "int" } },
+                { "name": "price", "type": "float" },
+                {
+                    "name": "shape",
+                    "type": [
+                        {
+                            "type": "record",
+                            "name": "ShapeCircle",
+                            "fields": [
+                                { "name": "radius", "type": "double" },
+                                { "name": "type", "type": "string" }
+                            ]
+                        },
+                        {
+                            "type": "record",
+                            "name": "ShapeSquare",
+                            "fields": [
+                                { "name": "side", "type": "double" },
+                                { "name": "type", "type": "string" }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "name": "status",
+                    "type": {
+                        "type": "enum",
+                        "name": "Status",
+                        "symbols": ["Pending", "Delivered"]
+                    }
+                },
+                {
+                    "name": "tree",
+                    "type": {
+                        "type": "record",
+                        "name": "Tree",
+                        "fields": [
+                            { "name": "children", "type": { "type": "array", "items": "Tree" } },
+                            { "name": "value", "type": ["null", "long"], "default": null }
+                        ]
+                    }
+                }
+            ]
+        })
+    );
+}
+
+fn avro_error<T: JsonSchema>() -> (String, String) {
+    let AvroError { path, message, .. } =
+        avro::schema_for::<T>(&mut SchemaGenerator::default()).unwrap_err();
+    (path, message)
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct WithTuple {
+    point: (i32, i32),
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct WithValue {
+    inner: Customer,
+    value: Value,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct WithInvalidName {
+    #[serde(rename = "my-field")]
+    my_field: u128,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct WithLargeInteger {
+    nested: Vec<WithLargeIntegerInner>,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct WithLargeIntegerInner {
+    value: u128,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(untagged)]
+enum Untagged {
+    A(Vec<u8>),
+    B(Vec<String>),
+}
+
+#[test]
+fn avro_errors() {
+    assert_eq!(
+        avro_error::<WithTuple>(),
+        (
+            "/properties/point/prefixItems".to_owned(),
+            "tuples are not supported".to_owned()
+        )
+    );
+    assert_eq!(
+        avro_error::<WithValue>(),
+        (
+            "/properties/value".to_owned(),
+            "schemas that allow any value are not supported".to_owned()
+        )
+    );
+    assert_eq!(
+        avro_error::<WithInvalidName>(),
+        (
+            "/properties/my-field".to_owned(),
+            "property name \"my-field\" is not a valid Avro field name".to_owned()
+        )
+    );
+    assert_eq!(
+        avro_error::<WithLargeInteger>(),
+        (
+            "/$defs/WithLargeIntegerInner/properties/value".to_owned(),
+            "128-bit integers are not supported".to_owned()
+        )
+    );
+    assert_eq!(
+        avro_error::<Untagged>(),
+        (
+            "/anyOf".to_owned(),
+            "unions containing multiple `array` schemas are not supported".to_owned()
+        )
+    );
+
+    let error = avro::schema_for::<WithTuple>(&mut SchemaGenerator::default()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "tuples are not supported (at \"/properties/point/prefixItems\")"
+    );
+}
@@ -0,0 +1,121 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::{json_schema, schema_for, Schema, SchemaBuilder, SchemaGenerator};
+use std::borrow::Cow;
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct Derived {
+    /// The ID.
+    #[schemars(length(max = 36))]
+    id: String,
+    tags: Vec<String>,
+    #[schemars(range(min = 1, max = 10))]
+    count: Option<u8>,
+}
+
+struct Manual;
+
+impl JsonSchema for Manual {
+    fn schema_name() -> Cow<'static, str> {
+        "Derived".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        Schema::object()
+            .property("id", Schema::string().description("The ID.").max_length(36))
+            .property(
+                "tags",
+                Schema::array().items(generator.subschema_for::<String>()),
+            )
+            .property(
+                "count",
+                Schema::integer()
+                    .nullable()
+                    .format("uint8")
+                    .minimum(1)
+                    .maximum(10),
+            )
+            .required("id")
+            .required("tags")
+            .into()
+    }
+}
+
+#[test]
+fn builder_matches_derived_schema() {
+    assert_eq!(schema_for!(Manual), schema_for!(Derived));
+}
+
+#[test]
+fn combinators() {
+    let schema: Schema = Schema::one_of([
+        Schema::constant("a"),
+        Schema::enumeration(["b", "c"]),
+        Schema::all_of([Schema::number().multiple_of(2).build(), true.into()]),
+        Schema::any_of([Schema::boolean(), Schema::null().title("Nothing")]),
+    ])
+    .into();
+
+    assert_eq!(
+        schema,
+        json_schema!({
+            "oneOf": [
+                { "const": "a" },
+                { "enum": ["b", "c"] },
+                { "allOf": [{ "type": "number", "multipleOf": 2 }, true] },
+                { "anyOf": [{ "type": "boolean" }, { "type": "null", "title": "Nothing" }] }
+            ]
+        })
+    );
+}
+
+#[test]
+fn modify_existing_schema() {
+    let schema = SchemaBuilder::new(json_schema!({ "required": ["a"] }))
+        .required("a")
+        .required("b")
+        .pattern_property("^x-", false)
+        .additional_properties(Schema::string().pattern("^[a-z]+$"))
+        .build();
+
+    assert_eq!(
+        schema,
+        json_schema!({
+            "required": ["a", "b"],
+            "patternProperties": { "^x-": false },
+            "additionalProperties": { "type": "string", "pattern": "^[a-z]+$" }
+        })
+    );
+}
+
+#[test]
+fn accessors() {
+    let mut schema = schema_for!(Derived);
+
+    let mut properties = schema
+        .properties()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    properties.sort_unstable();
+    assert_eq!(properties, ["count", "id", "tags"]);
+    assert_eq!(schema.required().collect::<Vec<_>>(), ["id", "tags"]);
+    assert!(schema.is_required("id"));
+    assert!(!schema.is_required("count"));
+    assert_eq!(schema.additional_properties(), None);
+
+    let tags = schema.get_property("tags").unwrap();
+    assert_eq!(tags.items(), Some(&json_schema!({ "type": "string" })));
+    assert_eq!(
+        Schema::new_ref("#/$defs/A".to_owned()).reference(),
+        Some("#/$defs/A")
+    );
+
+    for (_, property) in schema.properties_mut() {
+        property.remove("description");
+    }
+    assert_eq!(
+        schema.get_property("id"),
+        Some(&json_schema!({ "type": "string", "maxLength": 36 }))
+    );
+}
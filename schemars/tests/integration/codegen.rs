@@ -0,0 +1,203 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::codegen::rust_source_for;
+use schemars::generate::SchemaSettings;
+use schemars::{schema_for, SchemaGenerator};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+#[allow(dead_code)]
+mod generated {
+    use uuid1 as uuid;
+
+    include!("codegen/generated.rs");
+}
+
+/// # Order
+///
+/// An order placed by a customer.
+#[derive(JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct Order {
+    id: uuid1::Uuid,
+    #[serde(rename = "customerName")]
+    customer_name: String,
+    quantity: u32,
+    price: f64,
+    /// Notes from the customer.
+    notes: Option<String>,
+    tags: BTreeSet<String>,
+    lines: Vec<Line>,
+    status: Status,
+    payment: Payment,
+    shipping: Shipping,
+    event: Event,
+    metadata: BTreeMap<String, Value>,
+    position: (i8, i16),
+    #[serde(default)]
+    priority: i64,
+    #[serde(default = "default_label")]
+    label: String,
+    r#type: char,
+    parent: Option<Box<Order>>,
+    extra: Value,
+}
+
+fn default_label() -> String {
+    "standard".to_owned()
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum Status {
+    Pending,
+    #[serde(rename = "in-progress")]
+    InProgress,
+    Done,
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum Payment {
+    Cash,
+    Card { number: String, expiry: (u8, u16) },
+    Voucher(String),
+    Split(u32, u32),
+}
+
+#[derive(JsonSchema)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum Shipping {
+    Pickup,
+    Courier { tracking: String },
+    Post(Address),
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct Address {
+    street: String,
+    city: String,
+    coordinates: Option<Coordinates>,
+}
+
+#[derive(JsonSchema)]
+#[schemars(inline)]
+#[allow(dead_code)]
+struct Coordinates {
+    lat: f32,
+    lon: f32,
+}
+
+#[derive(JsonSchema)]
+#[serde(tag = "kind", content = "data")]
+#[allow(dead_code)]
+enum Event {
+    Created,
+    Updated(u64),
+    Deleted { reason: String },
+}
+
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum Line {
+    Simple(String),
+    Detailed { sku: String, count: u16 },
+}
+
+#[test]
+fn generated_source() {
+    let mut generator = SchemaGenerator::default();
+    let source = rust_source_for::<Order>(&mut generator);
+
+    snapbox::assert_data_eq!(
+        source,
+        snapbox::Data::read_from(Path::new("tests/integration/codegen/generated.rs"), None).raw()
+    );
+}
+
+#[test]
+fn generated_schema_matches_original() {
+    assert_eq!(schema_for!(generated::Order), schema_for!(Order));
+}
+
+#[test]
+fn generated_schema_matches_original_draft07() {
+    let generator = SchemaSettings::draft07().into_generator();
+
+    assert_eq!(
+        generator.clone().into_root_schema_for::<generated::Order>(),
+        generator.into_root_schema_for::<Order>()
+    );
+}
+
+#[test]
+fn handwritten_schema() {
+    let schema = schemars::json_schema!({
+        "type": "object",
+        "properties": {
+            "first-name": { "type": "string", "maxLength": 50 },
+            "self": { "type": ["integer", "null"], "format": "int32" },
+            "children": {
+                "type": "array",
+                "items": { "$ref": "#/$defs/tree node" }
+            },
+            "kind": { "enum": ["a", "b"] }
+        },
+        "required": ["first-name", "children"],
+        "$defs": {
+            "tree node": {
+                "type": "object",
+                "properties": {
+                    "children": {
+                        "type": "array",
+                        "items": { "$ref": "#/$defs/tree node" }
+                    },
+                    "next": { "$ref": "#/$defs/tree node" }
+                },
+                "required": ["children"]
+            }
+        }
+    });
+
+    assert_eq!(
+        schemars::codegen::rust_source("person", &schema),
+        r#"use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "person")]
+pub struct Person {
+    #[serde(rename = "first-name")]
+    pub first_name: String,
+    #[serde(rename = "self")]
+    pub self_: Option<i32>,
+    pub children: Vec<TreeNode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "PersonKind")]
+    pub kind: Option<PersonKind>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "tree node")]
+pub struct TreeNode {
+    pub children: Vec<TreeNode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Box<TreeNode>")]
+    pub next: Option<Box<TreeNode>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(inline)]
+pub enum PersonKind {
+    #[serde(rename = "a")]
+    A,
+    #[serde(rename = "b")]
+    B,
+}
+"#
+    );
+}
@@ -0,0 +1,94 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// An order placed by a customer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Order {
+    pub id: uuid::Uuid,
+    #[serde(rename = "customerName")]
+    pub customer_name: String,
+    pub quantity: u32,
+    pub price: f64,
+    /// Notes from the customer.
+    pub notes: Option<String>,
+    pub tags: std::collections::BTreeSet<String>,
+    pub lines: Vec<Line>,
+    pub status: Status,
+    pub payment: Payment,
+    pub shipping: Shipping,
+    pub event: Event,
+    pub metadata: std::collections::BTreeMap<String, serde_json::Value>,
+    pub position: (i8, i16),
+    #[serde(default)]
+    pub priority: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "String", extend("default" = "standard"))]
+    pub label: Option<String>,
+    pub r#type: char,
+    pub parent: Option<Box<Order>>,
+    pub extra: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Line {
+    String(String),
+    Object {
+        sku: String,
+        count: u16,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum Status {
+    Pending,
+    #[serde(rename = "in-progress")]
+    InProgress,
+    Done,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum Payment {
+    Cash,
+    Card {
+        number: String,
+        expiry: (u8, u16),
+    },
+    Voucher(String),
+    Split(u32, u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum Shipping {
+    Pickup,
+    Courier {
+        tracking: String,
+    },
+    Post(Address),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", content = "data")]
+pub enum Event {
+    Created,
+    Updated(u64),
+    Deleted {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+    pub coordinates: Option<AddressCoordinates>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(inline)]
+pub struct AddressCoordinates {
+    pub lat: f32,
+    pub lon: f32,
+}
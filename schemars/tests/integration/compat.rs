@@ -0,0 +1,301 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::compat::{check, Change, Finding, Incompatibility};
+use schemars::generate::{Contract, SchemaSettings};
+use schemars::{json_schema, schema_for};
+
+#[allow(dead_code)]
+mod v1 {
+    use crate::prelude::*;
+
+    #[derive(JsonSchema)]
+    pub struct Order {
+        pub id: u64,
+        pub note: Option<String>,
+        pub status: Status,
+        pub item: Item,
+        pub parent: Option<Box<Order>>,
+    }
+
+    #[derive(JsonSchema)]
+    pub enum Status {
+        Pending,
+        Shipped,
+        Cancelled,
+    }
+
+    #[derive(JsonSchema)]
+    #[serde(tag = "kind", deny_unknown_fields)]
+    pub enum Item {
+        Book {
+            #[schemars(length(max = 100))]
+            title: String,
+            legacy_isbn: String,
+        },
+        Music {
+            artist: String,
+        },
+    }
+}
+
+#[allow(dead_code)]
+mod v2 {
+    use crate::prelude::*;
+
+    #[derive(JsonSchema)]
+    pub struct Order {
+        pub id: u64,
+        pub note: String,
+        pub status: Status,
+        pub item: Item,
+        pub parent: Option<Box<Order>>,
+    }
+
+    #[derive(JsonSchema)]
+    pub enum Status {
+        Pending,
+        Shipped,
+        Delivered,
+    }
+
+    #[derive(JsonSchema)]
+    #[serde(tag = "kind", deny_unknown_fields)]
+    pub enum Item {
+        Book {
+            #[schemars(length(max = 50))]
+            title: String,
+        },
+        Film {
+            director: String,
+        },
+    }
+}
+
+fn findings(contract: Contract) -> Vec<(String, Change, Incompatibility)> {
+    let settings = SchemaSettings::default().with(|s| s.contract = contract.clone());
+    let old = settings
+        .clone()
+        .into_generator()
+        .into_root_schema_for::<v1::Order>();
+    let new = settings
+        .into_generator()
+        .into_root_schema_for::<v2::Order>();
+
+    check(&old, &new, contract)
+        .into_iter()
+        .map(
+            |Finding {
+                 path,
+                 change,
+                 incompatibility,
+                 ..
+             }| (path, change, incompatibility),
+        )
+        .collect()
+}
+
+fn expected_changes() -> Vec<(&'static str, Change, bool)> {
+    // (path, change, whether the change rejects values that were previously valid)
+    vec![
+        (
+            "/properties/item/$ref/oneOf/0/properties/title",
+            Change::KeywordChanged {
+                keyword: "maxLength".to_owned(),
+                old: Some(json!(100)),
+                new: Some(json!(50)),
+            },
+            true,
+        ),
+        (
+            "/properties/item/$ref/oneOf/0",
+            Change::PropertyNoLongerRequired {
+                property: "legacy_isbn".to_owned(),
+            },
+            false,
+        ),
+        (
+            "/properties/item/$ref/oneOf/0",
+            Change::PropertyRemoved {
+                property: "legacy_isbn".to_owned(),
+            },
+            true,
+        ),
+        (
+            "/properties/item/$ref",
+            Change::VariantRemoved {
+                variant: "Music".to_owned(),
+            },
+            true,
+        ),
+        (
+            "/properties/item/$ref",
+            Change::VariantAdded {
+                variant: "Film".to_owned(),
+            },
+            false,
+        ),
+        (
+            "/properties/note",
+            Change::TypeChanged {
+                old: Some(vec!["null".to_owned(), "string".to_owned()]),
+                new: Some(vec!["string".to_owned()]),
+            },
+            true,
+        ),
+        (
+            "/properties/status/$ref",
+            Change::EnumValueRemoved {
+                value: json!("Cancelled"),
+            },
+            true,
+        ),
+        (
+            "/properties/status/$ref",
+            Change::EnumValueAdded {
+                value: json!("Delivered"),
+            },
+            false,
+        ),
+        (
+            "",
+            Change::PropertyNowRequired {
+                property: "note".to_owned(),
+            },
+            true,
+        ),
+    ]
+}
+
+fn sorted<T: core::fmt::Debug>(mut items: Vec<T>) -> Vec<T> {
+    // Finding order depends on property order, which depends on the `preserve_order` feature
+    items.sort_by_cached_key(|item| format!("{item:?}"));
+    items
+}
+
+#[test]
+fn compat_deserialize() {
+    let expected = expected_changes()
+        .into_iter()
+        .map(|(path, change, narrows)| {
+            let incompatibility = if narrows {
+                Incompatibility::Backward
+            } else {
+                Incompatibility::Forward
+            };
+            (path.to_owned(), change, incompatibility)
+        })
+        .collect();
+
+    assert_eq!(sorted(findings(Contract::Deserialize)), sorted(expected));
+}
+
+#[test]
+fn compat_serialize() {
+    let expected = expected_changes()
+        .into_iter()
+        // `Option` fields are always required when serializing
+        .filter(|(_, change, _)| !matches!(change, Change::PropertyNowRequired { .. }))
+        .map(|(path, change, narrows)| {
+            let incompatibility = if narrows {
+                Incompatibility::Forward
+            } else {
+                Incompatibility::Backward
+            };
+            (path.to_owned(), change, incompatibility)
+        })
+        .collect();
+
+    assert_eq!(sorted(findings(Contract::Serialize)), sorted(expected));
+}
+
+#[test]
+fn compat_unchanged() {
+    let old = schema_for!(v1::Order);
+    let new = schema_for!(v1::Order);
+
+    assert_eq!(check(&old, &new, Contract::Deserialize), vec![]);
+    assert_eq!(check(&old, &new, Contract::Serialize), vec![]);
+}
+
+#[test]
+fn compat_display() {
+    let old = json_schema!({ "type": "integer", "minimum": 0 });
+    let new = json_schema!({ "type": ["integer", "null"], "minimum": 1 });
+
+    let messages = check(&old, &new, Contract::Deserialize)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        vec![
+            "type changed from integer to integer | null (forward-incompatible)",
+            "\"minimum\" changed from 0 to 1 (backward-incompatible)",
+        ]
+    );
+}
+
+#[test]
+fn compat_sibling_refs_to_same_definition() {
+    // Each `anyOf` branch of the old schema is merged with its sibling `required` keyword, and
+    // each is compared against the same definition in the new schema
+    let branches = json!([
+        { "title": "Value", "properties": { "value": { "type": "integer" } } },
+        { "title": "Value", "properties": { "value": { "type": "string" } } }
+    ]);
+    let old = json_schema!({
+        "type": "object",
+        "properties": {
+            "first": { "required": ["value"], "anyOf": branches },
+            "second": { "required": ["value"], "anyOf": branches }
+        }
+    });
+    let new = json_schema!({
+        "type": "object",
+        "properties": {
+            "first": {
+                "anyOf": [{ "$ref": "#/$defs/Value" }, { "$ref": "#/$defs/Value" }]
+            },
+            "second": {
+                "anyOf": [{ "$ref": "#/$defs/Value" }, { "$ref": "#/$defs/Value" }]
+            }
+        },
+        "$defs": {
+            "Value": {
+                "title": "Value",
+                "properties": { "value": { "type": "boolean" } },
+                "required": ["value"]
+            }
+        }
+    });
+
+    let changed_type = |path: &str, old: &str| {
+        (
+            path.to_owned(),
+            Change::TypeChanged {
+                old: Some(vec![old.to_owned()]),
+                new: Some(vec!["boolean".to_owned()]),
+            },
+            Incompatibility::Both,
+        )
+    };
+
+    assert_eq!(
+        sorted(
+            check(&old, &new, Contract::Deserialize)
+                .into_iter()
+                .map(|f| (f.path, f.change, f.incompatibility))
+                .collect()
+        ),
+        sorted(vec![
+            changed_type("/properties/first/anyOf/0/$ref/properties/value", "integer"),
+            changed_type("/properties/first/anyOf/1/$ref/properties/value", "string"),
+            changed_type(
+                "/properties/second/anyOf/0/$ref/properties/value",
+                "integer"
+            ),
+            changed_type("/properties/second/anyOf/1/$ref/properties/value", "string"),
+        ])
+    );
+}
@@ -0,0 +1,172 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::generate::SchemaSettings;
+use schemars::{json_schema, SchemaGenerator};
+
+#[allow(dead_code)]
+#[derive(JsonSchema, Deserialize, Serialize)]
+struct Order {
+    address: Address,
+    status: Status,
+    items: Vec<Item>,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema, Deserialize, Serialize)]
+struct Address {
+    street: String,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(rename_all(serialize = "UPPERCASE"))]
+enum Status {
+    Pending,
+    Shipped,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema, Deserialize, Serialize)]
+struct Item {
+    name: String,
+    #[serde(skip_deserializing)]
+    id: u64,
+    children: Vec<Item>,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema, Deserialize, Serialize)]
+struct Tree {
+    children: Vec<Tree>,
+}
+
+fn definition_names(generator: &SchemaGenerator) -> Vec<&str> {
+    let mut names = generator
+        .definitions()
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+#[test]
+fn shared_and_split_definitions() {
+    let mut generator = SchemaGenerator::default();
+    let (de, ser) = generator.subschemas_for_contracts::<Order>();
+
+    assert_eq!(de, json_schema!({ "$ref": "#/$defs/OrderRequest" }));
+    assert_eq!(ser, json_schema!({ "$ref": "#/$defs/OrderResponse" }));
+    assert_eq!(
+        definition_names(&generator),
+        [
+            "Address",
+            "ItemRequest",
+            "ItemResponse",
+            "OrderRequest",
+            "OrderResponse",
+            "StatusRequest",
+            "StatusResponse",
+        ]
+    );
+
+    let definitions = generator.definitions();
+    assert_eq!(
+        definitions["OrderRequest"]["properties"]["address"],
+        json!({ "$ref": "#/$defs/Address" })
+    );
+    assert_eq!(
+        definitions["OrderResponse"]["properties"]["address"],
+        json!({ "$ref": "#/$defs/Address" })
+    );
+    assert_eq!(
+        definitions["OrderRequest"]["properties"]["status"],
+        json!({ "$ref": "#/$defs/StatusRequest" })
+    );
+    assert_eq!(
+        definitions["OrderResponse"]["properties"]["status"],
+        json!({ "$ref": "#/$defs/StatusResponse" })
+    );
+    assert_eq!(
+        definitions["ItemRequest"]["properties"]["children"]["items"],
+        json!({ "$ref": "#/$defs/ItemRequest" })
+    );
+    assert_eq!(
+        definitions["ItemResponse"]["properties"]["children"]["items"],
+        json!({ "$ref": "#/$defs/ItemResponse" })
+    );
+
+    // Definitions should be the same as those generated separately for each contract
+    let mut de_generator = SchemaSettings::default().for_deserialize().into_generator();
+    de_generator.subschema_for::<Order>();
+    assert_eq!(
+        definitions["StatusRequest"],
+        de_generator.definitions()["Status"]
+    );
+
+    let mut ser_generator = SchemaSettings::default().for_serialize().into_generator();
+    ser_generator.subschema_for::<Order>();
+    assert_eq!(
+        definitions["StatusResponse"],
+        ser_generator.definitions()["Status"]
+    );
+}
+
+#[test]
+fn recursive_shared_definition() {
+    let mut generator = SchemaGenerator::default();
+    let (de, ser) = generator.subschemas_for_contracts::<Tree>();
+
+    assert_eq!(de, json_schema!({ "$ref": "#/$defs/Tree" }));
+    assert_eq!(ser, de);
+    assert_eq!(definition_names(&generator), ["Tree"]);
+    assert_eq!(
+        generator.definitions()["Tree"]["properties"]["children"]["items"],
+        json!({ "$ref": "#/$defs/Tree" })
+    );
+}
+
+#[test]
+fn existing_definitions_are_not_renamed() {
+    let mut generator = SchemaSettings::default().for_serialize().into_generator();
+    let status = generator.subschema_for::<Status>();
+    assert_eq!(status, json_schema!({ "$ref": "#/$defs/Status" }));
+
+    let (de, ser) = generator.subschemas_for_contracts::<Status>();
+    assert_eq!(de, json_schema!({ "$ref": "#/$defs/StatusRequest" }));
+    assert_eq!(ser, status);
+
+    let (de, ser) = generator.subschemas_for_contracts::<Address>();
+    assert_eq!(de, json_schema!({ "$ref": "#/$defs/Address" }));
+    assert_eq!(ser, de);
+
+    assert_eq!(
+        definition_names(&generator),
+        ["Address", "Status", "StatusRequest"]
+    );
+    assert!(generator.contract().is_serialize());
+}
+
+#[test]
+fn custom_suffixes() {
+    let mut generator = SchemaSettings::openapi3()
+        .with(|s| {
+            s.deserialize_name_suffix = "Input".into();
+            s.serialize_name_suffix = "Output".into();
+        })
+        .into_generator();
+    let (de, ser) = generator.subschemas_for_contracts::<Status>();
+
+    assert_eq!(
+        de,
+        json_schema!({ "$ref": "#/components/schemas/StatusInput" })
+    );
+    assert_eq!(
+        ser,
+        json_schema!({ "$ref": "#/components/schemas/StatusOutput" })
+    );
+    assert_eq!(
+        definition_names(&generator),
+        ["StatusInput", "StatusOutput"]
+    );
+}
@@ -0,0 +1,233 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::diff::{compare, Operation};
+use schemars::{json_schema, schema_for, Schema};
+
+#[allow(dead_code)]
+mod v1 {
+    use crate::prelude::*;
+
+    /// An order.
+    #[derive(JsonSchema)]
+    pub struct Order {
+        pub id: u64,
+        pub note: Option<String>,
+        pub status: Status,
+        pub customer: Customer,
+    }
+
+    #[derive(JsonSchema)]
+    pub enum Status {
+        Pending,
+        Shipped,
+        Cancelled,
+    }
+
+    #[derive(JsonSchema)]
+    pub struct Customer {
+        pub name: String,
+        pub email: String,
+    }
+}
+
+#[allow(dead_code)]
+mod v2 {
+    use crate::prelude::*;
+
+    /// An order placed by a customer.
+    #[derive(JsonSchema)]
+    pub struct Order {
+        pub id: u64,
+        pub note: String,
+        pub status: Status,
+        pub customer: Customer,
+        pub total: u32,
+    }
+
+    #[derive(JsonSchema)]
+    pub enum Status {
+        Delivered,
+        Cancelled,
+        Pending,
+    }
+
+    #[derive(JsonSchema)]
+    pub struct Customer {
+        #[schemars(length(max = 100))]
+        pub name: String,
+        pub phone: Option<String>,
+    }
+}
+
+// A minimal RFC 6902 implementation, supporting the operations generated by `compare`
+fn apply_patch(mut value: Value, patch: &Value) -> Value {
+    for operation in patch.as_array().unwrap() {
+        let path = operation["path"].as_str().unwrap();
+        let (parent, last) = path.rsplit_once('/').unwrap();
+        let last = last.replace("~1", "/").replace("~0", "~");
+        let target = value.pointer_mut(parent).unwrap();
+
+        match (operation["op"].as_str().unwrap(), target) {
+            ("add", Value::Array(items)) if last == "-" => items.push(operation["value"].clone()),
+            ("add", Value::Array(items)) => {
+                items.insert(last.parse().unwrap(), operation["value"].clone());
+            }
+            ("add" | "replace", Value::Object(obj)) => {
+                obj.insert(last, operation["value"].clone());
+            }
+            ("replace", Value::Array(items)) => {
+                items[last.parse::<usize>().unwrap()] = operation["value"].clone();
+            }
+            ("remove", Value::Array(items)) => {
+                items.remove(last.parse().unwrap());
+            }
+            ("remove", Value::Object(obj)) => {
+                obj.remove(&last);
+            }
+            (op, target) => panic!("unexpected operation {op} on {target}"),
+        }
+    }
+    value
+}
+
+#[test]
+fn identical_schemas() {
+    let diff = compare(&schema_for!(v1::Order), &schema_for!(v1::Order));
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_json_patch(), json!([]));
+    assert_eq!(diff.to_markdown(), "No changes.\n");
+}
+
+#[test]
+fn sets_are_unordered() {
+    let old = json_schema!({
+        "type": ["string", "null"],
+        "enum": ["a", "b", null],
+        "required": ["x", "y"]
+    });
+    let new = json_schema!({
+        "required": ["y", "x"],
+        "enum": [null, "b", "a"],
+        "type": ["null", "string"]
+    });
+
+    assert!(compare(&old, &new).is_empty());
+}
+
+#[test]
+fn json_patch() {
+    let old = schema_for!(v1::Order);
+    let new = schema_for!(v2::Order);
+    let diff = compare(&old, &new);
+
+    assert_eq!(
+        diff.to_json_patch(),
+        json!([
+            { "op": "remove", "path": "/$defs/Customer/properties/email" },
+            { "op": "add", "path": "/$defs/Customer/properties/name/maxLength", "value": 100 },
+            {
+                "op": "add",
+                "path": "/$defs/Customer/properties/phone",
+                "value": { "type": ["string", "null"] }
+            },
+            { "op": "remove", "path": "/$defs/Customer/required/1" },
+            { "op": "remove", "path": "/$defs/Status/enum/1" },
+            { "op": "add", "path": "/$defs/Status/enum/-", "value": "Delivered" },
+            { "op": "replace", "path": "/description", "value": "An order placed by a customer." },
+            { "op": "replace", "path": "/properties/note/type", "value": "string" },
+            {
+                "op": "add",
+                "path": "/properties/total",
+                "value": { "type": "integer", "format": "uint32", "minimum": 0 }
+            },
+            { "op": "add", "path": "/required/-", "value": "note" },
+            { "op": "add", "path": "/required/-", "value": "total" }
+        ])
+    );
+
+    let patched = apply_patch(old.to_value(), &diff.to_json_patch());
+    assert!(compare(&Schema::try_from(patched).unwrap(), &new).is_empty());
+}
+
+#[test]
+fn markdown() {
+    let diff = compare(&schema_for!(v1::Order), &schema_for!(v2::Order));
+
+    assert_eq!(
+        diff.to_markdown(),
+        r#"## Root schema (`Order`)
+
+**Added properties**
+- `total` (required)
+
+**Changed properties**
+- `note`: `type` changed from `["string","null"]` to `"string"`
+- `note`: now required
+
+**Other changes**
+- `description` changed from `"An order."` to `"An order placed by a customer."`
+
+## `Customer`
+
+**Added properties**
+- `phone`
+
+**Removed properties**
+- `email` (was required)
+
+**Changed properties**
+- `name`: `maxLength` added: `100`
+
+## `Status`
+
+**Other changes**
+- `enum` value `"Shipped"` removed
+- `enum` value `"Delivered"` added
+"#
+    );
+}
+
+#[test]
+fn operations() {
+    let old = json_schema!({ "$defs": { "Old": true }, "minimum": 1 });
+    let new = json_schema!({ "$defs": { "New": false }, "minimum": 2 });
+    let diff = compare(&old, &new);
+
+    assert_eq!(
+        diff.operations(),
+        [
+            Operation::Add {
+                path: "/$defs/New".to_owned(),
+                value: json!(false)
+            },
+            Operation::Remove {
+                path: "/$defs/Old".to_owned(),
+                old: json!(true)
+            },
+            Operation::Replace {
+                path: "/minimum".to_owned(),
+                old: json!(1),
+                value: json!(2)
+            },
+        ]
+    );
+    assert_eq!(
+        diff.to_markdown(),
+        r#"## Root schema
+
+**Other changes**
+- `minimum` changed from `1` to `2`
+
+## `New`
+
+**Other changes**
+- definition added
+
+## `Old`
+
+**Other changes**
+- definition removed
+"#
+    );
+}
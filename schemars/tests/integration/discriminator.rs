@@ -0,0 +1,191 @@
+use crate::prelude::*;
+use schemars::generate::SchemaSettings;
+use schemars::{json_schema, SchemaGenerator};
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+struct Dog {
+    name: String,
+    good: bool,
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(tag = "kind")]
+enum Animal {
+    Cat {
+        lives: u8,
+    },
+    Dog(Dog),
+    #[serde(rename = "fish", alias = "goldfish")]
+    Fish,
+    #[serde(skip_deserializing)]
+    Unknown,
+}
+
+impl Animal {
+    fn values() -> impl IntoIterator<Item = Self> {
+        [
+            Self::Cat { lives: 9 },
+            Self::Dog(Dog::default()),
+            Self::Fish,
+            Self::Unknown,
+        ]
+    }
+}
+
+fn discriminator_settings() -> SchemaSettings {
+    SchemaSettings::default().with(|s| s.internally_tagged_enum_discriminators = true)
+}
+
+fn openapi3_discriminator_settings() -> SchemaSettings {
+    SchemaSettings::openapi3().with(|s| s.internally_tagged_enum_discriminators = true)
+}
+
+#[test]
+fn internally_tagged_enum_discriminator() {
+    test!(Animal, discriminator_settings())
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip(Animal::values())
+        .assert_allows_de_roundtrip([json!({ "kind": "goldfish" })])
+        .assert_rejects_de([json!({ "kind": "Unknown" }), json!({ "kind": "Dog" })])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[test]
+fn internally_tagged_enum_discriminator_openapi3() {
+    test!(Animal, openapi3_discriminator_settings()).assert_snapshot();
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(tag = "kind")]
+#[schemars(_unstable_ref_variants)]
+enum RefVariants {
+    Unit,
+    Struct { value: i32 },
+}
+
+#[test]
+fn ref_variants_discriminator() {
+    test!(RefVariants, discriminator_settings())
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip([RefVariants::Unit, RefVariants::Struct { value: 1 }])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(tag = "kind")]
+enum WithUntagged {
+    Unit,
+    #[serde(untagged)]
+    Other(Value),
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(tag = "kind")]
+enum WithOther {
+    Unit,
+    #[serde(other)]
+    Other,
+}
+
+#[test]
+fn no_discriminator_for_untagged_or_other_variants() {
+    let mut generator = discriminator_settings().into_generator();
+
+    for schema in [
+        generator.root_schema_for::<WithUntagged>(),
+        generator.root_schema_for::<WithOther>(),
+    ] {
+        assert_eq!(schema.get("discriminator"), None);
+        assert_eq!(schema.get("$defs"), None);
+    }
+}
+
+#[test]
+fn no_discriminator_by_default() {
+    let schema = SchemaGenerator::default().into_root_schema_for::<Animal>();
+
+    assert_eq!(schema.get("discriminator"), None);
+    assert_eq!(
+        schema
+            .get("$defs")
+            .and_then(Value::as_object)
+            .map(|d| d.len()),
+        Some(1),
+        "only `Dog` should be a definition"
+    );
+
+    let schema = SchemaSettings::openapi3()
+        .into_generator()
+        .into_root_schema_for::<Animal>();
+
+    assert_eq!(schema.get("discriminator"), None);
+    assert_eq!(
+        schema
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .map(|d| d.keys().map(String::as_str).collect::<Vec<_>>()),
+        Some(vec!["Dog"]),
+    );
+}
+
+#[test]
+fn discriminator_mapping_for_contracts() {
+    let mut generator = openapi3_discriminator_settings().into_generator();
+    let (de, ser) = generator.subschemas_for_contracts::<Animal>();
+
+    assert_eq!(
+        de,
+        json_schema!({ "$ref": "#/components/schemas/AnimalRequest" })
+    );
+    assert_eq!(
+        ser,
+        json_schema!({ "$ref": "#/components/schemas/AnimalResponse" })
+    );
+
+    let definitions = generator.take_definitions(true);
+
+    assert_eq!(
+        definitions["AnimalRequest"]["discriminator"],
+        json!({
+            "propertyName": "kind",
+            "mapping": {
+                "Cat": "#/components/schemas/Animal_Cat",
+                "Dog": "#/components/schemas/Animal_Dog",
+                "fish": "#/components/schemas/Animal_fishRequest",
+                "goldfish": "#/components/schemas/Animal_fishRequest",
+            }
+        })
+    );
+    assert_eq!(
+        definitions["AnimalResponse"]["discriminator"],
+        json!({
+            "propertyName": "kind",
+            "mapping": {
+                "Cat": "#/components/schemas/Animal_Cat",
+                "Dog": "#/components/schemas/Animal_Dog",
+                "fish": "#/components/schemas/Animal_fishResponse",
+                "Unknown": "#/components/schemas/Animal_Unknown",
+            }
+        })
+    );
+
+    for references in [
+        &definitions["AnimalRequest"]["discriminator"]["mapping"],
+        &definitions["AnimalResponse"]["discriminator"]["mapping"],
+    ] {
+        for reference in references.as_object().unwrap().values() {
+            let name = reference
+                .as_str()
+                .unwrap()
+                .strip_prefix("#/components/schemas/")
+                .unwrap();
+            assert!(definitions.contains_key(name), "missing definition {name}");
+        }
+    }
+
+    // The user's own `Dog` type keeps its name
+    assert_eq!(
+        definitions["Animal_Dog"]["allOf"][0]["$ref"],
+        json!("#/components/schemas/Dog")
+    );
+}
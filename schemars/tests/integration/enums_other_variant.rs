@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+#[serde(tag = "type")]
+enum Internal {
+    #[default]
+    Unit,
+    Struct {
+        value: i32,
+    },
+    #[serde(alias = "legacy")]
+    Renamed,
+    #[serde(skip_deserializing)]
+    SerializeOnly,
+    #[serde(other)]
+    Unknown,
+}
+
+#[test]
+fn enum_internal_other() {
+    test!(Internal)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_ser_roundtrip([Internal::SerializeOnly, Internal::Unknown])
+        .assert_allows_de_roundtrip([
+            json!({ "type": "Unknown" }),
+            json!({ "type": "SomethingElse" }),
+            json!({ "type": "SerializeOnly" }),
+        ])
+        .assert_rejects_de([
+            json!({ "type": "Struct" }),
+            json!({ "type": 1 }),
+            json!({}),
+        ])
+        .assert_matches_de_roundtrip(arbitrary_values_except(
+            Value::is_array,
+            "internally tagged enums can technically be deserialized from sequences, but that's not intended to be used via JSON, so schemars ignores it",
+        ));
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    #[default]
+    Unit,
+    Int(i32),
+    #[serde(other)]
+    Unknown,
+}
+
+#[test]
+fn enum_adjacent_other() {
+    test!(Adjacent)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_ser_roundtrip([Adjacent::Int(1), Adjacent::Unknown])
+        .assert_allows_de_roundtrip([json!({ "t": "Unknown" }), json!({ "t": "SomethingElse" })])
+        .assert_rejects_de([json!({ "t": "Int" }), json!({ "c": 1 })]);
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum Identifier {
+    #[default]
+    Name,
+    Age,
+    #[serde(other)]
+    Other,
+}
+
+#[test]
+fn enum_identifier_other() {
+    test!(Identifier)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_ser_roundtrip([Identifier::Age, Identifier::Other])
+        .assert_allows_de_roundtrip([json!("name"), json!("Name"), json!("other"), json!("")])
+        .assert_matches_de_roundtrip(arbitrary_values_except(
+            Value::is_object,
+            "unit variants can technically be deserialized from a single-entry map with a null value, but that's not intended to be used via JSON, so schemars ignores it",
+        ));
+}
@@ -0,0 +1,156 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::export::{Difference, Exporter, StaleReason};
+use schemars::generate::SchemaSettings;
+use schemars::schema_for;
+use std::fs;
+use std::path::PathBuf;
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct User {
+    name: String,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct Order {
+    user: User,
+    total: u64,
+}
+
+fn temp_dir(test_name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "schemars-export-{}-{test_name}",
+        std::process::id()
+    ));
+    _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn file_per_schema() {
+    let dir = temp_dir("file_per_schema");
+    let exporter = Exporter::default()
+        .with_type::<User>("user")
+        .with_type::<Order>("order");
+
+    assert_eq!(exporter.check(&dir).unwrap().len(), 2);
+
+    exporter.write(&dir).unwrap();
+    assert_eq!(exporter.check(&dir).unwrap(), []);
+
+    let order = fs::read_to_string(dir.join("order.json")).unwrap();
+    assert_eq!(
+        order,
+        serde_json::to_string_pretty(&schema_for!(Order)).unwrap() + "\n"
+    );
+    assert!(order.starts_with("{\n  \"$schema\":"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn check_stale_files() {
+    let dir = temp_dir("check_stale_files");
+    let exporter = Exporter::default()
+        .with_type::<User>("user")
+        .with_type::<Order>("order");
+    exporter.write(&dir).unwrap();
+
+    let mut user: Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("user.json")).unwrap()).unwrap();
+    user["properties"]["name"]["type"] = json!("integer");
+    user["properties"]["age"] = json!({ "type": "integer" });
+    fs::write(dir.join("user.json"), user.to_string()).unwrap();
+
+    let order = fs::read_to_string(dir.join("order.json")).unwrap();
+    fs::write(dir.join("order.json"), order.replace("\n", "")).unwrap();
+
+    let stale = exporter.check(&dir).unwrap();
+    assert_eq!(stale.len(), 2);
+
+    assert_eq!(stale[0].path, dir.join("user.json"));
+    let StaleReason::Changed(differences) = &stale[0].reason else {
+        panic!("unexpected reason: {:?}", stale[0].reason);
+    };
+    let mut pointers = differences
+        .iter()
+        .map(|d: &Difference| d.pointer.as_str())
+        .collect::<Vec<_>>();
+    pointers.sort_unstable();
+    assert_eq!(pointers, ["/properties/age", "/properties/name/type"]);
+
+    let name_type = differences
+        .iter()
+        .find(|d| d.pointer == "/properties/name/type")
+        .unwrap();
+    assert_eq!(name_type.existing, Some(json!("integer")));
+    assert_eq!(name_type.expected, Some(json!("string")));
+    assert_eq!(
+        name_type.to_string(),
+        r#"/properties/name/type: "integer" -> "string""#
+    );
+
+    assert_eq!(stale[1].path, dir.join("order.json"));
+    assert_eq!(stale[1].reason, StaleReason::Reformatted);
+
+    exporter.write(&dir).unwrap();
+    assert_eq!(exporter.check(&dir).unwrap(), []);
+
+    fs::write(dir.join("user.json"), "{").unwrap();
+    let stale = exporter.check(&dir).unwrap();
+    assert!(matches!(stale[0].reason, StaleReason::InvalidJson(_)));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn bundle() {
+    let exporter = Exporter::from(SchemaSettings::draft07().into_generator())
+        .with_type::<User>("user")
+        .with_type::<Order>("Order")
+        .with_type::<Vec<u8>>("bytes")
+        .bundled("schemas.json");
+
+    let files = exporter.files();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, PathBuf::from("schemas.json"));
+    assert_eq!(
+        files[0].schema.as_value(),
+        &json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "definitions": {
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" }
+                    },
+                    "required": ["name"]
+                },
+                "Order": {
+                    "type": "object",
+                    "properties": {
+                        "user": { "$ref": "#/definitions/User" },
+                        "total": {
+                            "type": "integer",
+                            "format": "uint64",
+                            "minimum": 0
+                        }
+                    },
+                    "required": ["user", "total"]
+                },
+                "user": { "$ref": "#/definitions/User" },
+                "bytes": {
+                    "type": "array",
+                    "items": {
+                        "type": "integer",
+                        "format": "uint8",
+                        "minimum": 0,
+                        "maximum": 255
+                    }
+                }
+            }
+        })
+    );
+}
@@ -0,0 +1,233 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::generate::SchemaSettings;
+use schemars::kubernetes::{self, Finding, Issue};
+use schemars::{json_schema, Schema};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct DatabaseSpec {
+    /// The number of instances
+    replicas: Option<u32>,
+    port: IntOrString,
+    engine: Engine,
+    backup: Option<Backup>,
+    storage: Storage,
+    tier: Tier,
+    labels: BTreeMap<String, String>,
+    zones: BTreeSet<String>,
+    config: Value,
+    replica_of: Option<Box<DatabaseSpec>>,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(untagged)]
+enum IntOrString {
+    Int(i32),
+    String(String),
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(tag = "name")]
+enum Engine {
+    Postgres { version: u8 },
+    MySql { version: u8, innodb: bool },
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct Backup {
+    #[schemars(length(min = 1))]
+    schedule: String,
+    #[schemars(extend("exclusiveMinimum" = 0))]
+    retention_days: f32,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(rename_all = "camelCase")]
+enum Storage {
+    Ephemeral {},
+    Persistent { size_gb: u32 },
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+enum Tier {
+    /// Shared infrastructure
+    Standard,
+    /// Dedicated infrastructure
+    Premium,
+}
+
+#[test]
+fn kubernetes_structural() {
+    test!(DatabaseSpec, SchemaSettings::kubernetes_structural()).assert_snapshot();
+}
+
+#[test]
+fn check_structural_schema() {
+    let schema = SchemaSettings::kubernetes_structural()
+        .into_generator()
+        .into_root_schema_for::<DatabaseSpec>();
+
+    assert_eq!(kubernetes::check(&schema), Vec::new());
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct Unsupported {
+    window: (u8, u8),
+    mode: Mode,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+enum Mode {
+    Off,
+    Limited(u32),
+}
+
+fn sorted_findings(schema: &Schema) -> Vec<(String, Issue)> {
+    let mut findings: Vec<_> = kubernetes::check(schema)
+        .into_iter()
+        .map(|Finding { path, issue, .. }| (path, issue))
+        .collect();
+    findings.sort_by(|a, b| a.0.cmp(&b.0));
+    findings
+}
+
+#[test]
+fn check_unsupported_constructs() {
+    let schema = SchemaSettings::kubernetes_structural()
+        .into_generator()
+        .into_root_schema_for::<Unsupported>();
+
+    assert_eq!(
+        sorted_findings(&schema),
+        vec![
+            ("/properties/mode".to_owned(), Issue::MissingType),
+            (
+                "/properties/mode/oneOf/0/type".to_owned(),
+                Issue::KeywordInJunctor {
+                    keyword: "type".to_owned()
+                }
+            ),
+            (
+                "/properties/mode/oneOf/1".to_owned(),
+                Issue::PropertyOnlyInJunctor {
+                    property: "Limited".to_owned()
+                }
+            ),
+            (
+                "/properties/mode/oneOf/1/properties/Limited/type".to_owned(),
+                Issue::KeywordInJunctor {
+                    keyword: "type".to_owned()
+                }
+            ),
+            (
+                "/properties/mode/oneOf/1/type".to_owned(),
+                Issue::KeywordInJunctor {
+                    keyword: "type".to_owned()
+                }
+            ),
+            ("/properties/window".to_owned(), Issue::MissingItems),
+            (
+                "/properties/window/prefixItems".to_owned(),
+                Issue::UnsupportedKeyword {
+                    keyword: "prefixItems".to_owned()
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn check_handwritten_schema() {
+    let schema = json_schema!({
+        "type": "object",
+        "properties": {
+            "port": {
+                "x-kubernetes-int-or-string": true,
+                "anyOf": [{ "type": "integer" }, { "type": "string" }]
+            },
+            "tags": {
+                "type": ["array", "null"],
+                "items": { "$ref": "#/$defs/Tag" }
+            },
+            "selector": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" }
+                },
+                "additionalProperties": { "type": "string" },
+                "allOf": [
+                    {
+                        "description": "Match by name",
+                        "properties": {
+                            "name": { "minLength": 1 },
+                            "namespace": {}
+                        }
+                    }
+                ]
+            },
+            "raw": true
+        },
+        "$defs": {
+            "Tag": { "type": "string" }
+        }
+    });
+
+    assert_eq!(
+        sorted_findings(&schema),
+        vec![
+            (
+                "/$defs".to_owned(),
+                Issue::UnsupportedKeyword {
+                    keyword: "$defs".to_owned()
+                }
+            ),
+            (
+                "/properties/raw".to_owned(),
+                Issue::BooleanSchema { value: true }
+            ),
+            (
+                "/properties/selector".to_owned(),
+                Issue::PropertiesWithAdditionalProperties
+            ),
+            (
+                "/properties/selector/allOf/0".to_owned(),
+                Issue::PropertyOnlyInJunctor {
+                    property: "namespace".to_owned()
+                }
+            ),
+            (
+                "/properties/selector/allOf/0/description".to_owned(),
+                Issue::KeywordInJunctor {
+                    keyword: "description".to_owned()
+                }
+            ),
+            ("/properties/tags".to_owned(), Issue::TypeNotString),
+            ("/properties/tags/items".to_owned(), Issue::MissingType),
+            (
+                "/properties/tags/items/$ref".to_owned(),
+                Issue::UnsupportedKeyword {
+                    keyword: "$ref".to_owned()
+                }
+            ),
+        ]
+    );
+
+    assert_eq!(
+        kubernetes::check(&json_schema!({ "type": "string" }))[0].to_string(),
+        "root schema must be an object schema"
+    );
+    assert_eq!(
+        sorted_findings(&schema)[4].1.to_string(),
+        "keyword \"description\" is not allowed within a logical junctor"
+    );
+}
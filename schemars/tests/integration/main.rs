@@ -1,18 +1,29 @@
 #![allow(clippy::disallowed_names)]
 
+mod alias;
 #[cfg(feature = "arrayvec07")]
 mod arrayvec;
+#[cfg(feature = "avro")]
+mod avro;
 mod bound;
+mod builder;
 #[cfg(feature = "bytes1")]
 mod bytes;
 #[cfg(feature = "chrono04")]
 mod chrono;
+// Expected source depends on properties being kept in field order
+#[cfg(all(feature = "codegen", feature = "uuid1", feature = "preserve_order"))]
+mod codegen;
+mod compat;
 mod contract;
+mod contract_definitions;
 mod crate_alias;
 #[cfg(any(feature = "rust_decimal1", feature = "bigdecimal04"))]
 mod decimal;
 mod default;
 mod deprecated;
+mod diff;
+mod discriminator;
 mod docs;
 #[cfg(feature = "either1")]
 mod either;
@@ -21,9 +32,11 @@ mod enums;
 mod enums_deny_unknown_fields;
 mod enums_flattened;
 mod enums_option_flattened;
+mod enums_other_variant;
 mod enums_ref_variants;
 mod enums_untagged_variant;
 mod examples;
+mod export;
 mod extend;
 mod flatten;
 mod from_into;
@@ -34,15 +47,24 @@ mod indexmap;
 mod inline_subschemas;
 #[cfg(feature = "jiff02")]
 mod jiff;
+mod kubernetes;
 mod macros;
 mod map;
+#[cfg(feature = "registry")]
+mod registry;
 mod remote_derive;
+mod resolve;
 mod same_name;
+#[cfg(feature = "sample")]
+mod sample;
 mod schema_name;
 mod schema_with;
 #[cfg(feature = "semver1")]
 mod semver;
+#[cfg(feature = "serde_with3")]
+mod serde_with;
 mod settings;
+mod simplify;
 mod skip;
 #[cfg(feature = "smallvec1")]
 mod smallvec;
@@ -50,13 +72,21 @@ mod smallvec;
 mod smol_str;
 mod std_types;
 mod structs;
+mod structured_output;
+#[cfg(feature = "tool")]
+mod tool;
 mod transform;
 mod transparent;
+// Expected declarations depend on properties being kept in field order
+#[cfg(all(feature = "typescript", feature = "preserve_order"))]
+mod typescript;
 mod unset;
 #[cfg(feature = "url2")]
 mod url;
 #[cfg(feature = "uuid1")]
 mod uuid;
+#[cfg(all(feature = "validate", feature = "uuid1"))]
+mod validate;
 mod validator;
 
 mod prelude {
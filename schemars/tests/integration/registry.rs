@@ -0,0 +1,93 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::export::Exporter;
+use schemars::{registry, SchemaGenerator};
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(export)]
+struct RegisteredStruct {
+    value: RegisteredEnum,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(export = "registry_test", rename = "RenamedEnum")]
+enum RegisteredEnum {
+    A,
+    B,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(transparent)]
+#[schemars(export = "registry_test")]
+struct RegisteredNewtype(RegisteredStruct);
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct NotRegistered;
+
+fn registered_names() -> Vec<String> {
+    registry::iter()
+        .filter(|r| r.schema_id().starts_with(module_path!()))
+        .map(|r| r.schema_name().into_owned())
+        .collect()
+}
+
+#[test]
+fn registry_iter() {
+    assert_eq!(
+        registered_names(),
+        ["RegisteredStruct", "RegisteredStruct", "RenamedEnum"]
+    );
+
+    let registered = registry::iter()
+        .find(|r| r.schema_name() == "RenamedEnum")
+        .unwrap();
+    assert_eq!(registered.group(), Some("registry_test"));
+    assert_eq!(
+        registered.schema_id(),
+        concat!(module_path!(), "::RenamedEnum")
+    );
+}
+
+#[test]
+fn registry_group() {
+    let ids = registry::group("registry_test")
+        .map(|r| r.schema_id())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        [
+            concat!(module_path!(), "::RegisteredStruct"),
+            concat!(module_path!(), "::RenamedEnum"),
+        ]
+    );
+}
+
+#[test]
+fn registry_generate_definitions() {
+    let mut generator = SchemaGenerator::default();
+    for registered in registry::group("registry_test") {
+        registered.subschema_fn()(&mut generator);
+    }
+
+    let mut names = generator.definitions().keys().collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, ["RegisteredStruct", "RenamedEnum"]);
+}
+
+#[test]
+fn registry_export() {
+    let files = Exporter::default()
+        .with_registered_types(Some("registry_test"))
+        .files();
+
+    let paths = files
+        .iter()
+        .map(|f| f.path.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, ["RegisteredStruct.json", "RenamedEnum.json"]);
+    assert_eq!(files[1].schema, schemars::schema_for!(RegisteredEnum));
+}
@@ -0,0 +1,83 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::generate::SchemaSettings;
+use schemars::{json_schema, schema_for, Schema, SchemaGenerator};
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "Node/Tree 🌳")]
+struct Node {
+    children: Vec<Node>,
+    parent: Option<Box<Node>>,
+    value: Value,
+}
+
+#[test]
+fn schema_resolve_ref() {
+    let schema = schema_for!(Node);
+    let reference = schema
+        .get_property("children")
+        .and_then(Schema::items)
+        .and_then(Schema::reference)
+        .unwrap();
+
+    assert_eq!(reference, "#");
+    assert_eq!(schema.resolve_ref(reference), Some(&schema));
+    assert_eq!(
+        schema.resolve_ref("#/properties/value"),
+        Some(&json_schema!(true))
+    );
+    assert_eq!(schema.resolve_ref("#/required"), None);
+}
+
+#[test]
+fn generator_resolve() {
+    for settings in [
+        SchemaSettings::draft2020_12(),
+        SchemaSettings::draft07(),
+        SchemaSettings::openapi3(),
+    ] {
+        let mut generator = SchemaGenerator::new(settings);
+        let schema = generator.subschema_for::<Node>();
+        let definition = generator.definitions()["Node/Tree 🌳"].clone();
+
+        assert!(schema.reference().is_some());
+        assert_eq!(
+            generator.resolve(&schema).map(Schema::as_value),
+            Some(&definition)
+        );
+
+        let inline = json_schema!({ "type": "string" });
+        assert_eq!(generator.resolve(&inline), Some(&inline));
+    }
+}
+
+#[test]
+fn generator_resolve_chains() {
+    let mut generator = SchemaGenerator::default();
+    let definitions = generator.definitions_mut();
+    definitions.insert(
+        "A".to_owned(),
+        json!({ "$ref": "#/$defs/B", "title": "ignored" }),
+    );
+    definitions.insert("B".to_owned(), json!({ "type": "integer" }));
+    definitions.insert("Cycle1".to_owned(), json!({ "$ref": "#/$defs/Cycle2" }));
+    definitions.insert("Cycle2".to_owned(), json!({ "$ref": "#/%24defs/Cycle1" }));
+    definitions.insert("Invalid".to_owned(), json!("not a schema"));
+
+    let resolve = |reference: &str| {
+        generator
+            .resolve(&Schema::new_ref(reference.to_owned()))
+            .cloned()
+    };
+
+    assert_eq!(
+        resolve("#/$defs/A"),
+        Some(json_schema!({ "type": "integer" }))
+    );
+    assert_eq!(resolve("#/$defs/Cycle1"), None);
+    assert_eq!(resolve("#/$defs/Invalid"), None);
+    assert_eq!(resolve("#/$defs/Missing"), None);
+    assert_eq!(resolve("#/definitions/B"), None);
+    assert_eq!(resolve("#"), None);
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use schemars::generate::{FullModulePath, SchemaSettings, ShortestUniqueModulePath};
 
 mod a {
     use super::*;
@@ -42,3 +43,92 @@ fn same_name() {
         .assert_allows_ser_roundtrip_default()
         .assert_matches_de_roundtrip(arbitrary_values());
 }
+
+#[test]
+fn same_name_full_module_path() {
+    test!(
+        Config2,
+        SchemaSettings::default().with_definition_namer(FullModulePath::default())
+    )
+    .assert_snapshot()
+    .assert_allows_ser_roundtrip_default()
+    .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[test]
+fn same_name_shortest_unique_module_path() {
+    test!(
+        Config2,
+        SchemaSettings::default().with_definition_namer(ShortestUniqueModulePath::default())
+    )
+    .assert_snapshot()
+    .assert_allows_ser_roundtrip_default()
+    .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[test]
+fn full_module_path_independent_of_order() {
+    let definition_names = |a_first: bool| {
+        let mut generator = SchemaSettings::default()
+            .with_definition_namer(FullModulePath::default())
+            .into_generator();
+        if a_first {
+            generator.subschema_for::<a::Config>();
+            generator.subschema_for::<b::Config>();
+        } else {
+            generator.subschema_for::<b::Config>();
+            generator.subschema_for::<a::Config>();
+        }
+        let mut names = generator.definitions().keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    assert_eq!(definition_names(true), definition_names(false));
+    assert_eq!(
+        definition_names(true),
+        [
+            "integration.same_name.a.Config",
+            "integration.same_name.b.Config"
+        ]
+    );
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+pub struct AFirst {
+    a_cfg: a::Config,
+    b_cfg: b::Config,
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+pub struct BFirst {
+    b_cfg: b::Config,
+    a_cfg: a::Config,
+}
+
+#[test]
+fn shortest_unique_module_path_independent_of_order() {
+    let settings =
+        SchemaSettings::default().with_definition_namer(ShortestUniqueModulePath::default());
+    let definition_names = |schema: schemars::Schema| {
+        let mut names = schema.as_object().unwrap()["$defs"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    let a_first = definition_names(
+        settings
+            .clone()
+            .into_generator()
+            .into_root_schema_for::<AFirst>(),
+    );
+    let b_first = definition_names(settings.into_generator().into_root_schema_for::<BFirst>());
+
+    assert_eq!(a_first, b_first);
+    assert_eq!(a_first, ["a.Config", "b.Config"]);
+}
@@ -0,0 +1,203 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::generate::SchemaSettings;
+use schemars::sample::Sampler;
+use schemars::{json_schema, schema_for};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct Struct {
+    #[schemars(range(min = 1, max = 10))]
+    small: u8,
+    large: u64,
+    signed: i16,
+    float: f32,
+    #[schemars(length(min = 2, max = 5))]
+    string: String,
+    character: char,
+    optional: Option<bool>,
+    #[serde(default)]
+    defaulted: i32,
+    tuple: (u8, String),
+    array: [i8; 3],
+    set: HashSet<u8>,
+    #[schemars(length(max = 2))]
+    list: Vec<Unit>,
+    int_map: BTreeMap<i64, bool>,
+    string_map: BTreeMap<String, ()>,
+    external: External,
+    internal: Internal,
+    adjacent: Adjacent,
+    untagged: Untagged,
+    #[serde(flatten)]
+    flattened: Flattened,
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+struct Unit;
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+struct Flattened {
+    flattened_value: Option<u16>,
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+enum External {
+    Unit,
+    Newtype(u32),
+    Tuple(bool, f64),
+    Struct { a: String },
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(tag = "tag")]
+enum Internal {
+    Unit,
+    Struct { a: Option<String> },
+    Newtype(Flattened),
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    Unit,
+    Newtype(Vec<u8>),
+    Struct { a: i8 },
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(untagged)]
+enum Untagged {
+    Number(u8),
+    Text(String),
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+struct Recursive {
+    value: u8,
+    children: Vec<Recursive>,
+    next: Option<Box<Recursive>>,
+}
+
+#[test]
+fn sample_struct() {
+    test!(Struct).assert_allows_de_samples(200);
+}
+
+#[test]
+fn sample_struct_draft07() {
+    test!(Struct, SchemaSettings::draft07()).assert_allows_de_samples(200);
+}
+
+#[test]
+fn sample_recursive() {
+    test!(Recursive).assert_allows_de_samples(200);
+}
+
+#[test]
+fn sample_is_deterministic() {
+    let schema = schema_for!(Struct);
+
+    let first = Sampler::new(&schema)
+        .with_seed(42)
+        .take(20)
+        .collect::<Vec<_>>();
+    let second = Sampler::new(&schema)
+        .with_seed(42)
+        .take(20)
+        .collect::<Vec<_>>();
+    let other = Sampler::new(&schema)
+        .with_seed(43)
+        .take(20)
+        .collect::<Vec<_>>();
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn sample_max_depth() {
+    let schema = schema_for!(Recursive);
+
+    for value in Sampler::new(&schema).with_max_depth(0).take(20) {
+        assert_eq!(value["children"], json!([]));
+        assert_eq!(value.get("next"), None);
+    }
+}
+
+#[test]
+fn sample_definitions() {
+    let mut generator = SchemaSettings::default().into_generator();
+    let schema = generator.subschema_for::<Recursive>();
+    let definitions = generator.definitions().clone();
+
+    let values = Sampler::new(&schema)
+        .with_definitions(&definitions)
+        .take(50)
+        .map(|v| serde_json::from_value::<Recursive>(v).map(|r| r.value))
+        .collect::<Result<BTreeSet<_>, _>>()
+        .unwrap();
+
+    assert!(values.len() > 1);
+}
+
+#[test]
+fn sample_unsatisfiable() {
+    let schema = json_schema!(false);
+    assert_eq!(Sampler::new(&schema).sample(), None);
+
+    let schema = json_schema!({
+        "type": "integer",
+        "minimum": 5,
+        "maximum": 4
+    });
+    assert_eq!(Sampler::new(&schema).sample(), None);
+
+    let schema = json_schema!({
+        "type": "object",
+        "properties": {
+            "never": false
+        },
+        "required": ["never"]
+    });
+    assert_eq!(Sampler::new(&schema).sample(), None);
+}
+
+#[test]
+fn sample_bounds() {
+    let schema = json_schema!({
+        "type": "integer",
+        "exclusiveMinimum": 10,
+        "maximum": 30,
+        "multipleOf": 7
+    });
+    let values = Sampler::new(&schema)
+        .take(50)
+        .map(|v| v.as_i64().unwrap())
+        .collect::<BTreeSet<_>>();
+    assert_eq!(values, BTreeSet::from([14, 21, 28]));
+
+    let schema = json_schema!({
+        "type": "number",
+        "minimum": 0.1,
+        "exclusiveMaximum": 0.5
+    });
+    for value in Sampler::new(&schema).take(50) {
+        let value = value.as_f64().unwrap();
+        assert!((0.1..0.5).contains(&value), "{value}");
+    }
+}
+
+#[cfg(feature = "proptest1")]
+mod proptest {
+    use super::*;
+    use proptest1::prelude::*;
+
+    proptest! {
+        #[test]
+        fn sample_strategy(value in schemars::sample::strategy(schema_for!(Struct))) {
+            prop_assert!(serde_json::from_value::<Struct>(value).is_ok());
+        }
+    }
+}
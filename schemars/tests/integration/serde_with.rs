@@ -0,0 +1,152 @@
+use crate::prelude::*;
+use serde_with3::base64::Base64;
+use serde_with3::formats::{CommaSeparator, Flexible, PreferMany};
+use serde_with3::{
+    serde_as, BoolFromInt, DisplayFromStr, DurationSeconds, NoneAsEmptyString, OneOrMany,
+    StringWithSeparator, TimestampSeconds, VecSkipError,
+};
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+use user::Same;
+
+#[serde_as(crate = "::serde_with3")]
+#[derive(JsonSchema, Serialize, Deserialize)]
+struct SerdeAs {
+    #[serde_as(as = "DisplayFromStr")]
+    display_from_str: u64,
+    #[serde_as(as = "Base64")]
+    base64: Vec<u8>,
+    #[serde_as(as = "DurationSeconds")]
+    duration: Duration,
+    #[serde_as(as = "DurationSeconds<f64, Flexible>")]
+    flexible_duration: Duration,
+    #[serde_as(as = "TimestampSeconds")]
+    timestamp: SystemTime,
+    #[serde_as(as = "OneOrMany<_>")]
+    one_or_many: Vec<String>,
+    #[serde_as(as = "OneOrMany<DisplayFromStr, PreferMany>")]
+    prefer_many: Vec<u32>,
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, u8>")]
+    separated: Vec<u8>,
+    #[serde_as(as = "VecSkipError<_>")]
+    skip_errors: Vec<u8>,
+    #[serde_as(as = "NoneAsEmptyString")]
+    none_as_empty: Option<String>,
+    #[serde_as(as = "BoolFromInt")]
+    bool_from_int: bool,
+    #[serde_as(as = "Option<BTreeMap<DisplayFromStr, _>>")]
+    #[serde(default)]
+    map: Option<BTreeMap<u8, bool>>,
+}
+
+#[test]
+fn serde_as() {
+    test!(SerdeAs)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip([SerdeAs {
+            display_from_str: 123,
+            base64: b"hello".to_vec(),
+            duration: Duration::from_secs(60),
+            flexible_duration: Duration::from_millis(1500),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            one_or_many: vec!["one".to_owned()],
+            prefer_many: vec![1, 2],
+            separated: vec![1, 2, 3],
+            skip_errors: vec![4, 5],
+            none_as_empty: Some("value".to_owned()),
+            bool_from_int: true,
+            map: Some(BTreeMap::from([(1, true)])),
+        }])
+        .assert_allows_ser_roundtrip([SerdeAs {
+            display_from_str: 0,
+            base64: Vec::new(),
+            duration: Duration::ZERO,
+            flexible_duration: Duration::ZERO,
+            timestamp: SystemTime::UNIX_EPOCH,
+            one_or_many: Vec::new(),
+            prefer_many: Vec::new(),
+            separated: Vec::new(),
+            skip_errors: Vec::new(),
+            none_as_empty: None,
+            bool_from_int: false,
+            map: None,
+        }])
+        .assert_allows_de_roundtrip([json!({
+            "display_from_str": "1",
+            "base64": "",
+            "duration": 0,
+            "flexible_duration": "1.5",
+            "timestamp": -1,
+            "one_or_many": "one",
+            "prefer_many": "2",
+            "separated": "",
+            "skip_errors": [1, "two", null],
+            "none_as_empty": "",
+            "bool_from_int": 0,
+        })])
+        .assert_rejects_de([json!({
+            "display_from_str": 1,
+            "base64": "",
+            "duration": 0,
+            "flexible_duration": 1,
+            "timestamp": 0,
+            "one_or_many": [],
+            "prefer_many": [],
+            "separated": "",
+            "skip_errors": [],
+            "none_as_empty": "",
+            "bool_from_int": 0,
+        })]);
+}
+
+#[derive(JsonSchema, Serialize, Deserialize, Default)]
+struct SchemarsWith {
+    #[serde(with = "serde_with3::As::<DisplayFromStr>")]
+    display_from_str: u64,
+    #[serde(with = "serde_with3::As::<OneOrMany<DisplayFromStr>>")]
+    #[schemars(with = "OneOrMany<DisplayFromStr>")]
+    one_or_many: Vec<u64>,
+    #[serde(with = "serde_with3::As::<BoolFromInt<Flexible>>")]
+    #[schemars(with = "BoolFromInt<Flexible>")]
+    bool_from_int: bool,
+}
+
+#[test]
+fn schemars_with() {
+    test!(SchemarsWith)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_de_roundtrip([json!({
+            "display_from_str": "1",
+            "one_or_many": ["1", "2"],
+            "bool_from_int": 5,
+        })])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+mod user {
+    // A user-defined type that happens to have the same name as `serde_with::Same`
+    pub type Same = serde_with3::DisplayFromStr;
+}
+
+#[serde_as(crate = "::serde_with3")]
+#[derive(JsonSchema, Serialize, Deserialize, Default)]
+struct UserSame {
+    #[serde_as(as = "Vec<user::Same>")]
+    serde_as: Vec<u8>,
+    #[serde(with = "serde_with3::As::<Vec<DisplayFromStr>>")]
+    #[schemars(with = "Vec<Same>")]
+    schemars_with: Vec<u8>,
+}
+
+#[test]
+fn user_type_named_same() {
+    test!(UserSame)
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_ser_roundtrip([UserSame {
+            serde_as: vec![1, 2],
+            schemars_with: vec![3],
+        }])
+        .assert_rejects_de([json!({ "serde_as": [1], "schemars_with": [] })]);
+}
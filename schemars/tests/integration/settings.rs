@@ -24,6 +24,22 @@ pub enum InnerEnum {
     ValueNewType(Value),
 }
 
+#[test]
+fn draft04() {
+    test!(OuterStruct, SchemaSettings::draft04())
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[test]
+fn draft06() {
+    test!(OuterStruct, SchemaSettings::draft06())
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
 #[test]
 fn draft07() {
     test!(OuterStruct, SchemaSettings::draft07())
@@ -78,3 +94,25 @@ fn openapi3() {
 
     assert_eq!(definitions1, &definitions2);
 }
+
+#[test]
+fn openapi3_1() {
+    let settings = SchemaSettings::openapi3_1();
+
+    test!(OuterStruct, settings.clone())
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_matches_de_roundtrip(arbitrary_values());
+
+    // Ensure that `take_definitions()` applies transforms correctly
+
+    let gen1 = settings.into_generator();
+    let definitions1 =
+        &Value::from(gen1.into_root_schema_for::<OuterStruct>())["components"]["schemas"];
+
+    let mut gen2 = SchemaSettings::openapi3_1().into_generator();
+    gen2.subschema_for::<OuterStruct>();
+    let definitions2 = Value::Object(gen2.take_definitions(true));
+
+    assert_eq!(definitions1, &definitions2);
+}
@@ -0,0 +1,143 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::generate::SchemaSettings;
+use schemars::transform::{Simplify, Transform};
+use schemars::Schema;
+use std::net::Ipv4Addr;
+
+#[derive(JsonSchema, Serialize, Deserialize, Default)]
+struct Outer {
+    #[serde(flatten)]
+    inner: Inner,
+    level: Level,
+    fallback: Option<Level>,
+    count: Option<u32>,
+    maybe_inner: Option<Inner>,
+}
+
+#[derive(JsonSchema, Serialize, Deserialize, Default)]
+struct Inner {
+    name: String,
+    tags: Vec<String>,
+}
+
+#[derive(JsonSchema, Serialize, Deserialize, Default)]
+enum Level {
+    #[default]
+    Low,
+    /// Medium priority
+    Medium,
+    High,
+}
+
+#[derive(JsonSchema, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Number(u8),
+    Text(String),
+    Flag(Option<bool>),
+}
+
+fn simplified(settings: SchemaSettings) -> SchemaSettings {
+    settings.with_transform(Simplify::default())
+}
+
+fn assert_idempotent<T: JsonSchema>(settings: SchemaSettings) {
+    let mut extract = Simplify::default();
+    extract.extract_repeated_subschemas = true;
+
+    for simplify in [Simplify::default(), extract] {
+        let schema = settings
+            .clone()
+            .with_transform(simplify.clone())
+            .into_generator()
+            .into_root_schema_for::<T>();
+
+        let mut simplified_again = schema.clone();
+        simplify.clone().transform(&mut simplified_again);
+
+        assert_eq!(schema, simplified_again);
+    }
+}
+
+#[test]
+fn simplify_struct() {
+    test!(Outer, simplified(SchemaSettings::default()))
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default()
+        .assert_allows_ser_roundtrip([Outer {
+            inner: Inner {
+                name: "name".to_owned(),
+                tags: vec!["a".to_owned()],
+            },
+            level: Level::High,
+            fallback: Some(Level::Medium),
+            count: Some(3),
+            maybe_inner: Some(Inner::default()),
+        }])
+        .assert_rejects_de([
+            json!({ "tags": [], "level": "Low" }),
+            json!({ "name": "", "tags": [], "level": "None" }),
+            json!({ "name": "", "tags": [], "level": "Low", "count": -1 }),
+            json!({ "name": "", "tags": [], "level": "Low", "fallback": "" }),
+        ])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[test]
+fn simplify_untagged_enum() {
+    test!(Untagged, simplified(SchemaSettings::default()))
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip([
+            Untagged::Number(1),
+            Untagged::Text("text".to_owned()),
+            Untagged::Flag(None),
+            Untagged::Flag(Some(true)),
+        ])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[test]
+fn simplify_openapi3() {
+    test!(Outer, simplified(SchemaSettings::openapi3())).assert_snapshot();
+}
+
+#[derive(JsonSchema, Serialize, Deserialize)]
+struct Hosts {
+    primary: Ipv4Addr,
+    fallbacks: Vec<Ipv4Addr>,
+    name: String,
+    alias: String,
+}
+
+#[test]
+fn simplify_extract_repeated_formats() {
+    let mut simplify = Simplify::default();
+    simplify.extract_repeated_subschemas = true;
+
+    test!(Hosts, SchemaSettings::default().with_transform(simplify))
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip([Hosts {
+            primary: Ipv4Addr::LOCALHOST,
+            fallbacks: vec![Ipv4Addr::UNSPECIFIED],
+            name: "name".to_owned(),
+            alias: String::new(),
+        }])
+        .assert_rejects_de([json!({
+            "primary": "localhost",
+            "fallbacks": [],
+            "name": "",
+            "alias": ""
+        })])
+        .assert_matches_de_roundtrip(arbitrary_values());
+}
+
+#[test]
+fn simplify_is_idempotent() {
+    assert_idempotent::<Outer>(SchemaSettings::default());
+    assert_idempotent::<Outer>(SchemaSettings::draft07());
+    assert_idempotent::<Outer>(SchemaSettings::openapi3());
+    assert_idempotent::<Untagged>(SchemaSettings::default());
+    assert_idempotent::<Hosts>(SchemaSettings::default());
+    assert_idempotent::<Schema>(SchemaSettings::default());
+}
@@ -0,0 +1,138 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::generate::SchemaSettings;
+use schemars::structured_output::{self, Finding, Issue};
+use schemars::transform::StrictStructuredOutput;
+use schemars::{json_schema, Schema};
+use std::collections::BTreeMap;
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+struct SearchRequest {
+    /// The search query
+    query: String,
+    filter: Option<Filter>,
+    limit: Option<u32>,
+    /// The order of results
+    #[serde(default)]
+    order: Order,
+    mode: Mode,
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+struct Filter {
+    tags: Vec<String>,
+    #[serde(default)]
+    after: Option<String>,
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+enum Order {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+#[derive(JsonSchema, Deserialize, Serialize, Default)]
+enum Mode {
+    /// Return results quickly
+    #[default]
+    Fast,
+    /// Search exhaustively
+    Thorough,
+}
+
+#[test]
+fn strict_structured_output() {
+    test!(SearchRequest, SchemaSettings::strict_structured_output())
+        .assert_snapshot()
+        .assert_allows_ser_roundtrip_default();
+}
+
+#[test]
+fn check_strict_schema() {
+    let schema = SchemaSettings::strict_structured_output()
+        .into_generator()
+        .into_root_schema_for::<SearchRequest>();
+
+    assert_eq!(structured_output::check(&schema), Vec::new());
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct Unsupported {
+    labels: BTreeMap<String, String>,
+    #[schemars(email)]
+    contact: String,
+    point: (i32, i32),
+    extra: Value,
+}
+
+fn sorted_findings(schema: &Schema, transform: &StrictStructuredOutput) -> Vec<(String, Issue)> {
+    let mut findings: Vec<_> = structured_output::check_with(schema, transform)
+        .into_iter()
+        .map(|Finding { path, issue, .. }| (path, issue))
+        .collect();
+    findings.sort_by(|a, b| a.0.cmp(&b.0));
+    findings
+}
+
+#[test]
+fn check_unsupported_constructs() {
+    let schema = SchemaSettings::strict_structured_output()
+        .into_generator()
+        .into_root_schema_for::<Unsupported>();
+
+    assert_eq!(
+        sorted_findings(&schema, &StrictStructuredOutput::default()),
+        [
+            ("/properties/extra", Issue::BooleanSchema { value: true }),
+            ("/properties/labels", Issue::AdditionalPropertiesNotFalse),
+            (
+                "/properties/point/prefixItems",
+                Issue::UnsupportedKeyword {
+                    keyword: "prefixItems".to_owned()
+                }
+            ),
+        ]
+        .map(|(path, issue)| (path.to_owned(), issue))
+    );
+}
+
+#[test]
+fn check_untransformed_schema() {
+    let schema = json_schema!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "format": "uuid" },
+                "parent": { "$ref": "#/$defs/Parent", "description": "The parent" }
+            },
+            "required": ["id"]
+        }
+    });
+
+    let mut transform = StrictStructuredOutput::default();
+    transform.allowed_keywords.insert("format".into());
+
+    assert_eq!(
+        sorted_findings(&schema, &transform),
+        [
+            ("", Issue::RootNotObject),
+            ("/items", Issue::AdditionalPropertiesNotFalse),
+            (
+                "/items",
+                Issue::PropertyNotRequired {
+                    property: "parent".to_owned()
+                }
+            ),
+            ("/items/properties/parent", Issue::RefWithSiblings),
+        ]
+        .map(|(path, issue)| (path.to_owned(), issue))
+    );
+
+    let finding = &structured_output::check(&schema)[..];
+    assert!(finding.iter().any(|f| f.to_string()
+        == "keyword \"format\" is not supported (at \"/items/properties/id/format\")"));
+}
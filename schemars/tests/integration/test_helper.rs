@@ -1,6 +1,7 @@
 use jsonschema::Validator;
 use pretty_assertions::assert_eq;
 use schemars::{
+    consts::meta_schemas,
     generate::{Contract, SchemaSettings},
     JsonSchema, Schema,
 };
@@ -146,19 +147,44 @@ impl<T> TestHelper<T> {
     }
 
     fn de_schema_validate(&self, instance: &Value) -> bool {
-        self.de_schema_validator
+        let is_valid = self
+            .de_schema_validator
             .get_or_init(|| build_validator(&self.de_schema))
-            .is_valid(instance)
+            .is_valid(instance);
+        check_builtin_validator(&self.de_schema, instance, is_valid);
+        is_valid
     }
 
     fn ser_schema_validate(&self, instance: &Value) -> bool {
-        self.ser_schema_validator
+        let is_valid = self
+            .ser_schema_validator
             .get_or_init(|| build_validator(&self.ser_schema))
-            .is_valid(instance)
+            .is_valid(instance);
+        check_builtin_validator(&self.ser_schema, instance, is_valid);
+        is_valid
     }
 }
 
+/// Checks that schemars' own validator agrees with the `jsonschema` crate.
+#[cfg(feature = "validate")]
+fn check_builtin_validator(schema: &Schema, instance: &Value, expected: bool) {
+    let result = schema.validate(instance);
+    assert_eq!(
+        result.is_ok(),
+        expected,
+        "`Schema::validate` disagrees with `jsonschema` for value {instance} (result: {result:?})"
+    );
+}
+
+#[cfg(not(feature = "validate"))]
+fn check_builtin_validator(_: &Schema, _: &Value, _: bool) {}
+
 fn build_validator(schema: &Schema) -> Validator {
+    let mut schema = schema.clone();
+    // OpenAPI 3.1 schemas use a dialect of JSON Schema 2020-12, which can't be retrieved offline
+    if schema.get("$schema").and_then(Value::as_str) == Some(meta_schemas::OPENAPI3_1) {
+        schema.remove("$schema");
+    }
     jsonschema::options()
         .should_validate_formats(true)
         .build(schema.as_value())
@@ -264,6 +290,18 @@ impl<T: Serialize + DeserializeOwned> TestHelper<T> {
         self
     }
 
+    /// Checks that values generated from the "deserialize" schema successfully deserialize and
+    /// pass validation.
+    #[cfg(feature = "sample")]
+    pub fn assert_allows_de_samples(&self, count: usize) -> &Self {
+        let samples = schemars::sample::Sampler::new(&self.de_schema)
+            .take(count)
+            .collect::<Vec<_>>();
+        assert_eq!(samples.len(), count, "schema could not be sampled");
+
+        self.assert_allows_de_roundtrip(samples)
+    }
+
     /// Checks that the "deserialize" schema allows only the given sample values that successfully
     /// deserialize and pass validation.
     ///
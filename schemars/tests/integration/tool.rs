@@ -0,0 +1,166 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::generate::SchemaSettings;
+use schemars::{json_schema, structured_output};
+
+#[derive(JsonSchema, Serialize)]
+struct Forecast {
+    temperatures: Vec<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warning: Option<String>,
+}
+
+/// # Forecast
+///
+/// Gets the weather forecast for a city.
+#[schemars::tool]
+#[allow(dead_code, clippy::needless_pass_by_value)]
+fn get_forecast(
+    /// The name of the city
+    city: String,
+    #[schemars(range(min = 1, max = 14))] days: Option<u8>,
+    #[serde(rename = "units")] unit: Unit,
+) -> Forecast {
+    _ = (city, days, unit);
+    Forecast {
+        temperatures: Vec::new(),
+        warning: None,
+    }
+}
+
+#[derive(JsonSchema, Deserialize)]
+enum Unit {
+    Celsius,
+    Fahrenheit,
+}
+
+#[test]
+fn tool_schema() {
+    let tool = get_forecast_tool_schema(&SchemaSettings::default());
+
+    assert_eq!(tool.name, "get_forecast");
+    assert_eq!(
+        tool.description.as_deref(),
+        Some("Gets the weather forecast for a city.")
+    );
+    assert_eq!(
+        tool.parameters,
+        json_schema!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Forecast",
+            "description": "Gets the weather forecast for a city.",
+            "type": "object",
+            "properties": {
+                "city": {
+                    "description": "The name of the city",
+                    "type": "string"
+                },
+                "days": {
+                    "type": ["integer", "null"],
+                    "format": "uint8",
+                    "minimum": 1,
+                    "maximum": 14
+                },
+                "units": {
+                    "$ref": "#/$defs/Unit"
+                }
+            },
+            "required": ["city", "units"],
+            "$defs": {
+                "Unit": {
+                    "type": "string",
+                    "enum": ["Celsius", "Fahrenheit"]
+                }
+            }
+        })
+    );
+    assert_eq!(
+        tool.returns,
+        json_schema!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Forecast",
+            "type": "object",
+            "properties": {
+                "temperatures": {
+                    "type": "array",
+                    "items": {
+                        "type": "number",
+                        "format": "double"
+                    }
+                },
+                "warning": {
+                    "type": ["string", "null"]
+                }
+            },
+            "required": ["temperatures"]
+        })
+    );
+}
+
+struct Store;
+
+#[allow(dead_code)]
+impl Store {
+    #[schemars::tool(rename_all = "camelCase")]
+    #[allow(clippy::unused_self)]
+    pub fn r#find<'a>(&self, item_name: &'a str, tags: &[&str]) -> Option<&'a str> {
+        _ = tags;
+        Some(item_name)
+    }
+
+    #[schemars::tool]
+    async fn clear(&mut self, #[cfg(any())] _unused: u8) {}
+}
+
+#[test]
+fn tool_schema_for_method() {
+    let tool = Store::find_tool_schema(&SchemaSettings::draft07());
+
+    assert_eq!(tool.name, "find");
+    assert_eq!(tool.description, None);
+    assert_eq!(
+        tool.parameters,
+        json_schema!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "find",
+            "type": "object",
+            "properties": {
+                "itemName": {
+                    "type": "string"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": {
+                        "type": "string"
+                    }
+                }
+            },
+            "required": ["itemName", "tags"]
+        })
+    );
+    assert_eq!(
+        tool.returns,
+        json_schema!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Nullable_string",
+            "type": ["string", "null"]
+        })
+    );
+
+    let tool = Store::clear_tool_schema(&SchemaSettings::default());
+
+    assert_eq!(tool.name, "clear");
+    assert_eq!(tool.parameters.get("properties"), None);
+    assert_eq!(tool.returns.get("type"), Some(&json!("null")));
+}
+
+#[test]
+fn tool_schema_strict_structured_output() {
+    let tool = get_forecast_tool_schema(&SchemaSettings::strict_structured_output());
+
+    assert_eq!(structured_output::check(&tool.parameters), Vec::new());
+    assert_eq!(
+        tool.parameters.get("required"),
+        Some(&json!(["city", "units", "days"]))
+    );
+}
@@ -0,0 +1,126 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::generate::SchemaSettings;
+use schemars::typescript::declarations_for;
+use schemars::SchemaGenerator;
+use std::collections::BTreeMap;
+
+/// An event.
+///
+/// Events are published to subscribers.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct Event {
+    id: u64,
+    /// The kind of event.
+    kind: EventKind,
+    payload: Payload,
+    tags: Vec<String>,
+    attributes: BTreeMap<String, i32>,
+    position: (f64, f64),
+    #[serde(rename = "parent-event")]
+    parent: Option<Box<Event>>,
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum EventKind {
+    Created,
+    Deleted,
+}
+
+#[derive(JsonSchema)]
+#[serde(tag = "type", content = "data")]
+#[allow(dead_code)]
+enum Payload {
+    /// A text payload.
+    Text(String),
+    Binary {
+        #[deprecated]
+        bytes: Vec<u8>,
+    },
+    Empty,
+}
+
+#[test]
+fn typescript_declarations() {
+    let mut generator = SchemaGenerator::default();
+    let declarations = declarations_for::<Event>(&mut generator);
+
+    assert_eq!(
+        declarations,
+        r#"/**
+ * An event.
+ *
+ * Events are published to subscribers.
+ */
+export interface Event {
+  id: number;
+  /** The kind of event. */
+  kind: EventKind;
+  payload: Payload;
+  tags: string[];
+  attributes: Record<string, number>;
+  position: [number, number];
+  "parent-event"?: Event | null;
+}
+
+export type EventKind = "Created" | "Deleted";
+
+export type Payload =
+  /** A text payload. */
+  | {
+      type: "Text";
+      data: string;
+    }
+  | {
+      type: "Binary";
+      data: {
+        /** @deprecated */
+        bytes: number[];
+      };
+    }
+  | {
+      type: "Empty";
+    };
+"#
+    );
+}
+
+#[derive(JsonSchema, Default)]
+#[allow(dead_code)]
+struct Settings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    theme: Option<String>,
+    #[serde(default)]
+    volume: u8,
+    #[serde(skip_deserializing)]
+    updated_by: String,
+    #[serde(skip_serializing)]
+    password: String,
+}
+
+#[test]
+fn typescript_contract() {
+    let mut de_generator = SchemaSettings::default().for_deserialize().into_generator();
+    let mut ser_generator = SchemaSettings::default().for_serialize().into_generator();
+
+    assert_eq!(
+        declarations_for::<Settings>(&mut de_generator),
+        r#"export interface Settings {
+  theme?: string | null;
+  volume?: number;
+  password: string;
+}
+"#
+    );
+    assert_eq!(
+        declarations_for::<Settings>(&mut ser_generator),
+        r#"export interface Settings {
+  theme?: string | null;
+  volume: number;
+  readonly updated_by: string;
+}
+"#
+    );
+}
@@ -0,0 +1,142 @@
+use crate::prelude::*;
+use pretty_assertions::assert_eq;
+use schemars::{json_schema, schema_for};
+use std::collections::BTreeSet;
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct Outer {
+    inner: Inner,
+    #[serde(flatten)]
+    flattened: Flattened,
+    children: Vec<Outer>,
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+#[serde(tag = "kind")]
+enum Inner {
+    Small {
+        #[schemars(range(max = 10))]
+        value: u32,
+    },
+    Large {
+        #[schemars(length(min = 1))]
+        name: String,
+    },
+}
+
+#[derive(JsonSchema, Deserialize, Serialize)]
+struct Flattened {
+    id: uuid1::Uuid,
+}
+
+// Error order follows property order, which depends on the `preserve_order` feature
+fn error_paths(schema: &schemars::Schema, instance: Value) -> BTreeSet<(String, String)> {
+    schema
+        .validate(&instance)
+        .unwrap_err()
+        .into_iter()
+        .map(|e| (e.instance_path, e.schema_path))
+        .collect()
+}
+
+fn paths<const N: usize>(paths: [(&str, &str); N]) -> BTreeSet<(String, String)> {
+    paths
+        .into_iter()
+        .map(|(i, s)| (i.to_owned(), s.to_owned()))
+        .collect()
+}
+
+#[test]
+fn validate_derived_schema() {
+    let schema = schema_for!(Outer);
+
+    let valid = json!({
+        "inner": { "kind": "Small", "value": 5 },
+        "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+        "children": [{
+            "inner": { "kind": "Large", "name": "x" },
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "children": []
+        }]
+    });
+    assert_eq!(schema.validate(&valid), Ok(()));
+
+    let invalid = json!({
+        "inner": { "kind": "Small", "value": 11 },
+        "id": "not-a-uuid",
+        "children": [{
+            "inner": { "kind": "Large", "name": "x" },
+            "children": [],
+            "unknown": true
+        }]
+    });
+
+    assert_eq!(
+        error_paths(&schema, invalid),
+        paths([
+            ("/children/0", "/properties/children/items/$ref/required"),
+            (
+                "/children/0/unknown",
+                "/properties/children/items/$ref/additionalProperties"
+            ),
+            ("/inner", "/properties/inner/$ref/oneOf"),
+            ("/id", "/properties/id/format"),
+        ])
+    );
+}
+
+#[test]
+fn validate_keywords() {
+    let schema = json_schema!({
+        "type": "object",
+        "properties": {
+            "int": { "type": "integer", "exclusiveMinimum": 0, "multipleOf": 3 },
+            "tuple": {
+                "type": "array",
+                "prefixItems": [{ "type": "string" }, { "const": 1.0 }],
+                "items": false
+            },
+            "map": {
+                "type": "object",
+                "propertyNames": { "pattern": "^[a-z]+$" },
+                "additionalProperties": { "enum": ["a", "b"] }
+            },
+            "choice": {
+                "anyOf": [
+                    { "type": "string", "format": "date-time" },
+                    { "type": "null" }
+                ]
+            }
+        },
+        "additionalProperties": false
+    });
+
+    let valid = json!({
+        "int": 3.0,
+        "tuple": ["x", 1],
+        "map": { "abc": "a" },
+        "choice": "2024-02-29T12:30:00Z"
+    });
+    assert_eq!(schema.validate(&valid), Ok(()));
+
+    let invalid = json!({
+        "int": 4,
+        "tuple": ["x", 1, 2],
+        "map": { "ABC": "c" },
+        "choice": "2023-02-29T12:30:00Z",
+        "other": null
+    });
+
+    assert_eq!(
+        error_paths(&schema, invalid),
+        paths([
+            ("/choice", "/properties/choice/anyOf"),
+            ("/int", "/properties/int/multipleOf"),
+            ("/map", "/properties/map/propertyNames/pattern"),
+            ("/map/ABC", "/properties/map/additionalProperties/enum"),
+            ("/tuple/2", "/properties/tuple/items"),
+            ("/other", "/additionalProperties"),
+        ])
+    );
+}
@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+
+#[derive(JsonSchema)]
+#[schemars(export = 1)]
+pub struct Struct1;
+
+#[derive(JsonSchema)]
+#[schemars(export, export = "group")]
+pub struct Struct2;
+
+#[derive(JsonSchema)]
+#[schemars(export)]
+pub struct Struct3<T> {
+    pub field: T,
+}
+
+fn main() {}
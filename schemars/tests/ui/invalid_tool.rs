@@ -0,0 +1,16 @@
+#[schemars::tool]
+pub fn generic<T>(value: T) -> T {
+    value
+}
+
+#[schemars::tool]
+pub fn destructured((a, b): (u8, u8)) -> u8 {
+    a + b
+}
+
+#[schemars::tool]
+pub fn impl_trait(value: impl ToString) -> String {
+    value.to_string()
+}
+
+fn main() {}
@@ -83,7 +83,7 @@ impl<'a> FromSerde for Field<'a> {
             serde_attrs: serde.attrs,
             ty: serde.ty,
             original: serde.original,
-            attrs: FieldAttrs::new(&serde.original.attrs, errors),
+            attrs: FieldAttrs::new(&serde.original.attrs, serde.ty, errors),
         }
     }
 }
@@ -85,6 +85,10 @@ impl Variant<'_> {
         Name(self.serde_attrs.name())
     }
 
+    pub fn aliases(&self) -> Aliases<'_> {
+        Aliases::new(self.serde_attrs.name(), self.serde_attrs.aliases())
+    }
+
     pub fn is_unit(&self) -> bool {
         matches!(self.style, serde_ast::Style::Unit)
     }
@@ -107,6 +111,10 @@ impl Field<'_> {
         Name(self.serde_attrs.name())
     }
 
+    pub fn aliases(&self) -> Aliases<'_> {
+        Aliases::new(self.serde_attrs.name(), self.serde_attrs.aliases())
+    }
+
     pub fn add_mutators(&self, mutators: &mut Vec<TokenStream>) {
         self.attrs.common.add_mutators(mutators);
         self.attrs.validation.add_mutators(mutators);
@@ -153,6 +161,45 @@ impl quote::ToTokens for Name<'_> {
     }
 }
 
+/// The names other than the deserialize name that are accepted when deserializing, which expands
+/// to a `&[&str]` expression that is empty for the serialize contract.
+pub struct Aliases<'a>(Vec<&'a str>);
+
+impl<'a> Aliases<'a> {
+    fn new(name: &serde_derive_internals::attr::Name, aliases: &'a BTreeSet<String>) -> Self {
+        let de_name = name.deserialize_name();
+        Aliases(
+            aliases
+                .iter()
+                .map(String::as_str)
+                .filter(|a| *a != de_name)
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl quote::ToTokens for Aliases<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let aliases = &self.0;
+        if aliases.is_empty() {
+            quote!(&[]).to_tokens(tokens);
+        } else {
+            quote! {
+                if #GENERATOR.contract().is_deserialize() {
+                    &[#(#aliases),*] as &[&str]
+                } else {
+                    &[]
+                }
+            }
+            .to_tokens(tokens);
+        }
+    }
+}
+
 fn with_contract_check(
     skip_deserializing: bool,
     skip_serializing: bool,
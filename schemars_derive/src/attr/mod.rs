@@ -2,6 +2,7 @@ mod custom_meta;
 mod doc;
 mod parse_meta;
 mod schemars_to_serde;
+mod serde_as;
 mod validation;
 
 use parse_meta::{
@@ -51,6 +52,7 @@ pub struct ContainerAttrs {
     pub inline: bool,
     pub ref_variants: bool,
     pub with: Option<WithAttr>,
+    pub export: Option<ExportAttr>,
 }
 
 #[derive(Default)]
@@ -59,6 +61,10 @@ pub struct VariantAttrs {
     pub with: Option<WithAttr>,
 }
 
+pub struct ExportAttr {
+    pub group: Option<LitStr>,
+}
+
 pub enum WithAttr {
     Type(Type),
     Function(Path),
@@ -239,9 +245,16 @@ impl CommonAttrs {
 }
 
 impl FieldAttrs {
-    pub fn new(attrs: &[Attribute], cx: &Ctxt) -> Self {
+    pub fn new(attrs: &[Attribute], ty: &Type, cx: &Ctxt) -> Self {
         let mut result = Self::default();
         result.populate(attrs, cx);
+        if let Some(WithAttr::Type(with_ty)) = &mut result.with {
+            if serde_as::is_as_type(with_ty) {
+                if let Some(serde_as_ty) = serde_as::field_type(attrs, ty) {
+                    *with_ty = serde_as_ty;
+                }
+            }
+        }
         result
     }
 
@@ -366,6 +379,16 @@ impl ContainerAttrs {
                 }
             },
 
+            "export" if cx.attr_type == "schemars" => match (&self.export, &meta) {
+                (Some(_), _) => cx.duplicate_error(&meta),
+                (None, CustomMeta::Path(_)) => self.export = Some(ExportAttr { group: None }),
+                (None, _) => {
+                    self.export = require_name_value_lit_str(meta, cx)
+                        .ok()
+                        .map(|group| ExportAttr { group: Some(group) });
+                }
+            },
+
             "_unstable_ref_variants" if cx.attr_type == "schemars" => {
                 if !matches!(data, Data::Enum(_)) {
                     cx.error_spanned_by(
@@ -13,10 +13,12 @@ pub(crate) static SERDE_KEYWORDS: &[&str] = &[
     "rename",
     "rename_all",
     "rename_all_fields",
+    "alias",
     "deny_unknown_fields",
     "tag",
     "content",
     "untagged",
+    "other",
     "default",
     "skip",
     "skip_serializing",
@@ -189,7 +191,7 @@ mod tests {
             #[misc]
             struct MyStruct {
                 /// blah blah blah
-                #[serde(skip_serializing_if = "some_fn", bound = "removed")]
+                #[serde(skip_serializing_if = "some_fn", bound = "removed", alias = "f1")]
                 field1: i32,
                 #[serde(serialize_with = "se", deserialize_with = "de")]
                 #[schemars(with = "with", bound = "bound")]
@@ -205,7 +207,7 @@ mod tests {
             #[serde(rename = "overriden", rename_all = "camelCase", default)]
             struct MyStruct {
                 #[doc = r" blah blah blah"]
-                #[serde(skip_serializing_if = "some_fn")]
+                #[serde(skip_serializing_if = "some_fn", alias = "f1")]
                 field1: i32,
                 #[schemars(with = "with", bound = "bound")]
                 #[serde(bound = "bound", serialize_with = "se")]
@@ -0,0 +1,269 @@
+use syn::{Attribute, GenericArgument, Lit, PathArguments, PathSegment, Type, TypePath};
+
+// `serde_with` adapters that (de)serialize a sequence, where the adapter's first type parameter
+// applies to each element of the sequence.
+const SEQUENCE_ADAPTERS: [&str; 2] = ["OneOrMany", "VecSkipError"];
+
+// The `#[serde_as]` attribute macro rewrites `#[serde_as(as = "T")]` into
+// `#[serde(with = "::serde_with::As::<T>")]`, where any `_` placeholders in `T` have been replaced
+// with `::serde_with::Same`, so the field's schema should be the schema of `T`. Each placeholder
+// stands for the corresponding part of the field's type, so this returns `T` from the
+// `serde_as` attribute (which the macro leaves on the field) with its placeholders replaced by
+// that type, e.g. `Option<OneOrMany<_>>` for a field of type `Option<Vec<u32>>` becomes
+// `Option<OneOrMany<u32>>`.
+//
+// Any placeholders that can't be matched up with part of the field's type are left unchanged.
+pub fn field_type(attrs: &[Attribute], field_ty: &Type) -> Option<Type> {
+    let mut as_ty = None;
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde_as")) {
+        // Any errors in the attribute are reported by the `#[serde_as]` macro
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("as") || meta.path.is_ident("r#as") {
+                as_ty = Some(meta.value()?.parse::<syn::LitStr>()?.parse::<Type>()?);
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<Lit>()?;
+            }
+            Ok(())
+        });
+    }
+
+    let mut as_ty = as_ty?;
+    replace(&mut as_ty, Some(field_ty));
+    Some(as_ty)
+}
+
+// Whether `ty` is `serde_with::As<T>`, which is what `#[serde_as(as = "T")]` sets as the field's
+// `#[serde(with = ...)]` type.
+pub fn is_as_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "As" && !s.arguments.is_none()),
+        _ => false,
+    }
+}
+
+fn replace(ty: &mut Type, field_ty: Option<&Type>) {
+    let field_ty = field_ty.map(ungroup);
+
+    if is_placeholder(ty) {
+        if let Some(field_ty) = field_ty {
+            *ty = field_ty.clone();
+        }
+        return;
+    }
+
+    match ty {
+        Type::Path(path) => replace_in_path(path, field_ty),
+        Type::Tuple(tuple) => {
+            let field_elems = match field_ty {
+                Some(Type::Tuple(f)) if f.elems.len() == tuple.elems.len() => {
+                    f.elems.iter().map(Some).collect()
+                }
+                _ => vec![None; tuple.elems.len()],
+            };
+            for (elem, field_elem) in tuple.elems.iter_mut().zip(field_elems) {
+                replace(elem, field_elem);
+            }
+        }
+        Type::Array(array) => {
+            let field_elem = match field_ty {
+                Some(Type::Array(f)) => Some(&*f.elem),
+                _ => None,
+            };
+            replace(&mut array.elem, field_elem);
+        }
+        Type::Group(group) => replace(&mut group.elem, field_ty),
+        Type::Paren(paren) => replace(&mut paren.elem, field_ty),
+        _ => {}
+    }
+}
+
+fn replace_in_path(path: &mut TypePath, field_ty: Option<&Type>) {
+    let Some(segment) = path.path.segments.last_mut() else {
+        return;
+    };
+    let PathArguments::AngleBracketed(args) = &mut segment.arguments else {
+        return;
+    };
+
+    let type_args: Vec<&mut Type> = args
+        .args
+        .iter_mut()
+        .filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect();
+
+    let field_args = match field_ty {
+        Some(Type::Path(field_path)) => type_args_of(field_path),
+        _ => Vec::new(),
+    };
+
+    if field_ty.is_some_and(|f| last_ident_eq(f, &segment.ident))
+        && field_args.len() == type_args.len()
+    {
+        // e.g. `Option<T>` applied to `Option<U>`
+        for (ty, field_ty) in type_args.into_iter().zip(field_args) {
+            replace(ty, Some(field_ty));
+        }
+    } else if SEQUENCE_ADAPTERS.iter().any(|a| segment.ident == a) && field_args.len() == 1 {
+        // e.g. `OneOrMany<T>` applied to `Vec<U>`
+        let mut type_args = type_args.into_iter();
+        if let Some(ty) = type_args.next() {
+            replace(ty, Some(field_args[0]));
+        }
+        for ty in type_args {
+            replace(ty, None);
+        }
+    } else {
+        for ty in type_args {
+            replace(ty, None);
+        }
+    }
+}
+
+// Placeholders are `_`, or the `Same` type from `serde_with`, which may be written as a bare `Same`
+// since this is only used within `serde_as` attributes.
+fn is_placeholder(ty: &Type) -> bool {
+    let is_segment = |segment: Option<&PathSegment>, name: &str| {
+        segment.is_some_and(|s| s.ident == name && s.arguments.is_none())
+    };
+
+    match ty {
+        Type::Infer(_) => true,
+        Type::Path(TypePath { qself: None, path }) => {
+            let mut segments = path.segments.iter().rev();
+            is_segment(segments.next(), "Same")
+                && match segments.next() {
+                    None => path.leading_colon.is_none(),
+                    parent => is_segment(parent, "serde_with") && segments.next().is_none(),
+                }
+        }
+        _ => false,
+    }
+}
+
+fn type_args_of(path: &TypePath) -> Vec<&Type> {
+    match path.path.segments.last().map(|s| &s.arguments) {
+        Some(PathArguments::AngleBracketed(args)) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn last_ident_eq(ty: &Type, ident: &syn::Ident) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|s| s.ident == *ident),
+        _ => false,
+    }
+}
+
+fn ungroup(mut ty: &Type) -> &Type {
+    loop {
+        match ty {
+            Type::Group(group) => ty = &group.elem,
+            Type::Paren(paren) => ty = &paren.elem,
+            _ => return ty,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn serde_as_type(attr: Attribute, field_ty: &Type) -> Option<Type> {
+        field_type(&[attr], field_ty)
+    }
+
+    #[test]
+    fn replaces_placeholders() {
+        assert_eq!(
+            serde_as_type(parse_quote!(#[serde_as(as = "_")]), &parse_quote!(u32)),
+            Some(parse_quote!(u32))
+        );
+        assert_eq!(
+            serde_as_type(
+                parse_quote!(#[serde_as(r#as = "Option<OneOrMany<_, PreferMany>>")]),
+                &parse_quote!(Option<std::vec::Vec<String>>)
+            ),
+            Some(parse_quote!(Option<OneOrMany<String, PreferMany>>))
+        );
+        assert_eq!(
+            serde_as_type(
+                parse_quote!(#[serde_as(no_default, as = "BTreeMap<DisplayFromStr, VecSkipError<Same>>")]),
+                &parse_quote!(BTreeMap<u64, Vec<(i32, bool)>>)
+            ),
+            Some(parse_quote!(BTreeMap<DisplayFromStr, VecSkipError<(i32, bool)>>))
+        );
+        assert_eq!(
+            serde_as_type(
+                parse_quote!(#[serde_as(as = "([serde_with::Same; 2], (DisplayFromStr, ::serde_with::Same))")]),
+                &parse_quote!(([u8; 2], (u16, u32)))
+            ),
+            Some(parse_quote!(([u8; 2], (DisplayFromStr, u32))))
+        );
+    }
+
+    #[test]
+    fn keeps_unmatched_placeholders() {
+        assert_eq!(
+            serde_as_type(
+                parse_quote!(#[serde_as(as = "Vec<_>")]),
+                &parse_quote!(HashSet<u32>)
+            ),
+            Some(parse_quote!(Vec<_>))
+        );
+        assert_eq!(
+            serde_as_type(
+                parse_quote!(#[serde_as(as = "(_, _)")]),
+                &parse_quote!((u8, u8, u8))
+            ),
+            Some(parse_quote!((_, _)))
+        );
+    }
+
+    #[test]
+    fn keeps_other_same_types() {
+        assert_eq!(
+            serde_as_type(
+                parse_quote!(#[serde_as(as = "(my_mod::Same, Same<u8>, serde_with::Same::Other)")]),
+                &parse_quote!((u8, u16, u32))
+            ),
+            Some(parse_quote!((
+                my_mod::Same,
+                Same<u8>,
+                serde_with::Same::Other
+            )))
+        );
+    }
+
+    #[test]
+    fn requires_serde_as_attribute() {
+        assert_eq!(
+            serde_as_type(
+                parse_quote!(#[serde(with = "Vec<Same>")]),
+                &parse_quote!(Vec<u8>)
+            ),
+            None
+        );
+        assert_eq!(
+            serde_as_type(
+                parse_quote!(#[serde_as(deserialize_as = "Vec<_>")]),
+                &parse_quote!(Vec<u8>)
+            ),
+            None
+        );
+    }
+}
@@ -19,6 +19,8 @@ mod bound;
 mod idents;
 mod name;
 mod schema_exprs;
+#[cfg(feature = "tool")]
+mod tool;
 
 use ast::Container;
 use idents::GENERATOR;
@@ -44,6 +46,44 @@ pub fn derive_json_schema_repr_wrapper(input: proc_macro::TokenStream) -> proc_m
         .into()
 }
 
+/// Attribute macro for generating schemas describing a function's parameters and return type.
+///
+/// This keeps the function unchanged, and adds a sibling function with the same name followed by
+/// `_tool_schema`, which returns a [`ToolSchema`](https://docs.rs/schemars/latest/schemars/struct.ToolSchema.html)
+/// for the given `SchemaSettings`.
+///
+/// The parameters schema is an object schema with a property for each of the function's
+/// parameters (excluding any `self` receiver), generated as if the parameters were fields of a
+/// struct deriving `JsonSchema`. This means that parameters can have doc comments and
+/// `#[schemars(...)]`/`#[serde(...)]` attributes, and `Option<T>` parameters are not required.
+/// Any arguments to the attribute (e.g. `#[schemars::tool(rename_all = "camelCase")]`) are
+/// treated as container attributes on that struct.
+///
+/// ```ignore
+/// /// Gets the weather forecast for a city.
+/// #[schemars::tool]
+/// fn get_forecast(
+///     /// The name of the city
+///     city: String,
+///     #[schemars(range(min = 1, max = 14))] days: Option<u8>,
+/// ) -> Forecast {
+///     // ...
+/// }
+///
+/// let tool = get_forecast_tool_schema(&SchemaSettings::default());
+/// ```
+#[cfg(feature = "tool")]
+#[proc_macro_attribute]
+pub fn tool(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as syn::ItemFn);
+    tool::expand_tool(&args.into(), input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 fn derive_json_schema(mut input: syn::DeriveInput, repr: bool) -> syn::Result<TokenStream> {
     attr::process_serde_attrs(&mut input)?;
 
@@ -59,6 +99,25 @@ fn derive_json_schema(mut input: syn::DeriveInput, repr: bool) -> syn::Result<To
 
     let (impl_generics, ty_generics, where_clause) = cont.generics.split_for_impl();
 
+    let register = match &cont.attrs.export {
+        Some(_) if !cont.generics.params.is_empty() => {
+            return Err(syn::Error::new(
+                cont.generics.span(),
+                "`#[schemars(export)]` cannot be used on generic types",
+            ));
+        }
+        Some(export) => {
+            let group = export.group.as_ref().map_or_else(
+                || quote!(::core::option::Option::None),
+                |group| quote!(::core::option::Option::Some(#group)),
+            );
+            Some(quote! {
+                schemars::_schemars_register!(#type_name, #group);
+            })
+        }
+        None => None,
+    };
+
     if let Some(ty) = get_transparent_type(&cont) {
         return Ok(quote! {
             const _: () = {
@@ -90,6 +149,8 @@ fn derive_json_schema(mut input: syn::DeriveInput, repr: bool) -> syn::Result<To
                         <#ty as schemars::JsonSchema>::_schemars_private_is_option()
                     }
                 };
+
+                #register
             };
         });
     }
@@ -174,6 +235,8 @@ fn derive_json_schema(mut input: syn::DeriveInput, repr: bool) -> syn::Result<To
                     #inline
                 }
             };
+
+            #register
         };
     })
 }
@@ -276,13 +276,26 @@ fn expr_for_external_tagged_enum<'a>(
     variants: impl Iterator<Item = &'a Variant<'a>>,
     deny_unknown_fields: bool,
 ) -> SchemaExpr {
-    let (unit_variants, complex_variants): (Vec<_>, Vec<_>) = variants.partition(|v| {
-        v.is_unit() && v.attrs.is_default() && !v.serde_attrs.untagged() && !cont.attrs.ref_variants
+    let variants: Vec<_> = variants.collect();
+    let other_tags = other_variant_tags(&variants);
+    let (unit_variants, complex_variants): (Vec<_>, Vec<_>) = variants.into_iter().partition(|v| {
+        v.is_unit()
+            && v.attrs.is_default()
+            && !v.serde_attrs.untagged()
+            && !v.serde_attrs.other()
+            && !cont.attrs.ref_variants
     });
     let add_unit_names = unit_variants.iter().map(|v| {
         let name = v.name();
+        let aliases = v.aliases();
+        let add_aliases = (!aliases.is_empty()).then(|| {
+            quote! {
+                enum_values.extend((#aliases).iter().map(|&alias| alias.into()));
+            }
+        });
         v.with_contract_check(quote! {
             enum_values.push((#name).into());
+            #add_aliases
         })
     });
     let unit_schema = SchemaExpr::from(quote!({
@@ -316,18 +329,16 @@ fn expr_for_external_tagged_enum<'a>(
             );
         }
 
-        let name = variant.name();
-
         let mut schema_expr =
             SchemaExpr::from(if variant.is_unit() && variant.attrs.with.is_none() {
-                quote! {
-                    schemars::_private::new_unit_enum_variant(#name)
-                }
+                expr_for_variant_tag(variant, &other_tags)
             } else {
+                let name = variant.name();
+                let aliases = variant.aliases();
                 let sub_schema =
                     expr_for_untagged_enum_variant(cont, variant, deny_unknown_fields, false);
                 quote! {
-                    schemars::_private::new_externally_tagged_enum_variant(#name, #sub_schema)
+                    schemars::_private::new_externally_tagged_enum_variant(#name, #aliases, #sub_schema)
                 }
             });
 
@@ -345,7 +356,16 @@ fn expr_for_internal_tagged_enum<'a>(
     tag_name: &str,
     deny_unknown_fields: bool,
 ) -> SchemaExpr {
+    let variants: Vec<_> = variants.collect();
+    let other_tags = other_variant_tags(&variants);
+    // The tag of an enum with untagged or catch-all variants doesn't determine which variant's
+    // schema applies, so it can't be used as a discriminator.
+    let discriminated = !variants
+        .iter()
+        .any(|v| v.serde_attrs.untagged() || v.serde_attrs.other());
+
     let variant_schemas = variants
+        .into_iter()
         .map(|variant| {
             if variant.serde_attrs.untagged() {
                 return (Some(variant), expr_for_untagged_enum_variant(cont, variant, deny_unknown_fields, true))
@@ -353,18 +373,32 @@ fn expr_for_internal_tagged_enum<'a>(
 
             let mut schema_expr = expr_for_internal_tagged_enum_variant(cont, variant, deny_unknown_fields);
 
-            let name = variant.name();
+            let tag_schema = expr_for_variant_tag(variant, &other_tags);
             schema_expr.mutators.push(quote!(
-                schemars::_private::apply_internal_enum_variant_tag(&mut #SCHEMA, #tag_name, #name, #deny_unknown_fields);
+                schemars::_private::apply_internal_enum_variant_tag(&mut #SCHEMA, #tag_name, #tag_schema, #deny_unknown_fields);
             ));
 
             variant.add_mutators(&mut schema_expr.mutators);
 
+            if discriminated && !cont.attrs.ref_variants {
+                schema_expr = discriminated_ref_variant(cont, variant, &schema_expr);
+            }
+
             (Some(variant), schema_expr)
         })
         .collect();
 
-    variant_subschemas(cont, true, variant_schemas)
+    let mut schema_expr = variant_subschemas(cont, true, variant_schemas);
+
+    if discriminated {
+        schema_expr.mutators.push(quote! {
+            if #GENERATOR.settings().internally_tagged_enum_discriminators {
+                schemars::_private::add_discriminator(#GENERATOR, &mut #SCHEMA, #tag_name);
+            }
+        });
+    }
+
+    schema_expr
 }
 
 fn expr_for_untagged_enum<'a>(
@@ -393,7 +427,10 @@ fn expr_for_adjacent_tagged_enum<'a>(
     content_name: &str,
     deny_unknown_fields: bool,
 ) -> SchemaExpr {
+    let variants: Vec<_> = variants.collect();
+    let other_tags = other_variant_tags(&variants);
     let schemas = variants
+        .into_iter()
         .map(|variant| {
             if variant.serde_attrs.untagged() {
                 return (
@@ -422,13 +459,7 @@ fn expr_for_adjacent_tagged_enum<'a>(
                 })
                 .unwrap_or_default();
 
-            let name = variant.name();
-            let tag_schema = quote! {
-                schemars::json_schema!({
-                    "type": "string",
-                    "const": #name,
-                })
-            };
+            let tag_schema = expr_for_variant_tag(variant, &other_tags);
 
             let set_additional_properties = if deny_unknown_fields {
                 quote! {
@@ -462,6 +493,41 @@ fn expr_for_adjacent_tagged_enum<'a>(
     variant_subschemas(cont, true, schemas)
 }
 
+// The names (including aliases) of all variants that can be deserialized from their own tag, i.e.
+// the tags that are not handled by a `#[serde(other)]` variant.
+fn other_variant_tags<'a>(variants: &[&'a Variant]) -> Vec<&'a str> {
+    variants
+        .iter()
+        .filter(|v| {
+            !v.serde_attrs.other()
+                && !v.serde_attrs.untagged()
+                && !v.serde_attrs.skip_deserializing()
+        })
+        .flat_map(|v| v.serde_attrs.aliases())
+        .map(String::as_str)
+        .collect()
+}
+
+// The schema for a variant's tag. For a `#[serde(other)]` variant, this accepts any string that
+// isn't the tag of another variant when deserializing.
+fn expr_for_variant_tag(variant: &Variant, other_tags: &[&str]) -> TokenStream {
+    let name = variant.name();
+    let aliases = variant.aliases();
+    let tag_schema = quote!(schemars::_private::new_unit_enum_variant(#name, #aliases));
+
+    if variant.serde_attrs.other() {
+        quote! {
+            if #GENERATOR.contract().is_deserialize() {
+                schemars::_private::new_catch_all_enum_variant(&[#(#other_tags),*])
+            } else {
+                #tag_schema
+            }
+        }
+    } else {
+        tag_schema
+    }
+}
+
 /// Callers must determine if all subschemas are mutually exclusive. The current behaviour is to
 /// assume that variants are mutually exclusive except for untagged enums.
 fn variant_subschemas(
@@ -510,11 +576,61 @@ fn enum_ref_variants(cont: &Container, variant: Option<&Variant>, expr: SchemaEx
         return expr;
     };
 
+    let (ty, type_def) = ref_variant_type(cont, variant, &expr, false);
+
+    let mut expr = SchemaExpr::from(quote!(#GENERATOR.subschema_for::<#ty>()));
+
+    expr.definitions.push(type_def);
+
+    expr
+}
+
+// Moves the variant into its own definition only when `internally_tagged_enum_discriminators` is
+// enabled, so that the enum's discriminator can map the variant's tag to the definition. The
+// definition's name is prefixed with the enum's name so that it doesn't clash with other types.
+fn discriminated_ref_variant(cont: &Container, variant: &Variant, expr: &SchemaExpr) -> SchemaExpr {
+    let (ty, type_def) = ref_variant_type(cont, variant, expr, true);
+
+    let mut expr = SchemaExpr::from(quote! {
+        if #GENERATOR.settings().internally_tagged_enum_discriminators {
+            #GENERATOR.subschema_for::<#ty>()
+        } else {
+            <#ty as schemars::JsonSchema>::json_schema(#GENERATOR)
+        }
+    });
+
+    expr.definitions.push(type_def);
+
+    expr
+}
+
+// Returns a type whose `JsonSchema` impl produces the given variant schema as a non-inlined
+// schema, along with the type's definition. The schema is named after the variant, optionally
+// prefixed with the enum's schema name.
+fn ref_variant_type(
+    cont: &Container,
+    variant: &Variant,
+    expr: &SchemaExpr,
+    prefix_enum_name: bool,
+) -> (TokenStream, TokenStream) {
     let cont_name = &cont.ident;
     // FIXME can this use serialize name where appropriate?
     let variant_name = variant.serde_attrs.name().deserialize_name();
     let (impl_generics, ty_generics, where_clause) = cont.generics.split_for_impl();
 
+    let schema_name = if prefix_enum_name {
+        quote! {
+            schemars::_private::alloc::borrow::Cow::Owned(
+                schemars::_private::alloc::format!(
+                    "{}_{}",
+                    <#cont_name #ty_generics as schemars::JsonSchema>::schema_name(),
+                    #variant_name,
+            ))
+        }
+    } else {
+        quote!(schemars::_private::alloc::borrow::Cow::Borrowed(#variant_name))
+    };
+
     let type_def = quote! {
         struct _SchemarsRefVariant<T: ?::core::marker::Sized>(::core::marker::PhantomData<T>);
 
@@ -524,7 +640,7 @@ fn enum_ref_variants(cont: &Container, variant: Option<&Variant>, expr: SchemaEx
             }
 
             fn schema_name() -> schemars::_private::alloc::borrow::Cow<'static, str> {
-                schemars::_private::alloc::borrow::Cow::Borrowed(#variant_name)
+                #schema_name
             }
 
             fn schema_id() -> schemars::_private::alloc::borrow::Cow<'static, str> {
@@ -542,13 +658,10 @@ fn enum_ref_variants(cont: &Container, variant: Option<&Variant>, expr: SchemaEx
         }
     };
 
-    let mut expr = SchemaExpr::from(
-        quote!(#GENERATOR.subschema_for::<_SchemarsRefVariant::<#cont_name #ty_generics>>()),
-    );
-
-    expr.definitions.push(type_def);
-
-    expr
+    (
+        quote!(_SchemarsRefVariant::<#cont_name #ty_generics>),
+        type_def,
+    )
 }
 
 // This function is also used for tagged variants, in which case the resulting SchemaExpr will be
@@ -712,6 +825,7 @@ fn expr_for_struct(
                 }
 
                 let name = field.name();
+                let aliases = field.aliases();
                 let (ty, type_def) = type_for_field_schema(cont, field);
 
                 if type_def.is_some() {
@@ -739,7 +853,7 @@ fn expr_for_struct(
                 let definitions = core::mem::take(&mut schema_expr.definitions);
                 field.with_contract_check(quote!({
                     #(#definitions)*
-                    schemars::_private::insert_object_property(&mut #SCHEMA, #name, #is_optional, #schema_expr);
+                    schemars::_private::insert_object_property(&mut #SCHEMA, #name, #aliases, #is_optional, #schema_expr);
                 }))
             }
         })
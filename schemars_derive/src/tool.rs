@@ -0,0 +1,151 @@
+use proc_macro2::{TokenStream, TokenTree};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{Attribute, FnArg, ItemFn, Pat, ReturnType, Type};
+
+// Attributes on parameters that only apply to the generated parameters struct
+const HELPER_ATTRIBUTES: [&str; 5] = ["doc", "schemars", "serde", "validate", "garde"];
+
+pub fn expand_tool(args: &TokenStream, mut input: ItemFn) -> syn::Result<TokenStream> {
+    let generics = &input.sig.generics;
+    if generics.type_params().next().is_some() || generics.const_params().next().is_some() {
+        return Err(syn::Error::new(
+            generics.span(),
+            "`#[schemars::tool]` cannot be used on generic functions",
+        ));
+    }
+
+    let mut fields = Vec::new();
+    for arg in &mut input.sig.inputs {
+        let FnArg::Typed(param) = arg else {
+            // Receivers (e.g. `&self`) are not included in the parameters schema
+            continue;
+        };
+
+        let ident = match &*param.pat {
+            Pat::Ident(pat) if pat.subpat.is_none() => &pat.ident,
+            pat => {
+                return Err(syn::Error::new(
+                    pat.span(),
+                    "`#[schemars::tool]` parameters must be identifiers",
+                ))
+            }
+        };
+
+        let (field_attrs, fn_attrs): (Vec<_>, Vec<_>) =
+            param.attrs.drain(..).partition(is_helper_attr);
+        let cfg_attrs = fn_attrs.iter().filter(|a| is_cfg_attr(a));
+        param.attrs.extend(fn_attrs.iter().cloned());
+
+        let ty = static_type(&param.ty)?;
+
+        fields.push(quote! {
+            #(#cfg_attrs)*
+            #(#field_attrs)*
+            #ident: #ty
+        });
+    }
+
+    let return_type = match &input.sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ty) => {
+            let ty = static_type(ty)?;
+            quote!(#ty)
+        }
+    };
+
+    let fn_name = &input.sig.ident;
+    let fn_name_str = fn_name.unraw().to_string();
+    let companion_name = format_ident!("{}_tool_schema", fn_name.unraw());
+    let vis = &input.vis;
+    let fn_docs = input.attrs.iter().filter(|a| a.path().is_ident("doc"));
+
+    let has_rename = args
+        .clone()
+        .into_iter()
+        .any(|tt| matches!(tt, TokenTree::Ident(i) if i == "rename"));
+    let rename_attr = (!has_rename).then(|| quote!(#[schemars(rename = #fn_name_str)]));
+    let args_attr = (!args.is_empty()).then(|| quote!(#[schemars(#args)]));
+
+    // The struct derives `JsonSchema` rather than calling `derive_json_schema` directly, so that
+    // the compiler evaluates any `#[cfg(...)]` attributes on its fields first.
+    let params_struct = quote! {
+        #[derive(schemars::JsonSchema)]
+        #(#fn_docs)*
+        #rename_attr
+        #args_attr
+        #[allow(dead_code)]
+        struct __SchemarsToolParameters {
+            #(#fields,)*
+        }
+    };
+
+    let companion_doc = format!(
+        "Returns the [`ToolSchema`](schemars::ToolSchema) describing the parameters and return \
+         type of `{fn_name_str}`."
+    );
+
+    Ok(quote! {
+        #input
+
+        #[doc = #companion_doc]
+        #vis fn #companion_name(
+            settings: &schemars::generate::SchemaSettings,
+        ) -> schemars::ToolSchema {
+            #params_struct
+
+            schemars::_private::new_tool_schema::<__SchemarsToolParameters, #return_type>(
+                #fn_name_str,
+                settings,
+            )
+        }
+    })
+}
+
+fn is_helper_attr(attr: &Attribute) -> bool {
+    HELPER_ATTRIBUTES
+        .iter()
+        .any(|name| attr.path().is_ident(name))
+}
+
+fn is_cfg_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident("cfg") || attr.path().is_ident("cfg_attr")
+}
+
+// The parameters struct is defined within a separate function, so it can't use any of the
+// original function's lifetimes. All lifetimes (including elided ones) are therefore replaced with
+// `'static`, which does not affect the generated schema.
+fn static_type(ty: &Type) -> syn::Result<Type> {
+    struct StaticLifetimes(Option<syn::Error>);
+
+    impl VisitMut for StaticLifetimes {
+        fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+            *lifetime = syn::Lifetime::new("'static", lifetime.span());
+        }
+
+        fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+            reference
+                .lifetime
+                .get_or_insert_with(|| syn::Lifetime::new("'static", reference.and_token.span));
+            visit_mut::visit_type_reference_mut(self, reference);
+        }
+
+        fn visit_type_impl_trait_mut(&mut self, impl_trait: &mut syn::TypeImplTrait) {
+            self.0.get_or_insert_with(|| {
+                syn::Error::new(
+                    impl_trait.span(),
+                    "`#[schemars::tool]` cannot be used on functions with `impl Trait` parameter or return types",
+                )
+            });
+        }
+    }
+
+    let mut ty = ty.clone();
+    let mut visitor = StaticLifetimes(None);
+    visitor.visit_type_mut(&mut ty);
+    match visitor.0 {
+        Some(err) => Err(err),
+        None => Ok(ty),
+    }
+}